@@ -0,0 +1,236 @@
+// benches/parse.rs
+
+// Timings for the parsers on piped/preseeded answers, with criterion - run
+// with `cargo bench`, which compares against the last run.
+//
+// Allocations are counted too, before criterion starts. Unlike timings
+// they're the same on every machine, so each case has a budget and the run
+// fails when one goes over - that's the part CI checks.
+
+use criterion::{Criterion, criterion_group};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use velvetio::{Parse, form, run_scripted};
+
+const TEXT: &str = "the quick brown fox jumps over the lazy dog";
+const PATH: &str = "/usr/local/share/velvetio/config.toml";
+const ANSWERS: [&str; 4] = ["Ada", "36", "y", "2"];
+
+// Counts every allocation (reallocations included) made by the process
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+struct Case {
+    name: &'static str,
+    // Most allocations one call may make
+    budget: usize,
+    run: fn(),
+}
+
+// Each answer arrives as a freshly read line, so both sides pay for that
+const STRINGS: &[Case] = &[
+    Case {
+        name: "String::parse",
+        budget: 2,
+        run: || {
+            let line = black_box(TEXT.to_string());
+            black_box(String::parse(&line).unwrap());
+        },
+    },
+    Case {
+        name: "String::parse_owned",
+        budget: 1,
+        run: || {
+            let line = black_box(TEXT.to_string());
+            black_box(String::parse_owned(line).unwrap());
+        },
+    },
+    Case {
+        name: "PathBuf::parse",
+        budget: 2,
+        run: || {
+            let line = black_box(PATH.to_string());
+            black_box(PathBuf::parse(&line).unwrap());
+        },
+    },
+    Case {
+        name: "PathBuf::parse_owned",
+        budget: 1,
+        run: || {
+            let line = black_box(PATH.to_string());
+            black_box(PathBuf::parse_owned(line).unwrap());
+        },
+    },
+];
+
+// Numbers shouldn't allocate at all. bool is matched like a menu answer (any
+// case, unambiguous prefixes), which lowercases every word.
+const SCALARS: &[Case] = &[
+    Case {
+        name: "bool::parse",
+        budget: 14,
+        run: || {
+            black_box(bool::parse(black_box(" Yes ")).unwrap());
+        },
+    },
+    Case {
+        name: "Option<u32>::parse",
+        budget: 0,
+        run: || {
+            black_box(Option::<u32>::parse(black_box("None")).unwrap());
+        },
+    },
+    Case {
+        name: "u64::parse",
+        budget: 0,
+        run: || {
+            black_box(u64::parse(black_box("18446744073709551615")).unwrap());
+        },
+    },
+    Case {
+        name: "i32::parse",
+        budget: 0,
+        run: || {
+            black_box(i32::parse(black_box("-2147483648")).unwrap());
+        },
+    },
+    Case {
+        name: "f64::parse",
+        budget: 0,
+        run: || {
+            black_box(f64::parse(black_box("3.14159265")).unwrap());
+        },
+    },
+];
+
+const COLLECTIONS: &[Case] = &[
+    Case {
+        name: "Vec<u32>::parse",
+        budget: 2,
+        run: || {
+            black_box(Vec::<u32>::parse(black_box("1, 2, 3, 5, 8, 13, 21, 34")).unwrap());
+        },
+    },
+    Case {
+        name: "(f64, f64)::parse",
+        budget: 1,
+        run: || {
+            black_box(<(f64, f64)>::parse(black_box("40.7,-74.0")).unwrap());
+        },
+    },
+    Case {
+        name: "(u8, u8, u8)::parse",
+        budget: 1,
+        run: || {
+            black_box(<(u8, u8, u8)>::parse(black_box("255 128 0")).unwrap());
+        },
+    },
+];
+
+// A whole form, answers fed in like a pipe would
+const FORMS: &[Case] = &[Case {
+    name: "form().collect()",
+    budget: 117,
+    run: || {
+        black_box(run_scripted(&ANSWERS, || {
+            form()
+                .text("name", "Name")
+                .number("age", "Age")
+                .boolean("admin", "Admin")
+                .choice("tier", "Tier", &["free", "pro"])
+                .collect()
+        }));
+    },
+}];
+
+const GROUPS: [(&str, &[Case]); 4] = [
+    ("strings", STRINGS),
+    ("scalars", SCALARS),
+    ("collections", COLLECTIONS),
+    ("forms", FORMS),
+];
+
+// Allocations per call for every case, and the ones over budget
+fn over_budget() -> Vec<String> {
+    const ROUNDS: usize = 1_000;
+    let mut over = Vec::new();
+    for case in GROUPS.iter().flat_map(|(_, cases)| cases.iter()) {
+        // Warm up, then count
+        (case.run)();
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        for _ in 0..ROUNDS {
+            (case.run)();
+        }
+        let allocs = (ALLOCATIONS.load(Ordering::Relaxed) - before) / ROUNDS;
+        let went_over = allocs > case.budget;
+        println!(
+            "{:<28} {:>4} allocs{}",
+            case.name,
+            allocs,
+            if went_over { "  OVER BUDGET" } else { "" }
+        );
+        if went_over {
+            over.push(format!(
+                "{} ({} allocs, budget {})",
+                case.name, allocs, case.budget
+            ));
+        }
+    }
+    over
+}
+
+fn timings(c: &mut Criterion) {
+    for (group, cases) in GROUPS {
+        let mut group = c.benchmark_group(group);
+        for case in cases {
+            group.bench_function(case.name, |b| b.iter(case.run));
+        }
+        group.finish();
+    }
+}
+
+criterion_group! {
+    name = parsers;
+    // Short runs - the parsers are fast and steady
+    config = Criterion::default()
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(2));
+    targets = timings
+}
+
+fn main() {
+    let over = over_budget();
+    if !over.is_empty() {
+        eprintln!("\nOver the allocation budget:");
+        for name in &over {
+            eprintln!("  {}", name);
+        }
+        std::process::exit(1);
+    }
+
+    parsers();
+    Criterion::default().configure_from_args().final_summary();
+}
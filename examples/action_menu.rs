@@ -0,0 +1,32 @@
+// examples/action_menu.rs
+// Pick an action from a menu, then fill in its parameters
+// Run: cargo run --example action_menu --features derive
+
+use velvetio::Interactive;
+
+#[derive(Interactive)]
+enum Action {
+    Deploy {
+        environment: String,
+        #[interactive(prompt = "How many replicas?")]
+        replicas: u32,
+    },
+    Rollback(String),
+    #[interactive(label = "Scale to zero")]
+    ScaleDown,
+    DryRun(String, #[interactive(prompt = "Verbose?")] bool),
+}
+
+fn main() {
+    match Action::interact("What do you want to do?") {
+        Action::Deploy {
+            environment,
+            replicas,
+        } => println!("Deploying {} replicas to {}", replicas, environment),
+        Action::Rollback(version) => println!("Rolling back to {}", version),
+        Action::ScaleDown => println!("Scaling to zero"),
+        Action::DryRun(version, verbose) => {
+            println!("Dry run of {} (verbose: {})", version, verbose)
+        }
+    }
+}
@@ -0,0 +1,22 @@
+// examples/gallery.rs
+// Every prompt kind, picked from a menu, with a theme switch
+// Run: cargo run --example gallery --all-features
+// Or straight to one: cargo run --example gallery -- Steps
+
+use velvetio::Demo;
+
+fn main() {
+    let gallery = Demo::gallery();
+    match std::env::args().nth(1) {
+        Some(name) => {
+            if !gallery.run_one(&name) {
+                eprintln!("No demo called {:?}. There's:", name);
+                for name in gallery.names() {
+                    eprintln!("  {}", name);
+                }
+                std::process::exit(2);
+            }
+        }
+        None => gallery.run(),
+    }
+}
@@ -23,7 +23,7 @@ fn main() {
     let dev_port = ask!("Default dev port" => u16, default: 3000);
 
     // try_ask! for optional input with fallback
-    let budget = ask!("Monthly budget (USD)" => f64, or: 0.0);
+    let budget = ask!("Monthly budget (USD)" => velvetio::Money, or: velvetio::Money::new(0, 2));
 
     // confirm! macro
     let use_docker = confirm!("Use Docker for development?");
@@ -93,8 +93,8 @@ fn main() {
     // Advanced validation with built-in validators
     let username = ask!(
         "Admin username",
-        validate: and(min_length(3), max_length(20)),
-        error: "Username must be 3-20 characters"
+        // Says "At least 3 characters, at most 20 characters" itself
+        validate: and_described(min_length_described(3), max_length_described(20))
     );
 
     let server_count = ask!(
@@ -132,8 +132,8 @@ fn main() {
     let api_timeout = ask!(
         "API timeout (seconds)" => u32,
         validate: or(
-            |&n| n == 30,  // Quick option
-            |&n| n >= 60 && n <= 300  // Custom range
+            |n: &u32| *n == 30,  // Quick option
+            |n: &u32| (60..=300).contains(n)  // Custom range
         ),
         error: "Use 30 for default, or 60-300 for custom"
     );
@@ -161,7 +161,7 @@ fn main() {
     println!("Team size: {}", team_size);
     println!("Editor: {} (dev port: {})", editor, dev_port);
     println!("OS: {}", os);
-    println!("Budget: ${:.2}", budget);
+    println!("Budget: ${}", budget);
     println!("Languages: {}", languages.join(", "));
 
     if !databases.is_empty() {
@@ -0,0 +1,15 @@
+// fuzz/fuzz_targets/parse_bool.rs
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use velvetio::Parse;
+
+fuzz_target!(|input: &str| {
+    // Anything accepted reads the same once printed and parsed again
+    if let Ok(value) = bool::parse(input) {
+        assert_eq!(bool::parse(&value.to_string()).unwrap(), value);
+    }
+    let _ = char::parse(input);
+    let _ = velvetio::match_name(input, &["PostgreSQL", "Postgres-only", "MySQL", "SQLite"]);
+});
@@ -0,0 +1,24 @@
+// fuzz/fuzz_targets/parse_list.rs
+
+// Lists pick their separator from what's in the answer, so mixed and nested
+// separators are where they'd go wrong.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use velvetio::Parse;
+
+fuzz_target!(|input: &str| {
+    let _ = Vec::<u32>::parse(input);
+    let _ = Vec::<f64>::parse(input);
+    let _ = Vec::<(u8, u8)>::parse(input);
+    let _ = Vec::<Option<i64>>::parse(input);
+
+    // Whatever the separator, items come back trimmed and non-empty
+    if let Ok(items) = Vec::<String>::parse(input) {
+        for item in &items {
+            assert!(!item.is_empty());
+            assert_eq!(item.trim(), item);
+        }
+    }
+});
@@ -0,0 +1,16 @@
+// fuzz/fuzz_targets/parse_option.rs
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use velvetio::Parse;
+
+fuzz_target!(|input: &str| {
+    // Blank answers are always None
+    if input.trim().is_empty() {
+        assert_eq!(Option::<u32>::parse(input).unwrap(), None);
+    }
+    let _ = Option::<String>::parse(input);
+    let _ = Option::<Vec<u32>>::parse(input);
+    let _ = Option::<(f32, f32)>::parse(input);
+});
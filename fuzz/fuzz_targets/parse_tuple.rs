@@ -0,0 +1,14 @@
+// fuzz/fuzz_targets/parse_tuple.rs
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use velvetio::Parse;
+
+fuzz_target!(|input: &str| {
+    let _ = <(u32, String)>::parse(input);
+    let _ = <(f64, f64)>::parse(input);
+    let _ = <(u8, u8, u8)>::parse(input);
+    let _ = <(String, Vec<u32>)>::parse(input);
+    let _ = <(bool, Option<char>, i16)>::parse(input);
+});
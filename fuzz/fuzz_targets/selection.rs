@@ -0,0 +1,36 @@
+// fuzz/fuzz_targets/selection.rs
+
+// Menu answers: a number or name for one pick, and "1,3,5" / "all" / "none"
+// for several. The first byte picks how many options the menu has.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use velvetio::__private::{pick_choice, pick_many};
+
+const NAMES: [&str; 6] = ["Linux", "macOS", "Windows", "FreeBSD", "Free DOS", "ÄÖÜ"];
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&count, rest)) = data.split_first() else {
+        return;
+    };
+    let Ok(input) = std::str::from_utf8(rest) else {
+        return;
+    };
+    let count = count as usize % 64;
+
+    // Picks are in range and never repeated
+    if let Ok(picked) = pick_many(input, count) {
+        let mut seen = vec![false; count];
+        for index in picked {
+            assert!(index < count);
+            assert!(!seen[index]);
+            seen[index] = true;
+        }
+    }
+
+    let names = &NAMES[..count.min(NAMES.len())];
+    if let Ok(index) = pick_choice(input, names) {
+        assert!(index < names.len());
+    }
+});
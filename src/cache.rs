@@ -0,0 +1,295 @@
+// src/cache.rs
+
+use crate::{Result, VelvetIOError, session};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+static CACHE: Mutex<Option<AnswerCache>> = Mutex::new(None);
+
+/// Last accepted answer per prompt key, stored as a flat JSON object
+#[derive(Debug, Clone)]
+pub struct AnswerCache {
+    path: PathBuf,
+    answers: HashMap<String, String>,
+}
+
+impl AnswerCache {
+    /// Load from disk - a missing or unreadable file just means an empty cache
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let answers = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| decode(&content))
+            .unwrap_or_default();
+
+        Self { path, answers }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.answers.get(key).map(|s| s.as_str())
+    }
+
+    pub fn set(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.answers.insert(key.into(), value.into());
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.answers.remove(key)
+    }
+
+    pub fn clear(&mut self) {
+        self.answers.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.answers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Write the cache back to disk, creating parent directories as needed
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, encode(&self.answers))?;
+        Ok(())
+    }
+}
+
+/// `~/.cache/<app>/velvetio.json` (or the platform equivalent)
+pub fn default_cache_path(app: &str) -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CACHE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("LOCALAPPDATA").map(PathBuf::from))
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+
+    Some(base.join(app).join("velvetio.json"))
+}
+
+/// Turn on answer caching for this app - previous answers become defaults
+pub fn enable_cache(app: &str) -> Result<()> {
+    let path = default_cache_path(app).ok_or_else(|| {
+        VelvetIOError::new(
+            "Cannot find a cache directory (set HOME or XDG_CACHE_HOME)",
+            app,
+            "cache directory",
+        )
+    })?;
+    enable_cache_at(path);
+    Ok(())
+}
+
+/// Turn on answer caching using a specific file
+pub fn enable_cache_at(path: impl Into<PathBuf>) {
+    *lock() = Some(AnswerCache::load(path));
+}
+
+/// Stop reading and writing cached answers
+pub fn disable_cache() {
+    *lock() = None;
+}
+
+/// Forget every cached answer (keeps caching enabled)
+pub fn clear_cache() -> Result<()> {
+    match lock().as_mut() {
+        Some(cache) => {
+            cache.clear();
+            cache.save()
+        }
+        None => Ok(()),
+    }
+}
+
+// A running `Session` brings its own cache, used instead of this one
+pub(crate) fn cached(key: &str) -> Option<String> {
+    if let Some(answer) = session::with_cache(|cache| cache?.get(key).map(|s| s.to_string())) {
+        return answer;
+    }
+    lock()
+        .as_ref()
+        .and_then(|cache| cache.get(key))
+        .map(|s| s.to_string())
+}
+
+// Saved right away so an aborted wizard still keeps the answers given so far.
+// Caching is best-effort: a read-only disk shouldn't break the prompt.
+pub(crate) fn remember(key: &str, value: &str) {
+    // Sensitive answers stay out of the file
+    if crate::redact::hidden(key) {
+        return;
+    }
+    let save = |cache: &mut AnswerCache| {
+        cache.set(key, value);
+        let _ = cache.save();
+    };
+    if session::with_cache(|cache| cache.map(save)).is_none()
+        && let Some(cache) = lock().as_mut()
+    {
+        save(cache);
+    }
+}
+
+fn lock() -> std::sync::MutexGuard<'static, Option<AnswerCache>> {
+    CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// Tiny JSON codec for a flat string map - not worth a serde dependency
+
+fn encode(answers: &HashMap<String, String>) -> String {
+    let mut keys: Vec<&String> = answers.keys().collect();
+    keys.sort();
+
+    let mut out = String::from("{\n");
+    for (i, key) in keys.iter().enumerate() {
+        out.push_str("  ");
+        push_json_string(&mut out, key);
+        out.push_str(": ");
+        push_json_string(&mut out, &answers[*key]);
+        if i + 1 < keys.len() {
+            out.push(',');
+        }
+        out.push('\n');
+    }
+    out.push_str("}\n");
+    out
+}
+
+// A list kept under one key, like the picks from a multi-select, as a JSON
+// array so labels with commas in them come back whole
+pub(crate) fn encode_list<S: AsRef<str>>(items: &[S]) -> String {
+    let mut out = String::from("[");
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        push_json_string(&mut out, item.as_ref());
+    }
+    out.push(']');
+    out
+}
+
+// `None` when it isn't a JSON array of strings
+pub(crate) fn decode_list(content: &str) -> Option<Vec<String>> {
+    let mut chars = content.chars().peekable();
+    let mut items = Vec::new();
+
+    skip_whitespace(&mut chars);
+    if chars.next()? != '[' {
+        return None;
+    }
+
+    loop {
+        skip_whitespace(&mut chars);
+        match chars.peek()? {
+            ']' => {
+                chars.next();
+                skip_whitespace(&mut chars);
+                return chars.peek().is_none().then_some(items);
+            }
+            ',' => {
+                chars.next();
+                continue;
+            }
+            _ => {}
+        }
+        items.push(read_json_string(&mut chars)?);
+    }
+}
+
+// The JSON helpers are shared with protocol mode
+pub(crate) fn push_json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn decode(content: &str) -> Option<HashMap<String, String>> {
+    let mut chars = content.chars().peekable();
+    let mut answers = HashMap::new();
+
+    skip_whitespace(&mut chars);
+    if chars.next()? != '{' {
+        return None;
+    }
+
+    loop {
+        skip_whitespace(&mut chars);
+        match chars.peek()? {
+            '}' => {
+                chars.next();
+                return Some(answers);
+            }
+            ',' => {
+                chars.next();
+                continue;
+            }
+            _ => {}
+        }
+
+        let key = read_json_string(&mut chars)?;
+        skip_whitespace(&mut chars);
+        if chars.next()? != ':' {
+            return None;
+        }
+        skip_whitespace(&mut chars);
+        let value = read_json_string(&mut chars)?;
+        answers.insert(key, value);
+    }
+}
+
+pub(crate) fn skip_whitespace(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) {
+    while chars.peek().is_some_and(|c| c.is_whitespace()) {
+        chars.next();
+    }
+}
+
+pub(crate) fn read_json_string(
+    chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+) -> Option<String> {
+    if chars.next()? != '"' {
+        return None;
+    }
+
+    let mut s = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(s),
+            '\\' => match chars.next()? {
+                'n' => s.push('\n'),
+                'r' => s.push('\r'),
+                't' => s.push('\t'),
+                'b' => s.push('\u{8}'),
+                'f' => s.push('\u{c}'),
+                'u' => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    let code = u32::from_str_radix(&hex, 16).ok()?;
+                    s.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+                }
+                other => s.push(other),
+            },
+            c => s.push(c),
+        }
+    }
+}
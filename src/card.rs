@@ -0,0 +1,142 @@
+// src/card.rs
+
+// Payment card numbers. They're checked with the Luhn checksum, which catches
+// nearly every single-digit typo and swapped pair, and never shown in full
+// once typed - Display and Debug both mask all but the last four digits.
+
+use crate::charset::Charset;
+use crate::core::{PromptOptions, ask_loop, give_up};
+use crate::{AttemptPolicy, MAX_ATTEMPTS, Parse, Result, VelvetIOError};
+use std::fmt;
+
+/// A card number that passed the Luhn check
+///
+/// Prints masked (`**** **** **** 4242`), so it's safe in summaries and logs.
+/// Use [`digits`](CardNumber::digits) for the full number.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct CardNumber {
+    digits: String,
+}
+
+impl CardNumber {
+    /// The full number, digits only
+    pub fn digits(&self) -> &str {
+        &self.digits
+    }
+
+    pub fn last_four(&self) -> &str {
+        &self.digits[self.digits.len() - 4..]
+    }
+
+    /// Card network guessed from the leading digits, like "Visa"
+    pub fn brand(&self) -> Option<&'static str> {
+        brand(&self.digits)
+    }
+}
+
+impl fmt::Display for CardNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "**** **** **** {}", self.last_four())
+    }
+}
+
+impl fmt::Debug for CardNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CardNumber({})", self)
+    }
+}
+
+impl Parse for CardNumber {
+    // Errors carry the answer masked, so the number never ends up in a log
+    fn parse(input: &str) -> Result<Self> {
+        let digits: String = input.chars().filter(|c| !matches!(c, ' ' | '-')).collect();
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(VelvetIOError::parse_error(masked(input), Self::type_name()));
+        }
+
+        let looks_like = brand(&digits)
+            .map(|brand| format!(" (looks like {})", brand))
+            .unwrap_or_default();
+        if !(12..=19).contains(&digits.len()) {
+            let message = format!("Card numbers have 12 to 19 digits{}", looks_like);
+            return Err(VelvetIOError::validation_error(masked(input), message));
+        }
+        if !luhn(&digits) {
+            let message = format!("That card number has a typo{}", looks_like);
+            return Err(VelvetIOError::validation_error(masked(input), message));
+        }
+        Ok(CardNumber { digits })
+    }
+
+    fn type_name() -> &'static str {
+        "card number"
+    }
+}
+
+/// Ask for a card number, grouped as `#### #### ####` while it's typed
+///
+/// Exits the program if input is closed before a valid answer arrives.
+pub fn ask_card(prompt: &str) -> CardNumber {
+    card_loop(prompt, AttemptPolicy::unlimited()).unwrap_or_else(|e| give_up(e))
+}
+
+/// `ask_card` that gives up after `MAX_ATTEMPTS` invalid answers
+pub fn try_ask_card(prompt: &str) -> Result<CardNumber> {
+    card_loop(prompt, AttemptPolicy::limited(MAX_ATTEMPTS))
+}
+
+fn card_loop(prompt: &str, policy: AttemptPolicy) -> Result<CardNumber> {
+    let options = PromptOptions {
+        allowed: Some(Charset::parse("0-9 -")),
+        group_digits: Some(4),
+        sensitive: true,
+        echo: Some(masked),
+        ..PromptOptions::with_policy(policy)
+    };
+    ask_loop(prompt, None, &|_: &CardNumber| true, "", &options).map(|(card, _)| card)
+}
+
+// Every digit but the last four hidden, for error messages and transcripts
+fn masked(input: &str) -> String {
+    let digits = input.chars().filter(|c| c.is_ascii_digit()).count();
+    let mut seen = 0;
+    input
+        .chars()
+        .map(|c| match c.is_ascii_digit() {
+            true => {
+                seen += 1;
+                if digits - seen < 4 { c } else { '*' }
+            }
+            false => c,
+        })
+        .collect()
+}
+
+fn luhn(digits: &str) -> bool {
+    let sum: u32 = digits
+        .chars()
+        .rev()
+        .filter_map(|c| c.to_digit(10))
+        .enumerate()
+        .map(|(i, d)| match i % 2 {
+            1 if d * 2 > 9 => d * 2 - 9,
+            1 => d * 2,
+            _ => d,
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+fn brand(digits: &str) -> Option<&'static str> {
+    let prefix = |n: usize| digits.get(..n).and_then(|p| p.parse::<u32>().ok());
+    match (prefix(1)?, prefix(2)?, prefix(4).unwrap_or(0)) {
+        (4, _, _) => Some("Visa"),
+        (_, 51..=55, _) | (_, _, 2221..=2720) => Some("Mastercard"),
+        (_, 34 | 37, _) => Some("American Express"),
+        (_, 65, _) | (_, _, 6011 | 6440..=6499) => Some("Discover"),
+        (_, _, 3528..=3589) => Some("JCB"),
+        (_, 36 | 38, _) | (_, _, 3000..=3059) => Some("Diners Club"),
+        (_, 62, _) => Some("UnionPay"),
+        _ => None,
+    }
+}
@@ -0,0 +1,50 @@
+// src/charset.rs
+
+/// Characters a prompt accepts, like `a-z0-9-`
+///
+/// Ranges are written `a-z`; a `-` at the start or end is a literal dash.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Charset {
+    ranges: Vec<(char, char)>,
+    spec: String,
+}
+
+impl Charset {
+    pub(crate) fn parse(spec: &str) -> Self {
+        let chars: Vec<char> = spec.chars().collect();
+        let mut ranges = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            if i + 2 < chars.len() && chars[i + 1] == '-' {
+                let (low, high) = (chars[i], chars[i + 2]);
+                ranges.push((low.min(high), low.max(high)));
+                i += 3;
+            } else {
+                ranges.push((chars[i], chars[i]));
+                i += 1;
+            }
+        }
+
+        Self {
+            ranges,
+            spec: spec.to_string(),
+        }
+    }
+
+    pub(crate) fn digits() -> Self {
+        Self::parse("0-9")
+    }
+
+    pub(crate) fn contains(&self, c: char) -> bool {
+        self.ranges.iter().any(|&(low, high)| low <= c && c <= high)
+    }
+
+    // For error messages
+    pub(crate) fn describe(&self) -> String {
+        if self.spec == "0-9" {
+            "Only digits are allowed".to_string()
+        } else {
+            format!("Only these characters are allowed: {}", self.spec)
+        }
+    }
+}
@@ -0,0 +1,159 @@
+// src/choice.rs
+
+use crate::Result;
+use std::fmt;
+use std::sync::Arc;
+
+/// A menu option shown as `label` that stands for `value`
+///
+/// ```no_run
+/// use velvetio::{Choice, choose_value};
+///
+/// #[derive(Clone)]
+/// enum Region { UsEast1, EuWest1 }
+///
+/// let region = choose_value("Deploy to", &[
+///     Choice::new("Production (us-east-1)", Region::UsEast1),
+///     Choice::new("Staging (eu-west-1)", Region::EuWest1),
+/// ]);
+/// ```
+///
+/// It works in any menu, since it displays as its label. In forms, use
+/// `form().choice_values(..)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Choice<V> {
+    label: String,
+    value: V,
+    detect: Option<Detect>,
+}
+
+impl<V> Choice<V> {
+    pub fn new(label: impl Into<String>, value: V) -> Self {
+        Self {
+            label: label.into(),
+            value,
+            detect: None,
+        }
+    }
+
+    /// Check the environment for this option before the menu is shown
+    ///
+    /// `choose_value` and `multi_select_values` mark options whose check
+    /// passes as "(detected)" and preselect them, so enter takes them.
+    ///
+    /// ```no_run
+    /// use velvetio::{Choice, choose_value};
+    /// # fn docker_installed() -> bool { true }
+    /// # fn podman_installed() -> bool { false }
+    ///
+    /// let runtime = choose_value("Container runtime", &[
+    ///     Choice::new("Docker", "docker").detect(docker_installed),
+    ///     Choice::new("Podman", "podman").detect(podman_installed),
+    ///     Choice::new("None", "none"),
+    /// ]);
+    /// // Choose (1-3) [Docker (detected)]
+    /// ```
+    pub fn detect(mut self, check: impl Fn() -> bool + Send + Sync + 'static) -> Self {
+        self.detect = Some(Detect(Arc::new(check)));
+        self
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+
+    pub fn into_value(self) -> V {
+        self.value
+    }
+}
+
+impl<V> fmt::Display for Choice<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+type DetectFn = dyn Fn() -> bool + Send + Sync;
+
+// Compared by identity, since closures can't be compared
+#[derive(Clone)]
+struct Detect(Arc<DetectFn>);
+
+impl fmt::Debug for Detect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Detect(..)")
+    }
+}
+
+impl PartialEq for Detect {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Pick one option by its label and get its value back
+///
+/// The first option whose `detect` check passes is the default. Exits the
+/// program if `choices` is empty - use `try_choose_value` for lists built at
+/// runtime.
+pub fn choose_value<V: Clone>(prompt: &str, choices: &[Choice<V>]) -> V {
+    let (shown, detected) = run_detection(choices);
+    match detected.first() {
+        Some(&index) => crate::choose_with_default(prompt, &shown, index).into_value(),
+        None => crate::choose(prompt, &shown).into_value(),
+    }
+}
+
+/// `try_choose` for labelled values
+pub fn try_choose_value<V: Clone>(prompt: &str, choices: &[Choice<V>]) -> Result<V> {
+    let (shown, detected) = run_detection(choices);
+    let picked = match detected.first() {
+        Some(&index) => crate::try_choose_with_default(prompt, &shown, index),
+        None => crate::try_choose(prompt, &shown),
+    };
+    picked.map(Choice::into_value)
+}
+
+/// Pick several options by label and get their values back
+///
+/// Options whose `detect` check passes are preselected.
+pub fn multi_select_values<V: Clone>(prompt: &str, choices: &[Choice<V>]) -> Vec<V> {
+    let (shown, detected) = run_detection(choices);
+    let picked = match detected.is_empty() {
+        true => crate::multi_select(prompt, &shown),
+        false => crate::multi_select_with_default(prompt, &shown, &detected),
+    };
+    picked.into_iter().map(Choice::into_value).collect()
+}
+
+/// `try_multi_select` for labelled values
+pub fn try_multi_select_values<V: Clone>(prompt: &str, choices: &[Choice<V>]) -> Result<Vec<V>> {
+    let (shown, detected) = run_detection(choices);
+    let picked = match detected.is_empty() {
+        true => crate::try_multi_select(prompt, &shown)?,
+        false => crate::try_multi_select_with_default(prompt, &shown, &detected)?,
+    };
+    Ok(picked.into_iter().map(Choice::into_value).collect())
+}
+
+// Runs each option's check once, right before the menu is shown. Options that
+// pass get "(detected)" on their label; their indices come back as defaults.
+pub(crate) fn run_detection<V: Clone>(choices: &[Choice<V>]) -> (Vec<Choice<V>>, Vec<usize>) {
+    let mut detected = Vec::new();
+    let shown = choices
+        .iter()
+        .enumerate()
+        .map(|(index, choice)| match &choice.detect {
+            Some(Detect(check)) if check() => {
+                detected.push(index);
+                Choice::new(format!("{} (detected)", choice.label), choice.value.clone())
+            }
+            _ => choice.clone(),
+        })
+        .collect();
+    (shown, detected)
+}
@@ -0,0 +1,77 @@
+// src/context.rs
+
+use crate::theme::with_theme;
+use std::sync::Mutex;
+
+static CONTEXT: Mutex<Context> = Mutex::new(Context {
+    labels: Vec::new(),
+    numbered: false,
+    asked: 0,
+});
+
+struct Context {
+    labels: Vec<String>,
+    numbered: bool,
+    asked: usize,
+}
+
+/// Run `f` with every prompt inside it prefixed by `label`
+///
+/// Contexts nest, so prompts read like `Database ▸ Replica ▸ Host`.
+///
+/// ```no_run
+/// use velvetio::prelude::*;
+///
+/// let (host, port) = velvetio::with_context("Database", || {
+///     (ask!("Host"), ask!("Port" => u16))
+/// });
+/// ```
+pub fn with_context<T>(label: &str, f: impl FnOnce() -> T) -> T {
+    lock().labels.push(label.to_string());
+    // Pops the label even if `f` panics
+    let _scope = Scope;
+    f()
+}
+
+struct Scope;
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        lock().labels.pop();
+    }
+}
+
+/// Number every question from here on (`3. Host`), starting again at 1
+pub fn number_prompts(on: bool) {
+    let mut context = lock();
+    context.numbered = on;
+    context.asked = 0;
+}
+
+// The prompt as shown, with context and number. Counts as one question asked,
+// so call it once per question rather than once per retry.
+pub(crate) fn label(prompt: &str) -> String {
+    let mut context = lock();
+    if context.labels.is_empty() && !context.numbered {
+        return prompt.to_string();
+    }
+
+    let separator = with_theme(|theme| format!(" {} ", theme.context_separator));
+    let mut label = context.labels.join(&separator);
+    if !label.is_empty() {
+        label.push_str(&separator);
+    }
+    label.push_str(prompt);
+
+    if context.numbered {
+        context.asked += 1;
+        label = format!("{}. {}", context.asked, label);
+    }
+    label
+}
+
+fn lock() -> std::sync::MutexGuard<'static, Context> {
+    CONTEXT
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -1,253 +1,2167 @@
 // src/core.rs
 
-use crate::{Parse, Result};
+use crate::charset::Charset;
+use crate::script::{self, errln, out, outln};
+use crate::{
+    AfterDeadline, AttemptPolicy, Choice, ErrorKind, FormData, Parse, PromptTiming, Result,
+    Validator, VelvetIOError, cache, context, deadline, debug, editor, headless, i18n, layout,
+    lock, markup, parser, protocol, redact, sanitize, session, term, theme, tty, validators,
+};
+use std::borrow::Cow;
+use std::cell::RefCell;
 use std::collections::HashMap;
-use std::io::{self, Write};
+use std::io::{self, BufRead, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// How many invalid answers the fallible prompts accept before giving up
+pub const MAX_ATTEMPTS: u32 = 5;
+
+// With `v2-strict`, closed input is always an error instead of an empty answer
+pub(crate) const STRICT: bool = cfg!(feature = "v2-strict");
 
 /// Keep asking until we get valid input
+///
+/// Exits the program if input is closed before a valid answer arrives.
 pub fn ask<T: Parse>(prompt: &str) -> T {
+    ask_or_exit(prompt, None, &accept_any, "").0
+}
+
+/// Ask with a stable key that identifies the prompt even if its wording changes
+///
+/// With caching enabled, the last answer for this key comes back as the default.
+pub fn ask_keyed<T: Parse>(key: &str, prompt: &str) -> T {
+    let _field = debug::field(key);
+    let cached = cache::cached(key).filter(|value| !value.is_empty());
+    let (value, raw) = ask_or_exit(prompt, cached.as_deref(), &accept_any, "");
+    cache::remember(key, &raw);
+    value
+}
+
+/// Keyed version of `try_ask` - one attempt, cached answer used on enter
+pub fn try_ask_keyed<T: Parse>(key: &str, prompt: &str) -> Result<T> {
+    let _field = debug::field(key);
+    let cached = cache::cached(key).filter(|value| !value.is_empty());
+    let (value, raw) = ask_loop(
+        prompt,
+        cached.as_deref(),
+        &accept_any,
+        "",
+        &single_attempt(),
+    )
+    .map_err(|e| e.with_key(key))?;
+    cache::remember(key, &raw);
+    Ok(value)
+}
+
+// Infallible prompts can't hand back an error, so closed input ends the program
+fn ask_or_exit<T: Parse>(
+    prompt: &str,
+    default: Option<&str>,
+    validator: &dyn Fn(&T) -> bool,
+    error_msg: &str,
+) -> (T, String) {
+    ask_loop(
+        prompt,
+        default,
+        validator,
+        error_msg,
+        &PromptOptions::default(),
+    )
+    .unwrap_or_else(|e| give_up(e))
+}
+
+// The previous answer is the default, and the editor starts with it
+fn edit_options(previous: &str, policy: AttemptPolicy) -> PromptOptions {
+    PromptOptions {
+        prefill: Some(previous.to_string()),
+        ..PromptOptions::with_policy(policy)
+    }
+}
+
+fn single_attempt() -> PromptOptions {
+    PromptOptions::with_policy(AttemptPolicy::limited(1))
+}
+
+pub(crate) fn accept_any<T>(_: &T) -> bool {
+    true
+}
+
+pub(crate) fn give_up(error: VelvetIOError) -> ! {
+    // A scripted test would go down with the program
+    if script::active() {
+        panic!("{}", theme::render_error(&error));
+    }
+    tty::restore_terminal();
+    match protocol::is_json_protocol() {
+        true => protocol::error(&error),
+        false => eprintln!("{}", theme::render_error(&error)),
+    }
+    std::process::exit(error.exit_code())
+}
+
+/// Run a multi-step wizard and exit with the right status when it ends
+///
+/// Exits with 0 when `steps` succeeds. Cancelling prints "Aborted." and exits
+/// with 130 like an interrupted command; any other error is printed and exits
+/// with its `exit_code()`. The terminal is restored first in every case.
+///
+/// ```no_run
+/// use velvetio::{run_wizard, try_ask, try_confirm};
+///
+/// run_wizard(|| {
+///     let name: String = try_ask("Project name")?;
+///     if try_confirm("Create it?")? {
+///         println!("Created {}", name);
+///     }
+///     Ok(())
+/// });
+/// ```
+pub fn run_wizard<F>(steps: F) -> !
+where
+    F: FnOnce() -> Result<()>,
+{
+    let result = steps();
+    tty::restore_terminal();
+    match result {
+        Ok(()) => std::process::exit(0),
+        Err(e) if e.is_cancelled() => {
+            eprintln!("Aborted.");
+            std::process::exit(e.exit_code())
+        }
+        Err(e) => give_up(e),
+    }
+}
+
+// `Port [8080]`, or however the theme shows defaults. Screen readers always
+// get it spelled out.
+pub(crate) fn with_default(prompt: &str, default: &dyn std::fmt::Display) -> String {
+    // The default and the theme's brackets are shown as they are, even if
+    // they look like markup
+    let default = default.to_string();
+    let default = markup::escape(&default);
+    theme::with_theme(|theme| {
+        let format = match theme.accessible {
+            true => "(default: {})",
+            false => theme.default_format.as_str(),
+        };
+        format!(
+            "{} {}",
+            prompt,
+            markup::escape(format).replace("{}", &default)
+        )
+    })
+}
+
+// `••••1234`: the last 4 characters at most, and never how long it is.
+// Short values are hidden completely.
+pub(crate) fn redacted(value: &str) -> String {
+    let dot = match tty::stdout_is_tty() && !term::unicode_terminal() {
+        true => "*",
+        false => "•",
+    };
+    let chars: Vec<char> = value.chars().collect();
+    let shown: String = match chars.len() {
+        0..=7 => String::new(),
+        len => chars[len - 4..].iter().collect(),
+    };
+    format!("{}{}", dot.repeat(4), shown)
+}
+
+fn yes_no(prompt: &str) -> String {
+    if theme::with_theme(|theme| theme.accessible) {
+        format!("{} (yes or no)", prompt)
+    } else {
+        format!("{} (y/n)", prompt)
+    }
+}
+
+pub(crate) fn warn(message: impl std::fmt::Display) {
+    let prefix = theme::with_theme(|theme| theme.warning_prefix.clone());
+    errln!("{} {}", prefix, message);
+}
+
+// Prints a rejected answer's error, rendered for the theme, and fires its alert
+pub(crate) fn report(error: &VelvetIOError) {
+    reported(error);
+}
+
+// `report`, saying whether it printed anything - minimal themes say each
+// error once per prompt
+fn reported(error: &VelvetIOError) -> bool {
+    if protocol::is_json_protocol() {
+        protocol::error(error);
+        return true;
+    }
+    let shown = theme::render_error(error);
+    let printed = !theme::with_theme(|theme| theme.minimal) || !said_before(error, &shown);
+    if printed {
+        errln!("{}", shown);
+    }
+    theme::with_theme(|theme| theme.alert).fire();
+    printed
+}
+
+thread_local! {
+    // Errors shown for the prompt being answered
+    static SAID: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+// A first attempt starts the list over, so a prompt asked again later
+// explains itself again
+fn said_before(error: &VelvetIOError, shown: &str) -> bool {
+    SAID.with_borrow_mut(|said| {
+        if error.attempt().is_none_or(|attempt| attempt <= 1) {
+            said.clear();
+        }
+        if said.iter().any(|s| s == shown) {
+            return true;
+        }
+        said.push(shown.to_string());
+        false
+    })
+}
+
+// Shared retry loop - `default` is the raw answer used when the user just hits enter.
+// Returns the parsed value along with the raw answer it came from.
+pub(crate) fn ask_loop<T: Parse>(
+    prompt: &str,
+    default: Option<&str>,
+    validator: &dyn Fn(&T) -> bool,
+    error_msg: &str,
+    options: &PromptOptions,
+) -> Result<(T, String)> {
+    let _turn = lock::hold();
+    let _hidden = redact::scope(options.secret || options.sensitive);
+    let redact_default = options.redact_default || redact::hidden(prompt);
+    let labelled = context::label(prompt);
+    let json = protocol::is_json_protocol();
+    let display = match default {
+        // Sent on its own instead
+        Some(_) if json => labelled,
+        // Already on the line, ready to edit
+        Some(_) if options.prefill.is_some() && editor::available() => labelled,
+        Some(default) if redact_default => with_default(&labelled, &redacted(default)),
+        Some(default) => with_default(&labelled, &default),
+        None => labelled,
+    };
+    let described;
+    let options = match default {
+        Some(default) if json => {
+            let default = match redact_default {
+                true => redacted(default),
+                false => default.to_string(),
+            };
+            described = PromptOptions {
+                details: Some(protocol::Details {
+                    default: Some(default),
+                    ..protocol::Details::default()
+                }),
+                ..options.clone()
+            };
+            &described
+        }
+        _ => options,
+    };
+
+    let _described = debug::describe(
+        prompt,
+        T::type_name(),
+        default
+            .as_ref()
+            .map(|default| default as &dyn std::fmt::Display),
+        error_msg,
+    );
+    let mut attempts = 0;
+    let mut ask = || loop {
+        debug::attempt(attempts + 1);
+        let (input, closed) = next_answer(&display, options)?;
+        let answer = match default {
+            Some(default) if input.is_empty() => default,
+            _ => input.as_str(),
+        };
+
+        let parsed = parser::with_none_words(options.none_words.as_deref(), || T::parse(answer));
+        let error = match (check_limits(answer, options), parsed) {
+            (Some(error), _) => error,
+            (None, Ok(value)) if validator(&value) => {
+                // The line we read doubles as the raw answer, no copy needed
+                let raw = match default {
+                    Some(default) if input.is_empty() => default.to_string(),
+                    _ => input,
+                };
+                return Ok((value, raw));
+            }
+            (None, Ok(_)) => VelvetIOError::validation_error(answer, error_msg),
+            (None, Err(e)) => e,
+        };
+
+        if closed {
+            return Err(VelvetIOError::eof());
+        }
+        if options.policy.max_attempts() == Some(1) {
+            return Err(error);
+        }
+        report(&error.during(prompt, attempts + 1));
+        out_of_attempts(&mut attempts, &options.policy)?;
+    };
+    let result = ask();
+    result.map_err(|e| e.during(prompt, attempts + 1))
+}
+
+// Line-mode fallback for limits the editor would have enforced while typing
+fn check_limits(answer: &str, options: &PromptOptions) -> Option<VelvetIOError> {
+    let length = answer.chars().count();
+    if let Some(max) = options.max_chars.filter(|&max| length > max) {
+        let message = format!("Keep it to {} characters (this was {})", max, length);
+        return Some(VelvetIOError::validation_error(answer, message));
+    }
+
+    let allowed = options.allowed.as_ref()?;
+    if answer.chars().all(|c| allowed.contains(c)) {
+        return None;
+    }
+    Some(VelvetIOError::validation_error(answer, allowed.describe()))
+}
+
+// Per-prompt settings the shared loops understand
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PromptOptions {
+    pub(crate) policy: AttemptPolicy,
+    pub(crate) secret: bool,
+    pub(crate) max_chars: Option<usize>,
+    pub(crate) allowed: Option<Charset>,
+    pub(crate) none_words: Option<Vec<String>>,
+    // Digits shown in groups of this size as they're typed, like card numbers
+    pub(crate) group_digits: Option<usize>,
+    // Tab completion, when the editor is running
+    pub(crate) complete: Option<editor::Completer>,
+    // Checked on every key, when the editor is running
+    pub(crate) live: Option<editor::LiveCheck>,
+    // Show no more of the default than its last few characters
+    pub(crate) redact_default: bool,
+    // Kept out of transcripts, caches and error messages
+    pub(crate) sensitive: bool,
+    // How a sensitive answer is written down instead, when not as `***`
+    pub(crate) echo: Option<fn(&str) -> String>,
+    // Text the editor starts with, for fixing up a previous answer
+    pub(crate) prefill: Option<String>,
+    // What protocol mode sends along with the prompt
+    pub(crate) details: Option<protocol::Details>,
+}
+
+impl PromptOptions {
+    pub(crate) fn with_policy(policy: AttemptPolicy) -> Self {
+        Self {
+            policy,
+            ..Self::default()
+        }
+    }
+}
+
+// Reads one trimmed line. End of input is an error so callers can stop asking.
+fn read_answer(prompt: &str) -> Result<String> {
+    read_line(prompt, &PromptOptions::default())
+}
+
+// `read_answer` for `Port [8080]`. Protocol mode sends the default on its own.
+fn read_with_default(prompt: &str, default: &dyn std::fmt::Display) -> Result<String> {
+    let labelled = context::label(prompt);
+    let default = shown_default(prompt, default);
+    if !protocol::is_json_protocol() {
+        return read_answer(&with_default(&labelled, &default));
+    }
+    let options = PromptOptions {
+        details: Some(protocol::Details {
+            default: Some(default),
+            ..protocol::Details::default()
+        }),
+        ..PromptOptions::default()
+    };
+    read_line(&labelled, &options)
+}
+
+// A default as the prompt shows it - hidden when the prompt is sensitive
+fn shown_default(prompt: &str, default: &dyn std::fmt::Display) -> String {
+    match redact::hidden(prompt) {
+        true => crate::REDACTED.to_string(),
+        false => default.to_string(),
+    }
+}
+
+// Reused for every answer, so reading a line doesn't allocate - only the
+// trimmed answer that's kept does, once per answer. Stdin itself is only
+// locked per read: holding it across prompts would block the editor and any
+// reads the app does itself.
+thread_local! {
+    static LINE: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+// How an input prompt is laid out for the theme: a line of its own if the
+// answer goes underneath, then what comes right before the answer. That's
+// `Port: ` by default, or `Port:` over `> ` with an input indicator.
+fn prompt_layout(prompt: &str) -> (Option<String>, String) {
+    let prompt = layout::hanging("", prompt);
+    theme::with_theme(|theme| match &theme.input_indicator {
+        Some(indicator) => (
+            Some(format!("{}{}", prompt, theme.prompt_suffix.trim_end())),
+            indicator.clone(),
+        ),
+        None => (None, format!("{}{}", prompt, theme.prompt_suffix)),
+    })
+}
+
+// The whole prompt as printed, up to where the answer starts
+pub(crate) fn prompt_shown(prompt: &str) -> String {
+    match prompt_layout(prompt) {
+        (Some(line), before) => format!("{}\n{}", line, before),
+        (None, before) => before,
+    }
+}
+
+// `??` isn't an answer: it shows the prompt's debug info, and the line is
+// read again
+fn read_line(prompt: &str, options: &PromptOptions) -> Result<String> {
+    loop {
+        let line = read_one_line(prompt, options)?;
+        if !debug::requested(&line) {
+            return Ok(line);
+        }
+        debug::show(prompt, options);
+    }
+}
+
+fn read_one_line(prompt: &str, options: &PromptOptions) -> Result<String> {
+    if deadline::expired() {
+        return Err(VelvetIOError::timeout());
+    }
+    let hide = |_: &str| crate::REDACTED.to_string();
+    let mask: Option<&dyn Fn(&str) -> String> = match options.echo {
+        Some(ref echo) => Some(echo),
+        None if redact::hidden(prompt) => Some(&hide),
+        None => None,
+    };
+    if script::active()
+        && let Some(answer) = script::answer(prompt_shown(prompt), options.secret, mask)
+    {
+        return answer
+            .map(|line| clean(&line))
+            .ok_or_else(VelvetIOError::eof);
+    }
+    // Injected answers are echoed, so the log shows what was picked
+    if let Some(answer) = headless::answer() {
+        let echoed = match (&answer, mask) {
+            (Some(_), _) if options.secret => String::new(),
+            (Some(answer), Some(mask)) if !answer.is_empty() => mask(answer),
+            (Some(answer), _) => answer.clone(),
+            (None, _) => String::new(),
+        };
+        outln!("{}{}", prompt_shown(prompt), echoed);
+        return answer
+            .map(|line| clean(&line))
+            .ok_or_else(VelvetIOError::eof);
+    }
+    let remote = session::with_transport(|transport| {
+        transport.write(&prompt_shown(prompt), false)?;
+        transport.read_line(options.secret)
+    });
+    if let Some(answer) = remote {
+        return answer?
+            .map(|line| clean(&line))
+            .ok_or_else(VelvetIOError::eof);
+    }
+    if protocol::is_json_protocol() {
+        return protocol::ask(prompt, options.secret, options.details.as_ref())
+            .map(|line| clean(&line));
+    }
+
+    // Limits are enforced (and digits grouped, and tab completes) as you type
+    // when the terminal lets us. Secrets are read there too, so Ctrl+Z puts
+    // echo back before the program stops instead of leaving it off.
+    let (line, before) = prompt_layout(prompt);
+    if let Some(line) = &line {
+        outln!("{}", line);
+    }
+    let edited = options.max_chars.is_some()
+        || options.allowed.is_some()
+        || options.group_digits.is_some()
+        || options.complete.is_some()
+        || options.live.is_some()
+        || options.prefill.is_some()
+        || options.secret;
+    if edited
+        && editor::available()
+        && let Some(answer) = editor::read(&before, options)
+    {
+        return answer.map(|line| clean(&line));
+    }
+
+    let mut out = io::stdout().lock();
+    let _ = write!(out, "{}", before);
+    let _ = out.flush();
+    drop(out);
+
+    read_stdin(options.secret, clean)?.ok_or_else(VelvetIOError::eof)
+}
+
+// A line from stdin, made into an answer by `finish` while it's still in the
+// buffer. `None` at end of input.
+pub(crate) fn read_stdin<R>(secret: bool, finish: impl FnOnce(&str) -> R) -> io::Result<Option<R>> {
+    let echo = secret.then(tty::ModeGuard::hide);
+    if deadline::threaded() {
+        let read = deadline::read_line();
+        if echo.is_some_and(|guard| guard.is_active()) {
+            println!();
+        }
+        return Ok(read?.map(|line| finish(&line)));
+    }
+    LINE.with_borrow_mut(|line| {
+        line.clear();
+        let read = io::stdin().lock().read_line(line);
+        if echo.is_some_and(|guard| guard.is_active()) {
+            // The user's enter wasn't echoed either
+            println!();
+        }
+        Ok((read? > 0).then(|| finish(line.trim_end_matches(['\n', '\r']))))
+    })
+}
+
+// What was typed for each field so far and how long it took, and the line
+// read last
+#[derive(Default)]
+pub(crate) struct Typed {
+    // `None` when only the timings are kept, as in a scripted run
+    lines: Option<HashMap<String, String>>,
+    // In the order the fields were first answered
+    pub(crate) timings: Vec<(String, PromptTiming)>,
+    last: Option<String>,
+    // Lines read for the field being asked
+    reads: u32,
+}
+
+impl Typed {
+    fn time(&mut self, key: &str, timing: PromptTiming) {
+        match self.timings.iter_mut().find(|(filed, _)| filed == key) {
+            Some((_, total)) => total.add(timing),
+            None => self.timings.push((key.to_string(), timing)),
+        }
+    }
+}
+
+thread_local! {
+    // Set while a form keeps what was typed, or a scripted run times fields
+    static TYPED: RefCell<Option<Typed>> = const { RefCell::new(None) };
+}
+
+// Collects what was typed for each field until `finish`, for `FormData::raw`,
+// and how long each took, for `FormData::timing` and scripted runs
+pub(crate) struct KeepTyped {
+    outer: Option<Typed>,
+    finished: bool,
+}
+
+impl KeepTyped {
+    pub(crate) fn start() -> Self {
+        Self::keeping(Some(HashMap::new()))
+    }
+
+    // Without the lines, which a scripted run has already
+    pub(crate) fn timings() -> Self {
+        Self::keeping(None)
+    }
+
+    fn keeping(lines: Option<HashMap<String, String>>) -> Self {
+        let typed = Typed {
+            lines,
+            ..Typed::default()
+        };
+        Self {
+            outer: TYPED.replace(Some(typed)),
+            finished: false,
+        }
+    }
+
+    // An outer recording (a scripted run around `collect_data`) gets the
+    // timings too
+    pub(crate) fn finish(mut self) -> Typed {
+        self.finished = true;
+        let typed = TYPED.replace(self.outer.take()).unwrap_or_default();
+        TYPED.with_borrow_mut(|outer| {
+            if let Some(outer) = outer {
+                for (key, timing) in &typed.timings {
+                    outer.time(key, *timing);
+                }
+            }
+        });
+        typed
+    }
+
+    // Files the line read last under `key`, and what answering it took. A
+    // field that took its answer without reading one (out of time) has
+    // nothing typed.
+    fn file(key: &str, elapsed: Duration) {
+        TYPED.with_borrow_mut(|typed| {
+            if let Some(typed) = typed {
+                if let Some(lines) = &mut typed.lines {
+                    match typed.last.take() {
+                        Some(line) => lines.insert(key.to_string(), line),
+                        None => lines.remove(key),
+                    };
+                }
+                let attempts = std::mem::take(&mut typed.reads);
+                typed.time(key, PromptTiming { elapsed, attempts });
+            }
+        });
+    }
+
+    // The line read last wasn't an answer, like a `??`
+    pub(crate) fn unread() {
+        TYPED.with_borrow_mut(|typed| {
+            if let Some(typed) = typed {
+                typed.last = None;
+                typed.reads = typed.reads.saturating_sub(1);
+            }
+        });
+    }
+
+    fn forget_last() {
+        TYPED.with_borrow_mut(|typed| {
+            if let Some(typed) = typed {
+                typed.last = None;
+                typed.reads = 0;
+            }
+        });
+    }
+}
+
+impl Drop for KeepTyped {
+    fn drop(&mut self) {
+        if !self.finished {
+            TYPED.set(self.outer.take());
+        }
+    }
+}
+
+// Sanitized (unless turned off) and trimmed. A form keeping what was typed
+// gets the line as it was.
+fn clean(line: &str) -> String {
+    TYPED.with_borrow_mut(|typed| {
+        if let Some(typed) = typed {
+            if typed.lines.is_some() {
+                typed.last = Some(line.to_string());
+            }
+            typed.reads += 1;
+        }
+    });
+    if !sanitize::enabled() {
+        return line.trim().to_string();
+    }
+    match sanitize::sanitize(line) {
+        Cow::Borrowed(line) => line.trim().to_string(),
+        // Trimmed where it is, rather than copied again
+        Cow::Owned(mut cleaned) => {
+            warn("Removed hidden characters from your answer");
+            cleaned.truncate(cleaned.trim_end().len());
+            cleaned.drain(..cleaned.len() - cleaned.trim_start().len());
+            cleaned
+        }
+    }
+}
+
+// Like `read_answer`, but on the infallible path (no attempt limit) closed input
+// reads as one last empty answer, the way it always has. The flag tells the
+// caller to stop if that answer doesn't work out. `v2-strict` turns this off.
+pub(crate) fn next_answer(prompt: &str, options: &PromptOptions) -> Result<(String, bool)> {
+    match read_line(prompt, options) {
+        Ok(input) => Ok((input, false)),
+        Err(e) if e.is_eof() && options.policy.max_attempts().is_none() && !STRICT => {
+            Ok((String::new(), true))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+// Counts an invalid answer, then either pauses as the policy says or gives up
+pub(crate) fn out_of_attempts(attempts: &mut u32, policy: &AttemptPolicy) -> Result<()> {
+    *attempts += 1;
+    if policy.max_attempts().is_some_and(|max| *attempts >= max) {
+        let mut error = VelvetIOError::too_many_attempts(*attempts);
+        if let Some(message) = policy.lockout() {
+            error.message = message.to_string();
+        }
+        return Err(error);
+    }
+
+    std::thread::sleep(policy.delay_after(*attempts));
+    Ok(())
+}
+
+/// Optional typed answer - enter gives `None`, anything else has to parse as `T`
+///
+/// Unlike asking for `Option<T>`, words like "none" or "-" aren't special here,
+/// they're parsed as `T` like any other answer.
+/// Exits the program if input is closed in `v2-strict` mode.
+pub fn ask_opt<T: Parse>(prompt: &str) -> Option<T> {
+    let prompt = format!("{} (optional)", prompt);
+    ask_or_exit::<Blank<T>>(&prompt, None, &accept_any, "").0.0
+}
+
+/// Try once for an optional typed answer - enter gives `Ok(None)`
+pub fn try_ask_opt<T: Parse>(prompt: &str) -> Result<Option<T>> {
+    let prompt = format!("{} (optional)", prompt);
+    ask_loop::<Blank<T>>(&prompt, None, &accept_any, "", &single_attempt())
+        .map(|(value, _)| value.0)
+}
+
+// Empty means None, anything else has to parse - no keyword guessing
+struct Blank<T>(Option<T>);
+
+impl<T: Parse> Parse for Blank<T> {
+    fn parse(input: &str) -> Result<Self> {
+        if input.trim().is_empty() {
+            Ok(Blank(None))
+        } else {
+            T::parse(input).map(|value| Blank(Some(value)))
+        }
+    }
+
+    fn type_name() -> &'static str {
+        T::type_name()
+    }
+}
+
+/// Try once, return Result instead of retrying
+pub fn try_ask<T: Parse>(prompt: &str) -> Result<T> {
+    let _turn = lock::hold();
+    let _described = debug::describe(prompt, T::type_name(), None, "");
+    read_answer(&context::label(prompt))
+        .and_then(T::parse_owned)
+        .map_err(|e| e.during(prompt, 1))
+}
+
+/// Ask again, starting from the previous answer
+///
+/// On a terminal the line starts out holding `previous`, ready to edit with
+/// the arrow keys and backspace. Otherwise `previous` is shown as the default,
+/// so enter keeps it and anything typed replaces it.
+///
+/// Exits the program if input is closed before a valid answer arrives.
+///
+/// ```no_run
+/// let mut name: String = velvetio::ask("Project name");
+/// while !velvetio::confirm(&format!("Create {}?", name)) {
+///     name = velvetio::ask_edit("Project name", &name);
+/// }
+/// ```
+pub fn ask_edit<T: Parse>(prompt: &str, previous: &str) -> T {
+    let options = edit_options(previous, AttemptPolicy::default());
+    ask_loop(prompt, Some(previous), &accept_any, "", &options)
+        .unwrap_or_else(|e| give_up(e))
+        .0
+}
+
+/// Try once, starting from the previous answer - see [`ask_edit`]
+pub fn try_ask_edit<T: Parse>(prompt: &str, previous: &str) -> Result<T> {
+    let options = edit_options(previous, AttemptPolicy::limited(1));
+    ask_loop(prompt, Some(previous), &accept_any, "", &options).map(|(value, _)| value)
+}
+
+/// Ask with validation function
+///
+/// Exits the program if input is closed before a valid answer arrives.
+///
+/// A validator that describes itself, like
+/// [`min_length_described`](crate::min_length_described), shows that as a
+/// hint, and says it when an answer fails if there's no `error_message`.
+pub fn ask_with_validation<T: Parse, V>(
+    prompt: &str,
+    validator: V,
+    error_message: Option<&str>,
+) -> T
+where
+    V: Validator<T>,
+{
+    let _turn = lock::hold();
+    show_hint(&validator);
+    let error_msg = validators::error_message(&validator, error_message);
+    ask_or_exit(
+        prompt,
+        None,
+        &|value: &T| validator.check(value),
+        &error_msg,
+    )
+    .0
+}
+
+/// Ask with default - hit enter to use default
+///
+/// An answer that doesn't parse is reported and asked again, so a typo never
+/// silently turns into the default. Closed input also gives the default.
+pub fn ask_with_default<T: Parse + std::fmt::Display + Clone>(prompt: &str, default: T) -> T {
+    default_loop(prompt, default, false, &accept_any, "")
+}
+
+/// Old `ask_with_default` behavior - an answer that doesn't parse is reported
+/// and replaced by the default instead of being asked again
+pub fn ask_with_default_lenient<T: Parse + std::fmt::Display + Clone>(
+    prompt: &str,
+    default: T,
+) -> T {
+    default_loop(prompt, default, true, &accept_any, "")
+}
+
+/// Ask with a default that has to pass validation too - hit enter to use it
+///
+/// A default that fails the validator is rejected like any other answer.
+/// Exits the program if input is closed and the default isn't valid.
+pub fn ask_with_default_validated<T, V>(
+    prompt: &str,
+    default: T,
+    validator: V,
+    error_message: Option<&str>,
+) -> T
+where
+    T: Parse + std::fmt::Display + Clone,
+    V: Validator<T>,
+{
+    let _turn = lock::hold();
+    show_hint(&validator);
+    let error_msg = validators::error_message(&validator, error_message);
+    let check = |value: &T| validator.check(value);
+    default_loop(prompt, default, false, &check, &error_msg)
+}
+
+fn default_loop<T: Parse + std::fmt::Display + Clone>(
+    prompt: &str,
+    default: T,
+    lenient: bool,
+    validator: &dyn Fn(&T) -> bool,
+    error_msg: &str,
+) -> T {
+    let _turn = lock::hold();
+    let _described = debug::describe(prompt, T::type_name(), Some(&default), error_msg);
+
+    let mut attempts = 0;
     loop {
-        print!("{}: ", prompt);
-        let _ = io::stdout().flush();
-
-        let mut input = String::new();
-        match io::stdin().read_line(&mut input) {
-            Ok(_) => match T::parse(input.trim()) {
-                Ok(value) => return value,
-                Err(e) => eprintln!("❌ {}", e),
+        attempts += 1;
+        debug::attempt(attempts);
+        let (value, closed) = match read_with_default(prompt, &default) {
+            Ok(input) if input.is_empty() => (default.clone(), false),
+            Ok(input) => match T::parse(&input) {
+                Ok(value) => (value, false),
+                Err(mut e) if lenient => {
+                    e.message =
+                        format!("{} - using {}", e.message, shown_default(prompt, &default));
+                    report(&e.during(prompt, attempts));
+                    return default;
+                }
+                Err(e) => {
+                    report(&e.during(prompt, attempts));
+                    continue;
+                }
             },
-            Err(e) => eprintln!("❌ Input error: {}", e),
+            // Closed input takes the default; anything else (Ctrl+C, a
+            // broken pipe) ends the prompt
+            Err(e) if e.is_eof() => (default.clone(), true),
+            Err(e) => give_up(e.during(prompt, attempts)),
+        };
+
+        if validator(&value) {
+            return value;
+        }
+        if closed {
+            give_up(VelvetIOError::eof());
+        }
+        report(
+            &VelvetIOError::validation_error(value.to_string(), error_msg).during(prompt, attempts),
+        );
+    }
+}
+
+/// Try once with validation, return Result instead of retrying
+pub fn try_ask_with_validation<T: Parse, V>(
+    prompt: &str,
+    validator: V,
+    error_message: Option<&str>,
+) -> Result<T>
+where
+    V: Validator<T>,
+{
+    let _turn = lock::hold();
+    show_hint(&validator);
+    let error_msg = validators::error_message(&validator, error_message);
+    let check = |value: &T| validator.check(value);
+    ask_loop(prompt, None, &check, &error_msg, &single_attempt()).map(|(value, _)| value)
+}
+
+/// Try once with a default - enter gives the default, bad input gives an error
+pub fn try_ask_with_default<T: Parse + std::fmt::Display + Clone>(
+    prompt: &str,
+    default: T,
+) -> Result<T> {
+    try_default(prompt, default, &accept_any, "")
+}
+
+/// Try once with a default that has to pass validation too
+pub fn try_ask_with_default_validated<T, V>(
+    prompt: &str,
+    default: T,
+    validator: V,
+    error_message: Option<&str>,
+) -> Result<T>
+where
+    T: Parse + std::fmt::Display + Clone,
+    V: Validator<T>,
+{
+    let _turn = lock::hold();
+    show_hint(&validator);
+    let error_msg = validators::error_message(&validator, error_message);
+    let check = |value: &T| validator.check(value);
+    try_default(prompt, default, &check, &error_msg)
+}
+
+// What a validator checks, dimmed on a line before the prompt. Minimal themes
+// leave it to the error.
+fn show_hint<T>(validator: &impl Validator<T>) {
+    let Some(hint) = validator.describe() else {
+        return;
+    };
+    if protocol::is_json_protocol() || theme::with_theme(|theme| theme.minimal) {
+        return;
+    }
+    let hint = format!(
+        "[dim]{}[/]",
+        markup::escape(&validators::capitalized(&hint))
+    );
+    outln!("{}", layout::hanging("", &hint));
+}
+
+fn try_default<T: Parse + std::fmt::Display + Clone>(
+    prompt: &str,
+    default: T,
+    validator: &dyn Fn(&T) -> bool,
+    error_msg: &str,
+) -> Result<T> {
+    let _turn = lock::hold();
+    let _described = debug::describe(prompt, T::type_name(), Some(&default), error_msg);
+    let ask = || {
+        let input = read_with_default(prompt, &default)?;
+        let value = if input.is_empty() {
+            default.clone()
+        } else {
+            T::parse(&input)?
+        };
+
+        if validator(&value) {
+            Ok(value)
+        } else {
+            Err(VelvetIOError::validation_error(input, error_msg))
+        }
+    };
+    ask().map_err(|e| e.during(prompt, 1))
+}
+
+/// Yes/no question, answered in the theme's [`ConfirmStyle`](crate::ConfirmStyle)
+///
+/// Exits the program if input is closed before a valid answer arrives.
+pub fn confirm(prompt: &str) -> bool {
+    confirm_with_style(prompt, theme::with_theme(|theme| theme.confirm_style))
+}
+
+/// Yes/no question that returns an error instead of retrying forever
+///
+/// Fails on end of input, cancellation, or after `MAX_ATTEMPTS` invalid answers.
+pub fn try_confirm(prompt: &str) -> Result<bool> {
+    try_confirm_with_style(prompt, theme::with_theme(|theme| theme.confirm_style))
+}
+
+/// Like [`confirm`], in `style` whatever the theme says
+///
+/// ```no_run
+/// use velvetio::{ConfirmStyle, confirm_with_style};
+///
+/// let backups = confirm_with_style("Keep nightly backups", ConfirmStyle::Checkbox);
+/// ```
+pub fn confirm_with_style(prompt: &str, style: crate::ConfirmStyle) -> bool {
+    match yes_no_keys(prompt, style) {
+        Some(answer) => answer.unwrap_or_else(|e| give_up(e)),
+        None => ask::<bool>(&yes_no(prompt)),
+    }
+}
+
+/// Like [`try_confirm`], in `style` whatever the theme says
+pub fn try_confirm_with_style(prompt: &str, style: crate::ConfirmStyle) -> Result<bool> {
+    if let Some(answer) = yes_no_keys(prompt, style) {
+        return answer;
+    }
+    let prompt = yes_no(prompt);
+    ask_loop(
+        &prompt,
+        None,
+        &accept_any,
+        "",
+        &PromptOptions::with_policy(AttemptPolicy::limited(MAX_ATTEMPTS)),
+    )
+    .map(|(value, _)| value)
+}
+
+// A toggle or checkbox answer, when the style has one and the terminal can
+// take keys. `None` means ask in words.
+fn yes_no_keys(prompt: &str, style: crate::ConfirmStyle) -> Option<Result<bool>> {
+    if style == crate::ConfirmStyle::Words || !editor::available() {
+        return None;
+    }
+    let _turn = lock::hold();
+    let labelled = context::label(prompt);
+    editor::read_yes_no(&layout::hanging("", &labelled), style)
+}
+
+/// Show a summary of what's about to happen, then ask to proceed
+///
+/// ```no_run
+/// let ok = velvetio::confirm_values("About to create", &[("Name", "api"), ("Port", "8080")]);
+/// ```
+pub fn confirm_values<K, V>(title: &str, pairs: &[(K, V)]) -> bool
+where
+    K: std::fmt::Display,
+    V: std::fmt::Display,
+{
+    let _turn = lock::hold();
+    out!("{}", summary(title, pairs));
+    confirm("Proceed?")
+}
+
+/// Yes/no for each item in turn, like `rm -i`
+///
+/// Besides yes and no, "a" says yes to this item and all the rest, and "q"
+/// says no to this one and all the rest.
+/// Exits the program if input is closed before a valid answer arrives.
+///
+/// ```no_run
+/// let files = ["notes.txt", "draft.md", "old.log"];
+/// for (file, delete) in velvetio::confirm_each("Delete these files?", files) {
+///     if delete {
+///         println!("deleting {}", file);
+///     }
+/// }
+/// ```
+pub fn confirm_each<T, I>(prompt: &str, items: I) -> Vec<(T, bool)>
+where
+    T: std::fmt::Display,
+    I: IntoIterator<Item = T>,
+{
+    confirm_each_inner(prompt, items, &PromptOptions::default()).unwrap_or_else(|e| give_up(e))
+}
+
+/// Yes/no for each item, returning an error instead of retrying forever
+///
+/// Fails on end of input, cancellation, or after `MAX_ATTEMPTS` invalid answers.
+pub fn try_confirm_each<T, I>(prompt: &str, items: I) -> Result<Vec<(T, bool)>>
+where
+    T: std::fmt::Display,
+    I: IntoIterator<Item = T>,
+{
+    let options = PromptOptions::with_policy(AttemptPolicy::limited(MAX_ATTEMPTS));
+    confirm_each_inner(prompt, items, &options)
+}
+
+fn confirm_each_inner<T, I>(
+    prompt: &str,
+    items: I,
+    options: &PromptOptions,
+) -> Result<Vec<(T, bool)>>
+where
+    T: std::fmt::Display,
+    I: IntoIterator<Item = T>,
+{
+    let _turn = lock::hold();
+    outln!("{}", layout::hanging("", prompt));
+
+    let mut rest = None;
+    let mut answers = Vec::new();
+    for item in items {
+        let answer = match rest {
+            Some(answer) => answer,
+            None => {
+                let question = format!("  {} (y/n/a/q)", item);
+                match ask_loop::<Each>(&question, None, &accept_any, "", options)?.0 {
+                    Each::Yes(answer) => answer,
+                    Each::All => *rest.insert(true),
+                    Each::Quit => *rest.insert(false),
+                }
+            }
+        };
+        answers.push((item, answer));
+    }
+    Ok(answers)
+}
+
+// An answer to `confirm_each`: yes/no for this item, or for all that are left
+enum Each {
+    Yes(bool),
+    All,
+    Quit,
+}
+
+impl Parse for Each {
+    fn parse(input: &str) -> Result<Self> {
+        let trimmed = input.trim();
+        if trimmed.eq_ignore_ascii_case("a") || trimmed.eq_ignore_ascii_case("all") {
+            Ok(Each::All)
+        } else if trimmed.eq_ignore_ascii_case("q") || trimmed.eq_ignore_ascii_case("quit") {
+            Ok(Each::Quit)
+        } else {
+            bool::parse(input)
+                .map(Each::Yes)
+                .map_err(|_| VelvetIOError::parse_error(input, Self::type_name()))
+        }
+    }
+
+    fn type_name() -> &'static str {
+        "y/n, a for all, q to skip the rest"
+    }
+}
+
+/// Assign one of `choices` to each item in turn, like enable/disable per service
+///
+/// The choices are listed once, numbered, then each item gets a one-line
+/// question answered with a number or a name. Returns the pairs in item order.
+/// Exits the program if `choices` is empty - use `try_ask_choice_matrix` if
+/// it might be - or if input is closed before every item has an answer.
+///
+/// ```no_run
+/// use std::collections::HashMap;
+///
+/// let services = ["api", "worker", "scheduler"];
+/// let plan: HashMap<_, _> =
+///     velvetio::ask_choice_matrix("Services", services, &["enable", "disable", "ask later"])
+///         .into_iter()
+///         .collect();
+/// // Services: 1 enable, 2 disable, 3 ask later
+/// //   api      : 1
+/// //   worker   : dis
+/// //   scheduler: 3
+/// ```
+pub fn ask_choice_matrix<T, I, O>(prompt: &str, items: I, choices: &[O]) -> Vec<(T, O)>
+where
+    T: std::fmt::Display,
+    I: IntoIterator<Item = T>,
+    O: std::fmt::Display + Clone,
+{
+    matrix_inner(prompt, items, choices, &AttemptPolicy::unlimited()).unwrap_or_else(|e| give_up(e))
+}
+
+/// `ask_choice_matrix` that returns an error instead of retrying forever
+///
+/// Fails on empty choices, end of input, cancellation, or after
+/// `MAX_ATTEMPTS` invalid answers for one item.
+pub fn try_ask_choice_matrix<T, I, O>(prompt: &str, items: I, choices: &[O]) -> Result<Vec<(T, O)>>
+where
+    T: std::fmt::Display,
+    I: IntoIterator<Item = T>,
+    O: std::fmt::Display + Clone,
+{
+    matrix_inner(
+        prompt,
+        items,
+        choices,
+        &AttemptPolicy::limited(MAX_ATTEMPTS),
+    )
+}
+
+fn matrix_inner<T, I, O>(
+    prompt: &str,
+    items: I,
+    choices: &[O],
+    policy: &AttemptPolicy,
+) -> Result<Vec<(T, O)>>
+where
+    T: std::fmt::Display,
+    I: IntoIterator<Item = T>,
+    O: std::fmt::Display + Clone,
+{
+    let _turn = lock::hold();
+    if choices.is_empty() {
+        return Err(VelvetIOError::no_choices().during(prompt, 1));
+    }
+
+    let legend: Vec<String> = choices
+        .iter()
+        .enumerate()
+        .map(|(i, choice)| format!("{} {}", i + 1, markup::escape(&choice.to_string())))
+        .collect();
+    outln!(
+        "{}",
+        layout::hanging("", &format!("{}: {}", prompt, legend.join(", ")))
+    );
+
+    // Answers line up under each other
+    let items: Vec<T> = items.into_iter().collect();
+    let labels: Vec<String> = items.iter().map(|item| item.to_string()).collect();
+    let width = labels
+        .iter()
+        .map(|l| layout::text_width(l))
+        .max()
+        .unwrap_or(0);
+
+    let options = PromptOptions::with_policy(policy.clone());
+    let mut pairs = Vec::with_capacity(items.len());
+    for (item, label) in items.into_iter().zip(&labels) {
+        let label = layout::pad(&layout::isolate(label), width);
+        let question = format!("  {}", markup::escape(&label));
+        let mut attempts = 0;
+        let picked = loop {
+            let (input, closed) = next_answer(&question, &options)?;
+            match pick_choice(&input, choices) {
+                Ok(index) => break index,
+                Err(_) if closed => return Err(VelvetIOError::eof().during(prompt, attempts + 1)),
+                Err(e) => report(&e.during(prompt, attempts + 1)),
+            }
+            out_of_attempts(&mut attempts, policy).map_err(|e| e.during(prompt, attempts + 1))?;
+        };
+        pairs.push((item, choices[picked].clone()));
+    }
+    Ok(pairs)
+}
+
+// Title plus one aligned `key: value` line per pair
+pub(crate) fn summary<K, V>(title: &str, pairs: &[(K, V)]) -> String
+where
+    K: std::fmt::Display,
+    V: std::fmt::Display,
+{
+    let keys: Vec<String> = pairs.iter().map(|(key, _)| key.to_string()).collect();
+    let width = keys
+        .iter()
+        .map(|key| layout::text_width(key))
+        .max()
+        .unwrap_or(0);
+
+    let mut out = format!("{}:\n", title.trim_end_matches(':'));
+    for (key, (_, value)) in keys.iter().zip(pairs) {
+        let padding = width - layout::text_width(key);
+        let value = match redact::is_sensitive(key) {
+            true => crate::REDACTED.to_string(),
+            false => value.to_string(),
+        };
+        out.push_str(&format!(
+            "  {}:{} {}\n",
+            layout::isolate(key),
+            " ".repeat(padding),
+            layout::isolate(&value)
+        ));
+    }
+    out
+}
+
+/// Pick one option from a list
+///
+/// Answer with the option's number or its name - case doesn't matter and
+/// any unambiguous prefix works.
+///
+/// Exits the program if input is closed before a valid answer arrives, or if
+/// `choices` is empty - use `try_choose` for lists built at runtime.
+pub fn choose<T>(prompt: &str, choices: &[T]) -> T
+where
+    T: std::fmt::Display + Clone,
+{
+    choose_or_exit(prompt, choices, None)
+}
+
+/// Pick one option, with `choices[default]` picked when the user just hits enter
+///
+/// Exits the program if `choices` is empty.
+pub fn choose_with_default<T>(prompt: &str, choices: &[T], default: usize) -> T
+where
+    T: std::fmt::Display + Clone,
+{
+    choose_or_exit(prompt, choices, Some(default))
+}
+
+/// Pick one option, with a stable key for the answer cache
+///
+/// With caching enabled, the option picked last time is the default and is
+/// marked "(last time)" in the list. Exits the program if `choices` is empty.
+pub fn choose_keyed<T>(key: &str, prompt: &str, choices: &[T]) -> T
+where
+    T: std::fmt::Display + Clone,
+{
+    let default = cached_choice(key, choices);
+    let policy = AttemptPolicy::unlimited();
+    let choice = choose_or_skip(prompt, choices, default, &policy, Menu::Remembered)
+        .and_then(required)
+        .unwrap_or_else(|e| give_up(e));
+    cache::remember(key, &choice.to_string());
+    choice
+}
+
+fn choose_or_exit<T>(prompt: &str, choices: &[T], default: Option<usize>) -> T
+where
+    T: std::fmt::Display + Clone,
+{
+    choose_inner(prompt, choices, default, &AttemptPolicy::unlimited())
+        .unwrap_or_else(|e| give_up(e))
+}
+
+/// Pick one option, returning an error instead of exiting or retrying forever
+///
+/// Fails on an empty list, end of input, cancellation, or after
+/// `MAX_ATTEMPTS` invalid answers.
+pub fn try_choose<T>(prompt: &str, choices: &[T]) -> Result<T>
+where
+    T: std::fmt::Display + Clone,
+{
+    choose_inner(prompt, choices, None, &AttemptPolicy::limited(MAX_ATTEMPTS))
+}
+
+/// `try_choose` with `choices[default]` picked on enter
+pub fn try_choose_with_default<T>(prompt: &str, choices: &[T], default: usize) -> Result<T>
+where
+    T: std::fmt::Display + Clone,
+{
+    let policy = AttemptPolicy::limited(MAX_ATTEMPTS);
+    choose_inner(prompt, choices, Some(default), &policy)
+}
+
+/// Keyed version of `try_choose` - the cached pick is used on enter
+pub fn try_choose_keyed<T>(key: &str, prompt: &str, choices: &[T]) -> Result<T>
+where
+    T: std::fmt::Display + Clone,
+{
+    let default = cached_choice(key, choices);
+    let policy = AttemptPolicy::limited(MAX_ATTEMPTS);
+    let choice = choose_or_skip(prompt, choices, default, &policy, Menu::Remembered)
+        .and_then(required)
+        .map_err(|e| e.with_key(key))?;
+    cache::remember(key, &choice.to_string());
+    Ok(choice)
+}
+
+// Where the option cached for `key` is now, if it's still on offer
+fn cached_choice<T: std::fmt::Display>(key: &str, choices: &[T]) -> Option<usize> {
+    let cached = cache::cached(key)?;
+    choices.iter().position(|c| c.to_string() == cached)
+}
+
+// `default` is the index picked when the user just hits enter
+pub(crate) fn choose_inner<T>(
+    prompt: &str,
+    choices: &[T],
+    default: Option<usize>,
+    policy: &AttemptPolicy,
+) -> Result<T>
+where
+    T: std::fmt::Display + Clone,
+{
+    choose_or_skip(prompt, choices, default, policy, Menu::Required).and_then(required)
+}
+
+// Only optional menus can be skipped, so a required one always has a pick
+fn required<T>(choice: Option<T>) -> Result<T> {
+    choice.ok_or_else(VelvetIOError::no_choices)
+}
+
+// What kind of single-choice menu it is
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Menu {
+    Required,
+    // Enter (or "none") skips it
+    Optional,
+    // The default is what was picked last time, and says so
+    Remembered,
+}
+
+fn choose_or_skip<T>(
+    prompt: &str,
+    choices: &[T],
+    default: Option<usize>,
+    policy: &AttemptPolicy,
+    menu: Menu,
+) -> Result<Option<T>>
+where
+    T: std::fmt::Display + Clone,
+{
+    let _turn = lock::hold();
+
+    if choices.is_empty() {
+        return Err(VelvetIOError::no_choices().during(prompt, 1));
+    }
+
+    let optional = menu == Menu::Optional;
+    let default = default.filter(|&index| index < choices.len());
+    let json = protocol::is_json_protocol();
+    let choose_prompt = match default {
+        // The menu goes along with the question instead
+        _ if json => context::label(prompt),
+        Some(index) => with_default(&choose_prompt(choices.len()), &choices[index]),
+        None if optional => format!("{} (enter to skip)", choose_prompt(choices.len())),
+        None => choose_prompt(choices.len()),
+    };
+
+    let last_time = match menu {
+        Menu::Remembered => default.as_slice(),
+        _ => &[],
+    };
+    let options = PromptOptions {
+        details: json
+            .then(|| menu_details(choices, default.map(|i| choices[i].to_string()), false)),
+        ..PromptOptions::with_policy(policy.clone())
+    };
+    if !json {
+        print_noted(prompt, choices, last_time);
+    }
+
+    let _described = debug::describe(
+        prompt,
+        "an option's number or name",
+        default.map(|index| &choices[index] as &dyn std::fmt::Display),
+        "",
+    );
+    let mut retry = MenuRetry::new();
+    let mut attempts = 0;
+    let mut ask = || loop {
+        debug::attempt(attempts + 1);
+        let (input, closed) = next_answer(&choose_prompt, &options)?;
+        if let (Some(index), true) = (default, input.is_empty()) {
+            return Ok(Some(choices[index].clone()));
+        }
+        if optional && (input.is_empty() || parser::is_none_word(&input)) {
+            return Ok(None);
+        }
+
+        match pick_choice(&input, choices) {
+            Ok(index) => return Ok(Some(choices[index].clone())),
+            _ if closed => return Err(VelvetIOError::eof()),
+            Err(e) => retry.report(&choose_prompt, &input, &e.during(prompt, attempts + 1)),
+        }
+        out_of_attempts(&mut attempts, policy)?;
+    };
+    let result = ask();
+    result.map_err(|e| e.during(prompt, attempts + 1))
+}
+
+// The options (and default) protocol mode sends with a menu's question
+fn menu_details<T: std::fmt::Display>(
+    choices: &[T],
+    default: Option<String>,
+    multiple: bool,
+) -> protocol::Details {
+    protocol::Details {
+        default,
+        choices: choices.iter().map(|choice| choice.to_string()).collect(),
+        multiple,
+    }
+}
+
+// The question under a numbered menu
+pub(crate) fn choose_prompt(count: usize) -> String {
+    if theme::with_theme(|theme| theme.accessible) {
+        format!("Enter a number from 1 to {} or an option name", count)
+    } else {
+        format!("Choose (1-{})", count)
+    }
+}
+
+// Keeps a menu on screen across retries instead of printing it again. On a
+// terminal the rejected answer and its error are erased before the next
+// error is shown, so retries update in place below the menu.
+struct MenuRetry {
+    interactive: bool,
+    width: usize,
+    drawn: usize,
+}
+
+impl MenuRetry {
+    fn new() -> Self {
+        Self {
+            interactive: term::cursor_control()
+                && tty::stderr_is_tty()
+                && !theme::with_theme(|theme| theme.accessible),
+            width: layout::width().unwrap_or(80),
+            drawn: 0,
+        }
+    }
+
+    fn report(&mut self, prompt: &str, input: &str, error: &VelvetIOError) {
+        let answered = format!("{}{}", prompt_shown(prompt), input);
+        if self.interactive {
+            let lines = self.drawn + layout::rows(&answered, self.width);
+            // Up to where the last attempt started, then clear everything below
+            print!("\x1b[{}A\r\x1b[J", lines);
+            let _ = io::stdout().flush();
+        }
+
+        self.drawn = match reported(error) {
+            true => layout::rows(&theme::render_error(error), self.width),
+            false => 0,
+        };
+    }
+}
+
+// Numbered list under the prompt, long labels wrapped under themselves,
+// or in columns when there are lots of short ones
+pub(crate) fn print_choices<T: std::fmt::Display>(prompt: &str, choices: &[T]) {
+    print_noted(prompt, choices, &[]);
+}
+
+// The menu, with the options at `last_time` marked as picked on the last run
+fn print_noted<T: std::fmt::Display>(prompt: &str, choices: &[T], last_time: &[usize]) {
+    outln!("{}:", layout::hanging("", &context::label(prompt)));
+
+    let note = |i: usize| match last_time.contains(&i) {
+        true => " (last time)",
+        false => "",
+    };
+    let labels: Vec<String> = choices.iter().map(|choice| choice.to_string()).collect();
+    if let Some(width) = layout::width().filter(|_| !theme::with_theme(|theme| theme.accessible)) {
+        let digits = labels.len().to_string().len();
+        let cells: Vec<String> = labels
+            .iter()
+            .enumerate()
+            .map(|(i, label)| {
+                let label = layout::isolate(label);
+                format!("{:>w$}. {}{}", i + 1, label, note(i), w = digits)
+            })
+            .collect();
+        if let Some(lines) = layout::columns(&cells, width.saturating_sub(2)) {
+            for line in lines {
+                outln!("  {}", line);
+            }
+            return;
         }
     }
+
+    for (i, label) in labels.iter().enumerate() {
+        let number = format!("  {}. ", i + 1);
+        // Labels are data, shown as they are
+        let label = layout::isolate(label);
+        let label = match (markup::escape(&label), note(i)) {
+            (label, "") => label,
+            (label, note) => Cow::Owned(format!("{}{}", label, note)),
+        };
+        outln!("{}", layout::hanging(&number, &label));
+    }
 }
 
-/// Try once, return Result instead of retrying
-pub fn try_ask<T: Parse>(prompt: &str) -> Result<T> {
-    print!("{}: ", prompt);
-    let _ = io::stdout().flush();
+// Resolves an answer to an index - either a 1-based number or a label.
+// Labels match case-insensitively, exactly or by an unambiguous prefix.
+pub(crate) fn pick_choice<T: std::fmt::Display>(input: &str, choices: &[T]) -> Result<usize> {
+    if let Ok(number) = input.parse::<usize>() {
+        return match number {
+            1.. if number <= choices.len() => Ok(number - 1),
+            _ => Err(VelvetIOError::validation_error(
+                input,
+                format!("Please choose between 1 and {}", choices.len()),
+            )),
+        };
+    }
 
-    let mut input = String::new();
-    io::stdin().read_line(&mut input)?;
-    T::parse(input.trim())
+    let labels: Vec<String> = choices.iter().map(|c| c.to_string()).collect();
+    parser::find_name(input, &labels).map_err(|matches| match matches.as_slice() {
+        [] => VelvetIOError::validation_error(
+            input,
+            format!(
+                "Please enter a number between 1 and {} or an option name",
+                choices.len()
+            ),
+        ),
+        _ => {
+            let mut error = parser::ambiguous(input, &labels, &matches);
+            error.kind = ErrorKind::Validation;
+            error
+        }
+    })
 }
 
-/// Ask with validation function
-pub fn ask_with_validation<T: Parse, F>(
-    prompt: &str,
-    validator: F,
-    error_message: Option<&str>,
-) -> T
+/// Pick multiple options from a list
+///
+/// Exits the program if input is closed before a valid answer arrives.
+pub fn multi_select<T>(prompt: &str, choices: &[T]) -> Vec<T>
 where
-    F: Fn(&T) -> bool,
+    T: std::fmt::Display + Clone,
 {
-    let default_error = "Invalid input, please try again";
-    let error_msg = error_message.unwrap_or(default_error);
-
-    loop {
-        print!("{}: ", prompt);
-        let _ = io::stdout().flush();
-
-        let mut input = String::new();
-        match io::stdin().read_line(&mut input) {
-            Ok(_) => match T::parse(input.trim()) {
-                Ok(value) => {
-                    if validator(&value) {
-                        return value;
-                    } else {
-                        eprintln!("❌ {}", error_msg);
-                    }
-                }
-                Err(e) => eprintln!("❌ {}", e),
-            },
-            Err(e) => eprintln!("❌ Input error: {}", e),
-        }
+    if choices.is_empty() {
+        return Vec::new();
     }
+
+    multi_select_inner(prompt, choices, None, &AttemptPolicy::unlimited(), false)
+        .unwrap_or_else(|e| give_up(e))
 }
 
-/// Ask with default - hit enter to use default
-pub fn ask_with_default<T: Parse + std::fmt::Display + Clone>(prompt: &str, default: T) -> T {
-    print!("{} [{}]: ", prompt, default);
-    let _ = io::stdout().flush();
+/// Pick multiple options, returning an error instead of retrying forever
+///
+/// Fails on an empty list, end of input, cancellation, or after
+/// `MAX_ATTEMPTS` invalid answers.
+pub fn try_multi_select<T>(prompt: &str, choices: &[T]) -> Result<Vec<T>>
+where
+    T: std::fmt::Display + Clone,
+{
+    multi_select_inner(
+        prompt,
+        choices,
+        None,
+        &AttemptPolicy::limited(MAX_ATTEMPTS),
+        false,
+    )
+}
 
-    let mut input = String::new();
-    match io::stdin().read_line(&mut input) {
-        Ok(_) => {
-            let trimmed = input.trim();
-            if trimmed.is_empty() {
-                default
-            } else {
-                T::parse(trimmed).unwrap_or(default)
-            }
-        }
-        Err(_) => default,
+/// Pick at least one option from a list
+///
+/// "none" isn't accepted, and enter on its own shows the list again. Exits
+/// the program if `choices` is empty - use `try_multi_select_required` for
+/// lists built at runtime.
+pub fn multi_select_required<T>(prompt: &str, choices: &[T]) -> Vec<T>
+where
+    T: std::fmt::Display + Clone,
+{
+    multi_select_inner(prompt, choices, None, &AttemptPolicy::unlimited(), true)
+        .unwrap_or_else(|e| give_up(e))
+}
+
+/// Pick at least one option, returning an error instead of exiting or
+/// retrying forever
+pub fn try_multi_select_required<T>(prompt: &str, choices: &[T]) -> Result<Vec<T>>
+where
+    T: std::fmt::Display + Clone,
+{
+    let policy = AttemptPolicy::limited(MAX_ATTEMPTS);
+    multi_select_inner(prompt, choices, None, &policy, true)
+}
+
+/// Pick multiple options, with the ones at `default` picked when the user
+/// just hits enter
+pub fn multi_select_with_default<T>(prompt: &str, choices: &[T], default: &[usize]) -> Vec<T>
+where
+    T: std::fmt::Display + Clone,
+{
+    if choices.is_empty() {
+        return Vec::new();
     }
+
+    multi_select_inner(
+        prompt,
+        choices,
+        Some(default),
+        &AttemptPolicy::unlimited(),
+        false,
+    )
+    .unwrap_or_else(|e| give_up(e))
 }
 
-/// Yes/no question
-pub fn confirm(prompt: &str) -> bool {
-    ask::<bool>(&format!("{} (y/n)", prompt))
+/// `try_multi_select` with the ones at `default` picked on enter
+pub fn try_multi_select_with_default<T>(
+    prompt: &str,
+    choices: &[T],
+    default: &[usize],
+) -> Result<Vec<T>>
+where
+    T: std::fmt::Display + Clone,
+{
+    let policy = AttemptPolicy::limited(MAX_ATTEMPTS);
+    multi_select_inner(prompt, choices, Some(default), &policy, false)
 }
 
-/// Pick one option from a list
-pub fn choose<T>(prompt: &str, choices: &[T]) -> T
+/// Pick multiple options, with a stable key for the answer cache
+///
+/// With caching enabled, the options picked last time are the default and
+/// are marked "(last time)" in the list.
+pub fn multi_select_keyed<T>(key: &str, prompt: &str, choices: &[T]) -> Vec<T>
 where
     T: std::fmt::Display + Clone,
 {
     if choices.is_empty() {
-        panic!("Cannot choose from empty list");
+        return Vec::new();
     }
 
-    loop {
-        println!("{}:", prompt);
-        for (i, choice) in choices.iter().enumerate() {
-            println!("  {}. {}", i + 1, choice);
-        }
+    let default = cached_choices(key, choices);
+    let picked = select_many(
+        prompt,
+        choices,
+        default.as_deref(),
+        &AttemptPolicy::unlimited(),
+        false,
+        true,
+    )
+    .unwrap_or_else(|e| give_up(e));
+    cache::remember(key, &labels_for_cache(&picked));
+    picked
+}
+
+/// Keyed version of `try_multi_select` - the cached picks are used on enter
+pub fn try_multi_select_keyed<T>(key: &str, prompt: &str, choices: &[T]) -> Result<Vec<T>>
+where
+    T: std::fmt::Display + Clone,
+{
+    let default = cached_choices(key, choices);
+    let policy = AttemptPolicy::limited(MAX_ATTEMPTS);
+    let picked = select_many(prompt, choices, default.as_deref(), &policy, false, true)
+        .map_err(|e| e.with_key(key))?;
+    cache::remember(key, &labels_for_cache(&picked));
+    Ok(picked)
+}
+
+fn cached_choices<T: std::fmt::Display>(key: &str, choices: &[T]) -> Option<Vec<usize>> {
+    cache::cached(key).map(|cached| saved_picks(&cached, choices))
+}
+
+// Picks saved as text back to indices. The cache keeps them as a JSON array
+// of labels; form answers and defaults join them with ", ", which is read
+// label by label, longest first, so "Rust, Go" isn't taken for "Rust".
+// Labels that aren't options any more are dropped.
+pub(crate) fn saved_picks<T: std::fmt::Display>(saved: &str, choices: &[T]) -> Vec<usize> {
+    let labels: Vec<String> = choices.iter().map(|c| c.to_string()).collect();
+    if let Some(list) = cache::decode_list(saved) {
+        return list
+            .iter()
+            .filter_map(|label| labels.iter().position(|l| l == label))
+            .collect();
+    }
 
-        match try_ask::<usize>(&format!("Choose (1-{})", choices.len())) {
-            Ok(index) if index >= 1 && index <= choices.len() => {
-                return choices[index - 1].clone();
+    let mut picked = Vec::new();
+    let mut rest = saved.trim();
+    while !rest.is_empty() {
+        let found = labels
+            .iter()
+            .enumerate()
+            .filter(|(_, label)| {
+                !label.is_empty()
+                    && rest
+                        .strip_prefix(label.as_str())
+                        .is_some_and(|after| after.is_empty() || after.starts_with(','))
+            })
+            .max_by_key(|(_, label)| label.len());
+        rest = match found {
+            Some((index, label)) => {
+                picked.push(index);
+                &rest[label.len()..]
             }
-            Ok(_) => eprintln!("❌ Please choose between 1 and {}", choices.len()),
-            Err(e) => eprintln!("❌ {}", e),
-        }
+            None => rest.split_once(',').map_or("", |(_, after)| after),
+        };
+        rest = rest.trim_start_matches(',').trim_start();
     }
+    picked
 }
 
-/// Pick multiple options from a list
-pub fn multi_select<T>(prompt: &str, choices: &[T]) -> Vec<T>
+fn labels_for_cache<T: std::fmt::Display>(picked: &[T]) -> String {
+    let labels: Vec<String> = picked.iter().map(|c| c.to_string()).collect();
+    cache::encode_list(&labels)
+}
+
+// `default` holds the indices picked when the user just hits enter. With
+// `required`, "none" isn't an answer.
+pub(crate) fn multi_select_inner<T>(
+    prompt: &str,
+    choices: &[T],
+    default: Option<&[usize]>,
+    policy: &AttemptPolicy,
+    required: bool,
+) -> Result<Vec<T>>
+where
+    T: std::fmt::Display + Clone,
+{
+    select_many(prompt, choices, default, policy, required, false)
+}
+
+// With `remembered`, the default is last run's picks and they're marked so
+fn select_many<T>(
+    prompt: &str,
+    choices: &[T],
+    default: Option<&[usize]>,
+    policy: &AttemptPolicy,
+    required: bool,
+    remembered: bool,
+) -> Result<Vec<T>>
 where
     T: std::fmt::Display + Clone,
 {
+    let _turn = lock::hold();
+
     if choices.is_empty() {
-        return Vec::new();
+        return Err(VelvetIOError::no_choices().during(prompt, 1));
     }
 
-    loop {
-        println!("{}:", prompt);
-        for (i, choice) in choices.iter().enumerate() {
-            println!("  {}. {}", i + 1, choice);
+    let default: Option<Vec<usize>> = default.map(|indices| {
+        indices
+            .iter()
+            .copied()
+            .filter(|&i| i < choices.len())
+            .collect()
+    });
+    let json = protocol::is_json_protocol();
+    let default_labels = default.as_ref().map(|indices| {
+        let labels: Vec<String> = indices.iter().map(|&i| choices[i].to_string()).collect();
+        labels.join(", ")
+    });
+    let selection_prompt = match &default_labels {
+        // The menu goes along with the question instead
+        _ if json => context::label(prompt),
+        Some(labels) => with_default("Selection", labels),
+        None => "Selection".to_string(),
+    };
+    let options = PromptOptions {
+        details: json.then(|| menu_details(choices, default_labels.clone(), true)),
+        ..PromptOptions::with_policy(policy.clone())
+    };
+
+    let hint = match required {
+        true => "Enter numbers separated by commas (e.g., 1,3,5) or 'all':",
+        false => "Enter numbers separated by commas (e.g., 1,3,5) or 'all' or 'none':",
+    };
+    let last_time = match (&default, remembered) {
+        (Some(indices), true) => indices.as_slice(),
+        _ => &[],
+    };
+    let minimal = theme::with_theme(|theme| theme.minimal);
+    let show = || {
+        if json {
+            return;
+        }
+        print_noted(prompt, choices, last_time);
+        if !minimal {
+            outln!("{}", layout::hanging("", hint));
         }
-        println!("Enter numbers separated by commas (e.g., 1,3,5) or 'all' or 'none':");
+    };
+    show();
 
-        let input = ask::<String>("Selection");
-        let input = input.trim().to_lowercase();
+    let _described = debug::describe(
+        prompt,
+        "option numbers separated by commas, or 'all'",
+        default_labels
+            .as_ref()
+            .map(|labels| labels as &dyn std::fmt::Display),
+        match required {
+            true => "Pick at least one option",
+            false => "",
+        },
+    );
+    let mut retry = MenuRetry::new();
+    let mut attempts = 0;
+    let mut ask = || loop {
+        debug::attempt(attempts + 1);
+        let (input, closed) = next_answer(&selection_prompt, &options)?;
+        let answer = input.to_lowercase();
 
-        if input == "none" || input.is_empty() {
-            return Vec::new();
+        if answer.is_empty() {
+            match &default {
+                Some(indices) => return Ok(indices.iter().map(|&i| choices[i].clone()).collect()),
+                // Piped input that runs out still means "nothing", as it always has
+                None if closed && !required => return Ok(Vec::new()),
+                None if closed => return Err(VelvetIOError::eof()),
+                // Enter on its own is usually a slip - show the list again
+                // rather than quietly selecting nothing
+                None => {
+                    if !minimal {
+                        show();
+                    }
+                    retry = MenuRetry::new();
+                    continue;
+                }
+            }
         }
 
-        if input == "all" {
-            return choices.to_vec();
+        let picked = match pick_many(&answer, choices.len()) {
+            Ok(indices) if indices.is_empty() && required => Err(VelvetIOError::validation_error(
+                &input,
+                "Pick at least one option",
+            )),
+            picked => picked,
+        };
+        match picked {
+            Ok(indices) => return Ok(indices.iter().map(|&i| choices[i].clone()).collect()),
+            _ if closed => return Err(VelvetIOError::eof()),
+            Err(e) => retry.report(&selection_prompt, &input, &e.during(prompt, attempts + 1)),
         }
+        out_of_attempts(&mut attempts, policy)?;
+    };
+    let result = ask();
+    result.map_err(|e| e.during(prompt, attempts + 1))
+}
 
-        let parts: Vec<&str> = input.split(',').map(|s| s.trim()).collect();
-        let mut selected = Vec::new();
-        let mut valid = true;
+/// Order of the options `multi_select` hands back
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SelectionOrder {
+    /// As typed - "3,1" gives the third option, then the first
+    #[default]
+    Typed,
+    /// As listed in the menu, whatever order they were typed in
+    Listed,
+}
 
-        for part in parts {
-            match part.parse::<usize>() {
-                Ok(num) if num >= 1 && num <= choices.len() => {
-                    selected.push(choices[num - 1].clone());
-                }
-                Ok(num) => {
-                    eprintln!("❌ {} is not a valid option (1-{})", num, choices.len());
-                    valid = false;
-                    break;
-                }
-                Err(_) => {
-                    eprintln!("❌ Please enter numbers separated by commas");
-                    valid = false;
-                    break;
-                }
+static LISTED_ORDER: AtomicBool = AtomicBool::new(false);
+
+/// Choose the order `multi_select` returns picks in (as typed, by default)
+///
+/// A `Session` can pick its own with `Session::selection_order`.
+///
+/// Either way, an option typed twice is only returned once.
+pub fn set_selection_order(order: SelectionOrder) {
+    LISTED_ORDER.store(order == SelectionOrder::Listed, Ordering::Relaxed);
+}
+
+pub(crate) fn listed_order() -> bool {
+    match session::selection_order() {
+        Some(order) => order == SelectionOrder::Listed,
+        None => LISTED_ORDER.load(Ordering::Relaxed),
+    }
+}
+
+// Indices picked by a comma-separated answer, or "all" / "none"
+pub(crate) fn pick_many(input: &str, count: usize) -> Result<Vec<usize>> {
+    match input {
+        "none" => return Ok(Vec::new()),
+        "all" => return Ok((0..count).collect()),
+        _ => {}
+    }
+
+    let mut selected = Vec::new();
+    for part in input.split(',').map(|s| s.trim()) {
+        match part.parse::<usize>() {
+            Ok(num) if num >= 1 && num <= count => selected.push(num - 1),
+            Ok(num) => {
+                return Err(VelvetIOError::validation_error(
+                    input,
+                    format!("{} is not a valid option (1-{})", num, count),
+                ));
+            }
+            Err(_) => {
+                return Err(VelvetIOError::validation_error(
+                    input,
+                    "Please enter numbers separated by commas",
+                ));
             }
         }
+    }
 
-        if valid {
-            return selected;
+    // Callers almost always treat the picks as a set
+    let mut repeated = Vec::new();
+    let mut seen = vec![false; count];
+    selected.retain(|&i| {
+        let first = !seen[i];
+        if !first && !repeated.contains(&(i + 1)) {
+            repeated.push(i + 1);
         }
+        seen[i] = true;
+        first
+    });
+    if !repeated.is_empty() {
+        let numbers: Vec<String> = repeated.iter().map(|n| n.to_string()).collect();
+        warn(format!(
+            "Counted repeated choices once: {}",
+            numbers.join(", ")
+        ));
+    }
+
+    if listed_order() {
+        selected.sort_unstable();
     }
+    Ok(selected)
 }
 
 /// Form builder for collecting multiple inputs
 pub struct Form {
     fields: Vec<FormField>,
+    review: bool,
+    deadline: Option<Duration>,
+    after_deadline: AfterDeadline,
+    // A mistake made while building it, reported once it's collected
+    error: Option<VelvetIOError>,
 }
 
-struct FormField {
-    key: String,
-    prompt: String,
-    field_type: FieldType,
+pub(crate) struct FormField {
+    pub(crate) key: String,
+    pub(crate) prompt: String,
+    pub(crate) field_type: FieldType,
+    pub(crate) default: Option<String>,
+    pub(crate) sensitive: bool,
+    // Applied to the accepted answer before it's stored
+    pub(crate) map: Option<Box<MapFn>>,
+    // Its own limit on invalid answers, and what it gets without an answer
+    pub(crate) max_attempts: Option<u32>,
+    pub(crate) fallback: Option<String>,
 }
 
-enum FieldType {
+impl FormField {
+    pub(crate) fn new(key: &str, prompt: impl Into<String>, field_type: FieldType) -> Self {
+        Self {
+            key: key.to_string(),
+            prompt: prompt.into(),
+            field_type,
+            default: None,
+            sensitive: false,
+            map: None,
+            max_attempts: None,
+            fallback: None,
+        }
+    }
+
+    // Tagged, or with a key that looks it
+    pub(crate) fn is_sensitive(&self) -> bool {
+        self.sensitive || redact::is_sensitive(&self.key)
+    }
+}
+
+pub(crate) enum FieldType {
     Text,
     Number,
     Boolean,
     Choice(Vec<String>),
+    ValueChoice(Vec<Choice<String>>),
     MultiChoice(Vec<String>),
     Optional,
+    OptionalNumber,
+    OptionalChoice(Vec<String>),
     ValidatedText {
         validator: Box<dyn Fn(&str) -> bool>,
         error_msg: String,
     },
+    #[cfg(feature = "phone")]
+    Phone,
+    // Turned into a `Choice` once the earlier answers are in
+    DependentChoice(Box<ChoicesFn>),
 }
 
+pub(crate) type ChoicesFn = dyn Fn(&HashMap<String, String>) -> Vec<String>;
+pub(crate) type MapFn = dyn Fn(&str) -> String;
+
 impl Form {
     pub fn new() -> Self {
-        Self { fields: Vec::new() }
+        Self {
+            fields: Vec::new(),
+            review: false,
+            deadline: None,
+            after_deadline: AfterDeadline::Abort,
+            error: None,
+        }
     }
 
     pub fn text(mut self, key: &str, prompt: &str) -> Self {
-        self.fields.push(FormField {
-            key: key.to_string(),
-            prompt: prompt.to_string(),
-            field_type: FieldType::Text,
-        });
+        self.fields
+            .push(FormField::new(key, prompt, FieldType::Text));
         self
     }
 
     pub fn number(mut self, key: &str, prompt: &str) -> Self {
-        self.fields.push(FormField {
-            key: key.to_string(),
-            prompt: prompt.to_string(),
-            field_type: FieldType::Number,
-        });
+        self.fields
+            .push(FormField::new(key, prompt, FieldType::Number));
+        self
+    }
+
+    /// Phone number, stored in E.164 form like `+15551234567`
+    #[cfg(feature = "phone")]
+    pub fn phone(mut self, key: &str, prompt: &str) -> Self {
+        self.fields
+            .push(FormField::new(key, prompt, FieldType::Phone));
         self
     }
 
     pub fn boolean(mut self, key: &str, prompt: &str) -> Self {
-        self.fields.push(FormField {
-            key: key.to_string(),
-            prompt: prompt.to_string(),
-            field_type: FieldType::Boolean,
-        });
+        self.fields
+            .push(FormField::new(key, prompt, FieldType::Boolean));
         self
     }
 
     pub fn choice(mut self, key: &str, prompt: &str, choices: &[&str]) -> Self {
-        self.fields.push(FormField {
-            key: key.to_string(),
-            prompt: prompt.to_string(),
-            field_type: FieldType::Choice(choices.iter().map(|s| s.to_string()).collect()),
-        });
+        self.fields.push(FormField::new(
+            key,
+            prompt,
+            FieldType::Choice(choices.iter().map(|s| s.to_string()).collect()),
+        ));
+        self
+    }
+
+    /// Choice shown by label, with the picked option's value as the answer
+    ///
+    /// ```no_run
+    /// use velvetio::{Choice, form};
+    ///
+    /// let answers = form()
+    ///     .choice_values("region", "Region", &[
+    ///         Choice::new("Production (us-east-1)", "us-east-1"),
+    ///         Choice::new("Staging (eu-west-1)", "eu-west-1"),
+    ///     ])
+    ///     .collect();
+    /// // answers["region"] is "us-east-1" or "eu-west-1"
+    /// ```
+    pub fn choice_values<V>(mut self, key: &str, prompt: &str, choices: &[Choice<V>]) -> Self
+    where
+        V: std::fmt::Display,
+    {
+        let choices = choices
+            .iter()
+            .map(|c| Choice::new(c.label(), c.value().to_string()))
+            .collect();
+        self.fields
+            .push(FormField::new(key, prompt, FieldType::ValueChoice(choices)));
+        self
+    }
+
+    /// Choice whose options depend on earlier answers
+    ///
+    /// `choices` gets the answers so far, keyed like the results, when the
+    /// field comes up.
+    ///
+    /// ```no_run
+    /// let answers = velvetio::form()
+    ///     .choice("region", "Region", &["eu", "us"])
+    ///     .choice_with("zone", "Zone", |answers| match answers["region"].as_str() {
+    ///         "eu" => vec!["eu-west-1", "eu-central-1"],
+    ///         _ => vec!["us-east-1", "us-west-2"],
+    ///     })
+    ///     .collect();
+    /// ```
+    pub fn choice_with<F, S>(mut self, key: &str, prompt: &str, choices: F) -> Self
+    where
+        F: Fn(&HashMap<String, String>) -> Vec<S> + 'static,
+        S: ToString,
+    {
+        let choices = move |answers: &HashMap<String, String>| {
+            choices(answers).iter().map(|c| c.to_string()).collect()
+        };
+        self.fields.push(FormField::new(
+            key,
+            prompt,
+            FieldType::DependentChoice(Box::new(choices)),
+        ));
         self
     }
 
     pub fn multi_choice(mut self, key: &str, prompt: &str, choices: &[&str]) -> Self {
-        self.fields.push(FormField {
-            key: key.to_string(),
-            prompt: prompt.to_string(),
-            field_type: FieldType::MultiChoice(choices.iter().map(|s| s.to_string()).collect()),
-        });
+        self.fields.push(FormField::new(
+            key,
+            prompt,
+            FieldType::MultiChoice(choices.iter().map(|s| s.to_string()).collect()),
+        ));
         self
     }
 
+    /// Text that can be skipped with enter or a word like "none" - skipped
+    /// fields are left out of the results
     pub fn optional(mut self, key: &str, prompt: &str) -> Self {
-        self.fields.push(FormField {
-            key: key.to_string(),
-            prompt: format!("{} (optional)", prompt),
-            field_type: FieldType::Optional,
-        });
+        self.fields.push(FormField::new(
+            key,
+            format!("{} (optional)", prompt),
+            FieldType::Optional,
+        ));
+        self
+    }
+
+    /// Number that can be skipped, like `optional`
+    pub fn optional_number(mut self, key: &str, prompt: &str) -> Self {
+        self.fields.push(FormField::new(
+            key,
+            format!("{} (optional)", prompt),
+            FieldType::OptionalNumber,
+        ));
+        self
+    }
+
+    /// Choice that can be skipped, like `optional`
+    pub fn optional_choice(mut self, key: &str, prompt: &str, choices: &[&str]) -> Self {
+        self.fields.push(FormField::new(
+            key,
+            format!("{} (optional)", prompt),
+            FieldType::OptionalChoice(choices.iter().map(|s| s.to_string()).collect()),
+        ));
         self
     }
 
@@ -261,56 +2175,618 @@ impl Form {
     where
         F: Fn(&str) -> bool + 'static,
     {
-        self.fields.push(FormField {
-            key: key.to_string(),
-            prompt: prompt.to_string(),
-            field_type: FieldType::ValidatedText {
+        self.fields.push(FormField::new(
+            key,
+            prompt,
+            FieldType::ValidatedText {
                 validator: Box::new(validator),
                 error_msg: error_msg.to_string(),
             },
-        });
+        ));
+        self
+    }
+
+    /// Default for the field added last - hit enter to use it
+    ///
+    /// Choice fields take the label, multi-choice fields a comma-separated list
+    /// of labels. Validated fields check the default like any other answer.
+    /// A cached answer from a previous run wins over this default.
+    pub fn default(mut self, value: &str) -> Self {
+        if let Some(field) = self.fields.last_mut() {
+            field.default = Some(value.to_string());
+        }
+        self
+    }
+
+    /// Treat the field added last as sensitive
+    ///
+    /// Its answer never shows up in transcripts, the answer cache, error
+    /// messages or the review - they show `***` instead. Fields whose key
+    /// matches a sensitive pattern (see
+    /// [`set_sensitive_patterns`](crate::set_sensitive_patterns)) already are.
+    pub fn sensitive(mut self) -> Self {
+        if let Some(field) = self.fields.last_mut() {
+            field.sensitive = true;
+        }
+        self
+    }
+
+    /// Give up on the field added last after `max` invalid answers, however
+    /// many the form allows
+    ///
+    /// With a [`fallback`](Form::fallback) the field takes that instead of
+    /// failing the form, so one stubborn field doesn't stop an unattended
+    /// run.
+    pub fn max_attempts(mut self, max: u32) -> Self {
+        if let Some(field) = self.fields.last_mut() {
+            field.max_attempts = Some(max.max(1));
+        }
+        self
+    }
+
+    /// What the field added last gets when nobody answers it: after its
+    /// invalid answers run out, or once input is closed (a headless run out
+    /// of answers, a pipe that ended)
+    ///
+    /// Written like an answer, like [`default`](Form::default). Unlike a
+    /// default it's never offered at the prompt, and isn't cached. It's
+    /// mentioned on stderr when it's used.
+    ///
+    /// ```
+    /// use velvetio::{form, run_scripted};
+    ///
+    /// let run = run_scripted(&["eighty", "http"], || {
+    ///     form()
+    ///         .number("port", "Port")
+    ///         .max_attempts(1)
+    ///         .fallback("8080")
+    ///         .text("scheme", "Scheme")
+    ///         .try_collect()
+    /// });
+    /// let answers = run.value.unwrap();
+    /// assert_eq!(answers["port"], "8080");
+    /// assert_eq!(answers["scheme"], "http");
+    /// ```
+    pub fn fallback(mut self, value: &str) -> Self {
+        if let Some(field) = self.fields.last_mut() {
+            field.fallback = Some(value.to_string());
+        }
+        self
+    }
+
+    /// Run the answer for `key` through `map` once it's been accepted, and
+    /// store what comes out
+    ///
+    /// For normalizing in one place: lowercased emails, trimmed slashes,
+    /// canonical paths. Later prompts, the review and the results see the
+    /// mapped answer. The cache and a review's re-ask offer the answer as it
+    /// was given, so `map` runs once on whatever is accepted.
+    ///
+    /// ```
+    /// use velvetio::{form, run_scripted};
+    ///
+    /// let run = run_scripted(&["Ada@Example.COM"], || {
+    ///     form()
+    ///         .text("email", "Email")
+    ///         .map("email", |email| email.to_lowercase())
+    ///         .collect()
+    /// });
+    /// assert_eq!(run.value["email"], "ada@example.com");
+    /// ```
+    ///
+    /// A `key` that isn't a field of the form is an error from `try_collect`
+    /// before anything is asked.
+    pub fn map<F>(mut self, key: &str, map: F) -> Self
+    where
+        F: Fn(&str) -> String + 'static,
+    {
+        match self.fields.iter_mut().rev().find(|field| field.key == key) {
+            Some(field) => field.map = Some(Box::new(map)),
+            None => {
+                let message = format!("map() for '{}', which isn't a field of this form", key);
+                let error = VelvetIOError::new(message, key, "a field of this form").with_key(key);
+                self.error.get_or_insert(error);
+            }
+        }
+        self
+    }
+
+    /// List the answers numbered at the end, so any one of them can be
+    /// asked again before the form is done
+    ///
+    /// Typing a number re-asks that field with its answer as the default,
+    /// enter on its own finishes.
+    pub fn review(mut self) -> Self {
+        self.review = true;
+        self
+    }
+
+    /// Give the whole form this long to be filled in
+    ///
+    /// Every prompt checks the clock, and one waiting on the terminal stops
+    /// waiting when time's up. What happens then is up to
+    /// [`after_deadline`](Form::after_deadline): by default the form fails
+    /// with a `Timeout` error (exit code 124 if it gets that far), naming the
+    /// field it stopped at.
+    ///
+    /// While the deadline runs, answers are read as plain lines on another
+    /// thread, without the line editor. Afterwards the terminal is back to
+    /// normal, though a line that was still being typed when time ran out
+    /// answers the next prompt.
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use velvetio::AfterDeadline;
+    ///
+    /// let setup = velvetio::form()
+    ///     .text("region", "Region")
+    ///     .default("eu-west-1")
+    ///     .deadline(Duration::from_secs(60))
+    ///     .after_deadline(AfterDeadline::UseDefaults)
+    ///     .collect();
+    /// ```
+    pub fn deadline(mut self, limit: Duration) -> Self {
+        self.deadline = Some(limit);
+        self
+    }
+
+    /// What to do with the rest of the form once its deadline has passed
+    pub fn after_deadline(mut self, then: AfterDeadline) -> Self {
+        self.after_deadline = then;
         self
     }
 
+    /// What the form would ask, one line per field, without asking it
+    ///
+    /// Each line has the key, prompt, type (with the choices) and default,
+    /// so a `--describe` flag can preview the form and a reviewer can diff
+    /// it. Prompts are shown as written, `{key}` placeholders and all, and
+    /// choices that depend on earlier answers say so.
+    ///
+    /// ```
+    /// let setup = velvetio::form()
+    ///     .text("name", "Name")
+    ///     .choice("tier", "Tier", &["free", "pro"])
+    ///     .default("free")
+    ///     .optional("note", "Note for {name}");
+    /// assert_eq!(setup.describe(), "\
+    /// name: Name (text)
+    /// tier: Tier (one of: free, pro) [free]
+    /// note: Note for {name} (optional) (text)
+    /// ");
+    /// ```
+    pub fn describe(&self) -> String {
+        let mut out = String::new();
+        for field in &self.fields {
+            crate::flow::describe_field(&mut out, "", field);
+        }
+        if self.review {
+            out.push_str("(then a review of the answers)\n");
+        }
+        out
+    }
+
     /// Run through all fields and collect the results
+    ///
+    /// With caching enabled, each field offers its last answer as the default.
+    /// Exits the program if input is closed partway through, or if a choice
+    /// field has no choices - use `try_collect` for forms built at runtime.
     pub fn collect(self) -> HashMap<String, String> {
+        self.collect_inner(AttemptPolicy::unlimited())
+            .unwrap_or_else(|e| give_up(e))
+    }
+
+    /// Like `collect`, but returns the first error instead of exiting
+    ///
+    /// Each field accepts up to `MAX_ATTEMPTS` invalid answers.
+    pub fn try_collect(self) -> Result<HashMap<String, String>> {
+        self.collect_inner(AttemptPolicy::limited(MAX_ATTEMPTS))
+    }
+
+    /// Like `collect`, but keeps what was typed for each field next to the
+    /// answer it became - see [`FormData::raw`]
+    pub fn collect_data(self) -> FormData {
+        let typed = KeepTyped::start();
+        let answers = self.collect();
+        let typed = typed.finish();
+        FormData::with_typed(answers, typed.lines.unwrap_or_default(), typed.timings)
+    }
+
+    /// Like `try_collect`, keeping what was typed like `collect_data`
+    pub fn try_collect_data(self) -> Result<FormData> {
+        let typed = KeepTyped::start();
+        let answers = self.try_collect()?;
+        let typed = typed.finish();
+        Ok(FormData::with_typed(
+            answers,
+            typed.lines.unwrap_or_default(),
+            typed.timings,
+        ))
+    }
+
+    fn collect_inner(self, policy: AttemptPolicy) -> Result<HashMap<String, String>> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        let _turn = lock::hold();
+        let _deadline = deadline::scope(self.deadline, self.after_deadline);
         let mut results = HashMap::new();
+        // Answers before `map`, for the review to offer again
+        let mut given = HashMap::new();
+        let options = PromptOptions::with_policy(policy.clone());
 
-        for field in self.fields {
-            let value = match field.field_type {
-                FieldType::Text => ask::<String>(&field.prompt),
-                FieldType::Number => ask::<f64>(&field.prompt).to_string(),
-                FieldType::Boolean => ask::<bool>(&field.prompt).to_string(),
-                FieldType::Choice(choices) => {
-                    let choice_refs: Vec<&str> = choices.iter().map(|s| s.as_str()).collect();
-                    choose(&field.prompt, &choice_refs).to_string()
-                }
-                FieldType::MultiChoice(choices) => {
-                    let choice_refs: Vec<&str> = choices.iter().map(|s| s.as_str()).collect();
-                    let selected = multi_select(&field.prompt, &choice_refs);
-                    selected.join(", ")
+        for field in &self.fields {
+            if let Some(answer) = Self::answer(field, &mut results, None, &policy, &options)? {
+                given.insert(field.key.clone(), answer);
+            }
+        }
+        if self.review {
+            self.review_answers(&mut results, &mut given, &policy, &options)?;
+        }
+
+        Ok(results)
+    }
+
+    // Asks `field` and files the answer under its key. A skipped optional
+    // field has no answer, and loses the one it had. Returns the answer as
+    // given when `map` changed what was filed.
+    pub(crate) fn answer(
+        field: &FormField,
+        results: &mut HashMap<String, String>,
+        current: Option<&str>,
+        policy: &AttemptPolicy,
+        options: &PromptOptions,
+    ) -> Result<Option<String>> {
+        let _hidden = redact::scope(field.is_sensitive());
+        let _field = debug::field(&field.key);
+        KeepTyped::forget_last();
+        // A field's own attempt limit beats the form's
+        let capped;
+        let (policy, options) = match field.max_attempts {
+            Some(max) => {
+                let policy = policy.clone().capped(max);
+                capped = (PromptOptions::with_policy(policy.clone()), policy);
+                (&capped.1, &capped.0)
+            }
+            None => (policy, options),
+        };
+        let mut asked = true;
+        let started = Instant::now();
+        let value = match Self::ask_field(field, results, current, policy, options) {
+            Err(e) if e.is_timeout() && deadline::use_defaults() => {
+                asked = false;
+                Self::out_of_time(field, results, current)
+                    .or_else(|| Self::unanswered(field, results))
+                    .ok_or(e)
+            }
+            Err(e) if e.kind == ErrorKind::TooManyAttempts || e.is_eof() => {
+                asked = false;
+                Self::unanswered(field, results).ok_or(e)
+            }
+            // With one attempt, the invalid answer's own error is the one
+            // that comes back
+            Err(e)
+                if policy.max_attempts() == Some(1)
+                    && field.fallback.is_some()
+                    && matches!(e.kind, ErrorKind::Parse | ErrorKind::Validation) =>
+            {
+                report(&e);
+                asked = false;
+                Self::unanswered(field, results).ok_or(e)
+            }
+            value => value,
+        }
+        .map_err(|e| e.with_key(&field.key))?;
+        KeepTyped::file(&field.key, started.elapsed());
+        match value {
+            Some(value) => {
+                // A fallback isn't anybody's answer, so it's not offered next
+                // time. What's offered is the answer before `map`, which only
+                // shapes what's stored.
+                if asked {
+                    cache::remember(&field.key, &value);
                 }
-                FieldType::Optional => {
-                    let input = ask::<String>(&field.prompt);
-                    if input.trim().is_empty() {
-                        "".to_string()
-                    } else {
-                        input
+                let (stored, given) = match &field.map {
+                    Some(map) => (map(&value), Some(value)),
+                    None => (value, None),
+                };
+                results.insert(field.key.clone(), stored);
+                Ok(given)
+            }
+            None => {
+                results.remove(&field.key);
+                Ok(None)
+            }
+        }
+    }
+
+    // What a field gets once time's up: its default, or skipped if it's
+    // optional. `None` if it has to be answered and has no default.
+    fn out_of_time(
+        field: &FormField,
+        results: &HashMap<String, String>,
+        current: Option<&str>,
+    ) -> Option<Option<String>> {
+        let default = field
+            .default
+            .as_ref()
+            .map(|default| fill_value(default, results));
+        let default = match field.field_type {
+            FieldType::Optional | FieldType::OptionalNumber | FieldType::OptionalChoice(_) => {
+                return Some(current.map(str::to_string).or(default));
+            }
+            _ => current
+                .map(str::to_string)
+                .or_else(|| cache::cached(&field.key).filter(|value| !value.is_empty()))
+                .or(default)?,
+        };
+        Some(Some(Self::stored(field, default)?))
+    }
+
+    // The field's fallback, for when nobody answered it: out of attempts, or
+    // out of input. Said on stderr, so an unattended run's log shows it.
+    fn unanswered(field: &FormField, results: &HashMap<String, String>) -> Option<Option<String>> {
+        let template = field.fallback.as_deref()?;
+        let fallback = fill_value(template, results);
+        let shown = match field.is_sensitive() {
+            true => crate::REDACTED.to_string(),
+            false => fill(template, results),
+        };
+        warn(format!(
+            "No answer for {}, using {}",
+            markup::strip(&fill(&field.prompt, results)),
+            shown
+        ));
+        Some(Some(Self::stored(field, fallback)?))
+    }
+
+    // Written like an answer, so stored the way one would be
+    fn stored(field: &FormField, text: String) -> Option<String> {
+        match field.field_type {
+            FieldType::Number => Some(f64::parse(&text).ok()?.to_string()),
+            FieldType::Boolean => Some(bool::parse(&text).ok()?.to_string()),
+            _ => Some(text),
+        }
+    }
+
+    // The answers so far, numbered, until enter says they're fine
+    fn review_answers(
+        &self,
+        results: &mut HashMap<String, String>,
+        given: &mut HashMap<String, String>,
+        policy: &AttemptPolicy,
+        options: &PromptOptions,
+    ) -> Result<()> {
+        let mut attempts = 0;
+        let mut show = true;
+        let minimal = theme::with_theme(|theme| theme.minimal);
+        loop {
+            if show {
+                self.show_answers(results);
+            }
+            // Minimal themes list the answers once
+            show = !minimal;
+
+            let (input, closed) = match next_answer("Number to change (enter to finish)", options) {
+                // Out of time, and the answers are what they are
+                Err(e) if e.is_timeout() && deadline::use_defaults() => return Ok(()),
+                answer => answer?,
+            };
+            if input.is_empty() {
+                return Ok(());
+            }
+            let count = self.fields.len();
+            match input.parse::<usize>() {
+                Ok(number @ 1..) if number <= count => {
+                    let field = &self.fields[number - 1];
+                    let current = given.remove(&field.key);
+                    let current = current.or_else(|| results.get(&field.key).cloned());
+                    if let Some(answer) =
+                        Self::answer(field, results, current.as_deref(), policy, options)?
+                    {
+                        given.insert(field.key.clone(), answer);
                     }
                 }
-                FieldType::ValidatedText {
-                    validator,
-                    error_msg,
-                } => {
-                    ask_with_validation(&field.prompt, |s: &String| validator(s), Some(&error_msg))
+                _ if closed => return Ok(()),
+                _ => {
+                    let error = VelvetIOError::validation_error(
+                        &input,
+                        format!("Please enter a number between 1 and {}", count),
+                    );
+                    report(&error.during("Review", attempts + 1));
+                    out_of_attempts(&mut attempts, policy)?;
+                    show = false;
                 }
-            };
+            }
+        }
+    }
+
+    fn show_answers(&self, results: &HashMap<String, String>) {
+        let pairs: Vec<(String, String)> = self
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let prompt = markup::strip(&i18n::prompt(&field.key, &field.prompt, results));
+                let answer = match results.get(&field.key) {
+                    Some(_) if field.is_sensitive() => crate::REDACTED,
+                    Some(answer) => answer.as_str(),
+                    None => "-",
+                };
+                (format!("{}. {}", i + 1, prompt), answer.to_string())
+            })
+            .collect();
+        out!("{}", summary("Review", &pairs));
+    }
+
+    // `current` is the answer under review, which beats any other default
+    fn ask_field(
+        field: &FormField,
+        results: &HashMap<String, String>,
+        current: Option<&str>,
+        policy: &AttemptPolicy,
+        options: &PromptOptions,
+    ) -> Result<Option<String>> {
+        // Earlier answers fill `{key}` placeholders in the prompt and default.
+        // A translation for the key wins over the prompt as written.
+        let prompt = i18n::prompt(&field.key, &field.prompt, results);
+        let field_default = field
+            .default
+            .as_ref()
+            .map(|default| fill_value(default, results));
+        // A default made from a sensitive answer is shown like one
+        let from_hidden = field
+            .default
+            .as_ref()
+            .is_some_and(|default| fill(default, results) != fill_value(default, results));
+        let hiding_default;
+        let options = match from_hidden {
+            true => {
+                hiding_default = PromptOptions {
+                    redact_default: true,
+                    ..options.clone()
+                };
+                &hiding_default
+            }
+            false => options,
+        };
+        let resolved;
+        let field_type = match &field.field_type {
+            FieldType::DependentChoice(choices) => {
+                resolved = FieldType::Choice(choices(results));
+                &resolved
+            }
+            field_type => field_type,
+        };
+
+        // Optional fields are never pre-filled from the cache, so enter still means "skip"
+        let default = match field_type {
+            FieldType::Optional | FieldType::OptionalNumber | FieldType::OptionalChoice(_) => {
+                field_default
+            }
+            _ => cache::cached(&field.key)
+                .filter(|value| !value.is_empty())
+                .or(field_default),
+        };
+        let default = current.map(str::to_string).or(default);
+        let default = default.as_deref();
+
+        let value = match field_type {
+            FieldType::Text => ask_loop::<String>(&prompt, default, &accept_any, "", options)?.0,
+            FieldType::Number => ask_loop::<f64>(&prompt, default, &accept_any, "", options)?
+                .0
+                .to_string(),
+            #[cfg(feature = "phone")]
+            FieldType::Phone => {
+                ask_loop::<crate::PhoneNumber>(&prompt, default, &accept_any, "", options)?
+                    .0
+                    .to_string()
+            }
+            FieldType::Boolean => ask_loop::<bool>(&prompt, default, &accept_any, "", options)?
+                .0
+                .to_string(),
+            FieldType::Choice(choices) => {
+                let choice_refs: Vec<&str> = choices.iter().map(|s| s.as_str()).collect();
+                let default = default.and_then(|d| choice_refs.iter().position(|c| *c == d));
+                choose_inner(&prompt, &choice_refs, default, policy)?.to_string()
+            }
+            FieldType::ValueChoice(choices) => {
+                // Defaults and cached answers are values, not labels
+                let default = default.and_then(|d| choices.iter().position(|c| c.value() == d));
+                choose_inner(&prompt, choices, default, policy)?.into_value()
+            }
+            FieldType::DependentChoice(_) => unreachable!("resolved before asking"),
+            FieldType::MultiChoice(choices) => {
+                if choices.is_empty() {
+                    String::new()
+                } else {
+                    let choice_refs: Vec<&str> = choices.iter().map(|s| s.as_str()).collect();
+                    let default = default.map(|d| saved_picks(d, &choice_refs));
+                    multi_select_inner(&prompt, &choice_refs, default.as_deref(), policy, false)?
+                        .join(", ")
+                }
+            }
+            // Skippable fields return early, since they may have no value
+            FieldType::Optional => {
+                let (value, _) =
+                    ask_loop::<Option<String>>(&prompt, default, &accept_any, "", options)?;
+                return Ok(value);
+            }
+            FieldType::OptionalNumber => {
+                let (value, _) =
+                    ask_loop::<Option<f64>>(&prompt, default, &accept_any, "", options)?;
+                return Ok(value.map(|n| n.to_string()));
+            }
+            FieldType::OptionalChoice(choices) => {
+                let choice_refs: Vec<&str> = choices.iter().map(|s| s.as_str()).collect();
+                let default = default.and_then(|d| choice_refs.iter().position(|c| *c == d));
+                let choice =
+                    choose_or_skip(&prompt, &choice_refs, default, policy, Menu::Optional)?;
+                return Ok(choice.map(|c| c.to_string()));
+            }
+            FieldType::ValidatedText {
+                validator,
+                error_msg,
+            } => {
+                ask_loop(
+                    &prompt,
+                    default,
+                    &|s: &String| validator(s),
+                    error_msg,
+                    options,
+                )?
+                .0
+            }
+        };
+        Ok(Some(value))
+    }
+}
 
-            results.insert(field.key, value);
+// `{key}` replaced by that answer, `{{` and `}}` for literal braces. Unknown
+// keys are left as they are, so a typo shows up in the prompt. Sensitive
+// answers show as `***`, since this is text for the screen.
+pub(crate) fn fill(template: &str, answers: &HashMap<String, String>) -> String {
+    fill_in(template, answers, true)
+}
+
+// `fill` for a value the field will get, like a default, so sensitive
+// answers go in as they are
+pub(crate) fn fill_value(template: &str, answers: &HashMap<String, String>) -> String {
+    fill_in(template, answers, false)
+}
+
+fn fill_in(template: &str, answers: &HashMap<String, String>, redact: bool) -> String {
+    if !template.contains(['{', '}']) {
+        return template.to_string();
+    }
+
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            out.push_str(&rest[..1]);
+            rest = &rest[2..];
+            continue;
         }
 
-        results
+        if let Some(after) = rest.strip_prefix('{')
+            && let Some((key, tail)) = after.split_once('}')
+            && let Some(answer) = answers.get(key.trim())
+        {
+            match redact && redact::hidden(key.trim()) {
+                true => out.push_str(crate::REDACTED),
+                false => out.push_str(answer),
+            }
+            rest = tail;
+        } else {
+            out.push_str(&rest[..1]);
+            rest = &rest[1..];
+        }
     }
+    out.push_str(rest);
+    out
 }
 
 pub fn form() -> Form {
@@ -0,0 +1,305 @@
+// src/countries.rs
+
+// ISO 3166-1 country codes, generated from tzdata's iso3166.tab, with the
+// everyday names people search for where they differ from the listed one.
+
+use crate::core::give_up;
+use crate::search::choose_entry;
+use crate::{AttemptPolicy, MAX_ATTEMPTS, Result};
+
+/// Pick a country and get its ISO 3166-1 alpha-2 code, like `"DE"`
+///
+/// Type `/` and part of a name to search - `/holland` and `/uk` work too.
+/// Exits the program if input is closed before a country is picked.
+///
+/// ```no_run
+/// let country = velvetio::choose_country("Billing country");
+/// println!("{}", velvetio::country_name(country).unwrap());
+/// ```
+pub fn choose_country(prompt: &str) -> &'static str {
+    country_loop(prompt, &AttemptPolicy::unlimited()).unwrap_or_else(|e| give_up(e))
+}
+
+/// `choose_country` that gives up after `MAX_ATTEMPTS` invalid answers
+pub fn try_choose_country(prompt: &str) -> Result<&'static str> {
+    country_loop(prompt, &AttemptPolicy::limited(MAX_ATTEMPTS))
+}
+
+/// The English name for an ISO 3166-1 alpha-2 code, any case
+pub fn country_name(code: &str) -> Option<&'static str> {
+    COUNTRIES
+        .iter()
+        .find(|(id, _, _)| id.eq_ignore_ascii_case(code))
+        .map(|(_, name, _)| *name)
+}
+
+fn country_loop(prompt: &str, policy: &AttemptPolicy) -> Result<&'static str> {
+    let mut entries: Vec<_> = COUNTRIES
+        .iter()
+        .map(|(code, name, other)| (*code, format!("{} ({})", name, code), other.to_string()))
+        .collect();
+    // Listed by name, not code
+    entries.sort_by(|a, b| a.1.cmp(&b.1));
+    choose_entry(prompt, &entries, policy)
+}
+
+// (code, name, other names)
+const COUNTRIES: &[(&str, &str, &str)] = &[
+    ("AD", "Andorra", ""),
+    ("AE", "United Arab Emirates", "UAE Emirates"),
+    ("AF", "Afghanistan", ""),
+    ("AG", "Antigua & Barbuda", ""),
+    ("AI", "Anguilla", ""),
+    ("AL", "Albania", ""),
+    ("AM", "Armenia", ""),
+    ("AO", "Angola", ""),
+    ("AQ", "Antarctica", ""),
+    ("AR", "Argentina", ""),
+    ("AS", "Samoa (American)", ""),
+    ("AT", "Austria", ""),
+    ("AU", "Australia", ""),
+    ("AW", "Aruba", ""),
+    ("AX", "Åland Islands", "Aland Islands"),
+    ("AZ", "Azerbaijan", ""),
+    ("BA", "Bosnia & Herzegovina", ""),
+    ("BB", "Barbados", ""),
+    ("BD", "Bangladesh", ""),
+    ("BE", "Belgium", ""),
+    ("BF", "Burkina Faso", ""),
+    ("BG", "Bulgaria", ""),
+    ("BH", "Bahrain", ""),
+    ("BI", "Burundi", ""),
+    ("BJ", "Benin", ""),
+    ("BL", "St Barthelemy", "Saint Barthelemy"),
+    ("BM", "Bermuda", ""),
+    ("BN", "Brunei", ""),
+    ("BO", "Bolivia", ""),
+    ("BQ", "Caribbean NL", ""),
+    ("BR", "Brazil", ""),
+    ("BS", "Bahamas", ""),
+    ("BT", "Bhutan", ""),
+    ("BV", "Bouvet Island", ""),
+    ("BW", "Botswana", ""),
+    ("BY", "Belarus", ""),
+    ("BZ", "Belize", ""),
+    ("CA", "Canada", ""),
+    ("CC", "Cocos (Keeling) Islands", ""),
+    (
+        "CD",
+        "Congo (Dem. Rep.)",
+        "DRC Democratic Republic of the Congo",
+    ),
+    ("CF", "Central African Rep.", ""),
+    ("CG", "Congo (Rep.)", "Republic of the Congo"),
+    ("CH", "Switzerland", ""),
+    ("CI", "Côte d'Ivoire", "Ivory Coast Cote d'Ivoire"),
+    ("CK", "Cook Islands", ""),
+    ("CL", "Chile", ""),
+    ("CM", "Cameroon", ""),
+    ("CN", "China", "People's Republic of China PRC"),
+    ("CO", "Colombia", ""),
+    ("CR", "Costa Rica", ""),
+    ("CU", "Cuba", ""),
+    ("CV", "Cape Verde", ""),
+    ("CW", "Curaçao", "Curacao"),
+    ("CX", "Christmas Island", ""),
+    ("CY", "Cyprus", ""),
+    ("CZ", "Czech Republic", "Czech Republic"),
+    ("DE", "Germany", ""),
+    ("DJ", "Djibouti", ""),
+    ("DK", "Denmark", ""),
+    ("DM", "Dominica", ""),
+    ("DO", "Dominican Republic", ""),
+    ("DZ", "Algeria", ""),
+    ("EC", "Ecuador", ""),
+    ("EE", "Estonia", ""),
+    ("EG", "Egypt", ""),
+    ("EH", "Western Sahara", ""),
+    ("ER", "Eritrea", ""),
+    ("ES", "Spain", ""),
+    ("ET", "Ethiopia", ""),
+    ("FI", "Finland", ""),
+    ("FJ", "Fiji", ""),
+    ("FK", "Falkland Islands", ""),
+    ("FM", "Micronesia", ""),
+    ("FO", "Faroe Islands", ""),
+    ("FR", "France", ""),
+    ("GA", "Gabon", ""),
+    (
+        "GB",
+        "Britain (UK)",
+        "UK United Kingdom Great Britain England Scotland Wales",
+    ),
+    ("GD", "Grenada", ""),
+    ("GE", "Georgia", ""),
+    ("GF", "French Guiana", ""),
+    ("GG", "Guernsey", ""),
+    ("GH", "Ghana", ""),
+    ("GI", "Gibraltar", ""),
+    ("GL", "Greenland", ""),
+    ("GM", "Gambia", ""),
+    ("GN", "Guinea", ""),
+    ("GP", "Guadeloupe", ""),
+    ("GQ", "Equatorial Guinea", ""),
+    ("GR", "Greece", ""),
+    ("GS", "South Georgia & the South Sandwich Islands", ""),
+    ("GT", "Guatemala", ""),
+    ("GU", "Guam", ""),
+    ("GW", "Guinea-Bissau", ""),
+    ("GY", "Guyana", ""),
+    ("HK", "Hong Kong", ""),
+    ("HM", "Heard Island & McDonald Islands", ""),
+    ("HN", "Honduras", ""),
+    ("HR", "Croatia", ""),
+    ("HT", "Haiti", ""),
+    ("HU", "Hungary", ""),
+    ("ID", "Indonesia", ""),
+    ("IE", "Ireland", ""),
+    ("IL", "Israel", ""),
+    ("IM", "Isle of Man", ""),
+    ("IN", "India", ""),
+    ("IO", "British Indian Ocean Territory", ""),
+    ("IQ", "Iraq", ""),
+    ("IR", "Iran", ""),
+    ("IS", "Iceland", ""),
+    ("IT", "Italy", ""),
+    ("JE", "Jersey", ""),
+    ("JM", "Jamaica", ""),
+    ("JO", "Jordan", ""),
+    ("JP", "Japan", ""),
+    ("KE", "Kenya", ""),
+    ("KG", "Kyrgyzstan", ""),
+    ("KH", "Cambodia", ""),
+    ("KI", "Kiribati", ""),
+    ("KM", "Comoros", ""),
+    ("KN", "St Kitts & Nevis", ""),
+    ("KP", "Korea (North)", "North Korea"),
+    ("KR", "Korea (South)", "South Korea Republic of Korea"),
+    ("KW", "Kuwait", ""),
+    ("KY", "Cayman Islands", ""),
+    ("KZ", "Kazakhstan", ""),
+    ("LA", "Laos", ""),
+    ("LB", "Lebanon", ""),
+    ("LC", "St Lucia", ""),
+    ("LI", "Liechtenstein", ""),
+    ("LK", "Sri Lanka", ""),
+    ("LR", "Liberia", ""),
+    ("LS", "Lesotho", ""),
+    ("LT", "Lithuania", ""),
+    ("LU", "Luxembourg", ""),
+    ("LV", "Latvia", ""),
+    ("LY", "Libya", ""),
+    ("MA", "Morocco", ""),
+    ("MC", "Monaco", ""),
+    ("MD", "Moldova", ""),
+    ("ME", "Montenegro", ""),
+    ("MF", "St Martin (French)", ""),
+    ("MG", "Madagascar", ""),
+    ("MH", "Marshall Islands", ""),
+    ("MK", "North Macedonia", "Macedonia"),
+    ("ML", "Mali", ""),
+    ("MM", "Myanmar (Burma)", "Burma"),
+    ("MN", "Mongolia", ""),
+    ("MO", "Macau", ""),
+    ("MP", "Northern Mariana Islands", ""),
+    ("MQ", "Martinique", ""),
+    ("MR", "Mauritania", ""),
+    ("MS", "Montserrat", ""),
+    ("MT", "Malta", ""),
+    ("MU", "Mauritius", ""),
+    ("MV", "Maldives", ""),
+    ("MW", "Malawi", ""),
+    ("MX", "Mexico", ""),
+    ("MY", "Malaysia", ""),
+    ("MZ", "Mozambique", ""),
+    ("NA", "Namibia", ""),
+    ("NC", "New Caledonia", ""),
+    ("NE", "Niger", ""),
+    ("NF", "Norfolk Island", ""),
+    ("NG", "Nigeria", ""),
+    ("NI", "Nicaragua", ""),
+    ("NL", "Netherlands", "Holland Netherlands"),
+    ("NO", "Norway", ""),
+    ("NP", "Nepal", ""),
+    ("NR", "Nauru", ""),
+    ("NU", "Niue", ""),
+    ("NZ", "New Zealand", ""),
+    ("OM", "Oman", ""),
+    ("PA", "Panama", ""),
+    ("PE", "Peru", ""),
+    ("PF", "French Polynesia", ""),
+    ("PG", "Papua New Guinea", ""),
+    ("PH", "Philippines", ""),
+    ("PK", "Pakistan", ""),
+    ("PL", "Poland", ""),
+    ("PM", "St Pierre & Miquelon", ""),
+    ("PN", "Pitcairn", ""),
+    ("PR", "Puerto Rico", ""),
+    ("PS", "Palestine", ""),
+    ("PT", "Portugal", ""),
+    ("PW", "Palau", ""),
+    ("PY", "Paraguay", ""),
+    ("QA", "Qatar", ""),
+    ("RE", "Réunion", "Reunion"),
+    ("RO", "Romania", ""),
+    ("RS", "Serbia", ""),
+    ("RU", "Russia", "Russian Federation"),
+    ("RW", "Rwanda", ""),
+    ("SA", "Saudi Arabia", ""),
+    ("SB", "Solomon Islands", ""),
+    ("SC", "Seychelles", ""),
+    ("SD", "Sudan", ""),
+    ("SE", "Sweden", ""),
+    ("SG", "Singapore", ""),
+    ("SH", "St Helena", ""),
+    ("SI", "Slovenia", ""),
+    ("SJ", "Svalbard & Jan Mayen", ""),
+    ("SK", "Slovakia", ""),
+    ("SL", "Sierra Leone", ""),
+    ("SM", "San Marino", ""),
+    ("SN", "Senegal", ""),
+    ("SO", "Somalia", ""),
+    ("SR", "Suriname", ""),
+    ("SS", "South Sudan", ""),
+    ("ST", "Sao Tome & Principe", ""),
+    ("SV", "El Salvador", ""),
+    ("SX", "St Maarten (Dutch)", ""),
+    ("SY", "Syria", ""),
+    ("SZ", "Eswatini (Swaziland)", "Swaziland"),
+    ("TC", "Turks & Caicos Is", ""),
+    ("TD", "Chad", ""),
+    ("TF", "French S. Terr.", ""),
+    ("TG", "Togo", ""),
+    ("TH", "Thailand", ""),
+    ("TJ", "Tajikistan", ""),
+    ("TK", "Tokelau", ""),
+    ("TL", "East Timor", ""),
+    ("TM", "Turkmenistan", ""),
+    ("TN", "Tunisia", ""),
+    ("TO", "Tonga", ""),
+    ("TR", "Turkey", "Turkiye"),
+    ("TT", "Trinidad & Tobago", ""),
+    ("TV", "Tuvalu", ""),
+    ("TW", "Taiwan", "Republic of China"),
+    ("TZ", "Tanzania", ""),
+    ("UA", "Ukraine", ""),
+    ("UG", "Uganda", ""),
+    ("UM", "US minor outlying islands", ""),
+    ("US", "United States", "USA United States America"),
+    ("UY", "Uruguay", ""),
+    ("UZ", "Uzbekistan", ""),
+    ("VA", "Vatican City", "Vatican Holy See"),
+    ("VC", "St Vincent", ""),
+    ("VE", "Venezuela", ""),
+    ("VG", "Virgin Islands (UK)", ""),
+    ("VI", "Virgin Islands (US)", ""),
+    ("VN", "Vietnam", ""),
+    ("VU", "Vanuatu", ""),
+    ("WF", "Wallis & Futuna", ""),
+    ("WS", "Samoa (western)", ""),
+    ("YE", "Yemen", ""),
+    ("YT", "Mayotte", ""),
+    ("ZA", "South Africa", ""),
+    ("ZM", "Zambia", ""),
+    ("ZW", "Zimbabwe", ""),
+];
@@ -0,0 +1,316 @@
+// src/cron.rs
+
+// Cron expressions: checked field by field when they're typed, then read back
+// in words ("every day at 02:30") so a wrong schedule is caught before it's
+// deployed rather than at 3am.
+
+use crate::core::{PromptOptions, ask_loop, give_up};
+use crate::{AttemptPolicy, MAX_ATTEMPTS, Parse, Result, VelvetIOError};
+use std::fmt;
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const WEEKDAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+// Name, lowest and highest value of each field, seconds first
+const FIELDS: [(&str, u32, u32); 6] = [
+    ("second", 0, 59),
+    ("minute", 0, 59),
+    ("hour", 0, 23),
+    ("day of month", 1, 31),
+    ("month", 1, 12),
+    ("day of week", 0, 7),
+];
+
+/// A cron schedule: 5 fields (minute hour day month weekday), or 6 with
+/// seconds first
+///
+/// Fields take `*`, numbers, ranges (`1-5`), lists (`1,15`), steps (`*/15`),
+/// and month or weekday names (`JAN`, `mon-fri`).
+///
+/// ```
+/// use velvetio::{CronExpr, Parse};
+///
+/// let cron = CronExpr::parse("30 2 * * *").unwrap();
+/// assert_eq!(cron.describe(), "every day at 02:30");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpr {
+    fields: Vec<String>,
+    // Every field's matching values, seconds included (just 0 when not given)
+    values: [Vec<u32>; 6],
+}
+
+impl CronExpr {
+    /// The schedule in words, like "every 15 minutes" or
+    /// "at 09:00 on Monday to Friday"
+    pub fn describe(&self) -> String {
+        let [seconds, minutes, hours, days, months, weekdays] = &self.values;
+        let every = |field: usize| self.values[field].len() == full(field).len();
+
+        let time = if every(1) && every(2) && seconds == &[0] {
+            "every minute".to_string()
+        } else if every(1) && every(2) && every(0) {
+            "every second".to_string()
+        } else if every(1) && every(2) && evenly_spaced(seconds, 60).is_some() {
+            let step = evenly_spaced(seconds, 60).unwrap_or(1);
+            format!("every {} seconds", step)
+        } else if every(2) && seconds == &[0] && evenly_spaced(minutes, 60).is_some() {
+            let step = evenly_spaced(minutes, 60).unwrap_or(1);
+            format!("every {} minutes", step)
+        } else if every(2) && seconds == &[0] && minutes.len() == 1 {
+            format!("at minute {} of every hour", minutes[0])
+        } else if minutes.len() * hours.len() * seconds.len() <= 4 {
+            let mut times = Vec::new();
+            for hour in hours {
+                for minute in minutes {
+                    for second in seconds {
+                        times.push(match second {
+                            0 => format!("{:02}:{:02}", hour, minute),
+                            _ => format!("{:02}:{:02}:{:02}", hour, minute, second),
+                        });
+                    }
+                }
+            }
+            format!("at {}", and_list(&times))
+        } else {
+            let text = |field: usize| self.fields[field - (6 - self.fields.len())].clone();
+            match every(1) {
+                true => format!("every minute of hour {}", text(2)),
+                false => format!("at minute {} of hour {}", text(1), text(2)),
+            }
+        };
+
+        let mut when = match (every(3), every(5)) {
+            (true, true) if time.starts_with("every") => time,
+            (true, true) => format!("every day {}", time),
+            (true, false) => {
+                let names: Vec<String> = runs(weekdays, |d| WEEKDAYS[d as usize]);
+                format!("{} on {}", time, and_list(&names))
+            }
+            (false, true) => {
+                let numbers: Vec<String> = runs(days, |d| d.to_string());
+                let plural = if days.len() > 1 { "s" } else { "" };
+                format!(
+                    "{} on day{} {} of the month",
+                    time,
+                    plural,
+                    and_list(&numbers)
+                )
+            }
+            (false, false) => {
+                let numbers: Vec<String> = runs(days, |d| d.to_string());
+                let names: Vec<String> = runs(weekdays, |d| WEEKDAYS[d as usize]);
+                format!(
+                    "{} on day {} of the month or on {}",
+                    time,
+                    and_list(&numbers),
+                    and_list(&names)
+                )
+            }
+        };
+        if !every(4) {
+            let names: Vec<String> = runs(months, |m| MONTHS[m as usize - 1]);
+            when = format!("{} in {}", when, and_list(&names));
+        }
+        when
+    }
+}
+
+impl fmt::Display for CronExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.fields.join(" "))
+    }
+}
+
+impl Parse for CronExpr {
+    fn parse(input: &str) -> Result<Self> {
+        let fields: Vec<String> = input.split_whitespace().map(|f| f.to_string()).collect();
+        let skip = match fields.len() {
+            5 => 1,
+            6 => 0,
+            _ => {
+                return Err(VelvetIOError::validation_error(
+                    input,
+                    "Use 5 fields (minute hour day month weekday), or 6 with seconds first",
+                ));
+            }
+        };
+
+        let mut values: [Vec<u32>; 6] = Default::default();
+        values[0] = vec![0];
+        for (i, text) in fields.iter().enumerate() {
+            let field = i + skip;
+            values[field] = parse_field(text, field)
+                .map_err(|message| VelvetIOError::validation_error(input, message))?;
+        }
+        // 7 is Sunday too
+        if values[5].contains(&7) {
+            values[5].retain(|&d| d != 7);
+            if !values[5].contains(&0) {
+                values[5].insert(0, 0);
+            }
+        }
+
+        Ok(CronExpr { fields, values })
+    }
+
+    fn type_name() -> &'static str {
+        "cron expression"
+    }
+}
+
+/// Ask for a cron expression, then read it back in words and confirm it
+///
+/// Exits the program if input is closed before a valid answer arrives.
+pub fn ask_cron(prompt: &str) -> CronExpr {
+    cron_loop(prompt, &PromptOptions::default()).unwrap_or_else(|e| give_up(e))
+}
+
+/// `ask_cron` that gives up after `MAX_ATTEMPTS` invalid answers
+pub fn try_ask_cron(prompt: &str) -> Result<CronExpr> {
+    let options = PromptOptions::with_policy(AttemptPolicy::limited(MAX_ATTEMPTS));
+    cron_loop(prompt, &options)
+}
+
+fn cron_loop(prompt: &str, options: &PromptOptions) -> Result<CronExpr> {
+    let _turn = crate::lock::hold();
+    loop {
+        let (cron, _) = ask_loop(prompt, None, &|_: &CronExpr| true, "", options)?;
+        let check = format!("Runs {} - is that right?", cron.describe());
+        let right = match options.policy.max_attempts() {
+            Some(_) => crate::try_confirm(&check)?,
+            None => crate::confirm(&check),
+        };
+        if right {
+            return Ok(cron);
+        }
+    }
+}
+
+// One field's matching values, sorted, or what's wrong with it
+fn parse_field(text: &str, field: usize) -> std::result::Result<Vec<u32>, String> {
+    let (name, low, high) = FIELDS[field];
+    let mut values = Vec::new();
+
+    for item in text.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => match step.parse::<u32>() {
+                Ok(step) if step > 0 => (range, step),
+                _ => return Err(format!("'{}' isn't a valid step for {}", step, name)),
+            },
+            None => (item, 1),
+        };
+
+        let (start, end) = match range {
+            "*" | "?" => (low, high),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (value(start, field)?, value(end, field)?),
+                // "5/15" means from 5 to the end, every 15
+                None if step > 1 => (value(range, field)?, high),
+                None => (value(range, field)?, value(range, field)?),
+            },
+        };
+        if start > end {
+            return Err(format!("'{}' runs backwards for {}", range, name));
+        }
+        values.extend((start..=end).step_by(step as usize));
+    }
+
+    values.sort_unstable();
+    values.dedup();
+    Ok(values)
+}
+
+// A number or name in a field, checked against its range
+fn value(text: &str, field: usize) -> std::result::Result<u32, String> {
+    let (name, low, high) = FIELDS[field];
+    let named = |names: &[&str], first: u32| {
+        names
+            .iter()
+            .position(|n| text.len() == 3 && n[..3].eq_ignore_ascii_case(text))
+            .map(|i| i as u32 + first)
+    };
+    let number = match field {
+        4 => named(&MONTHS, 1),
+        5 => named(&WEEKDAYS, 0),
+        _ => None,
+    }
+    .or_else(|| text.parse().ok());
+
+    match number {
+        Some(n) if (low..=high).contains(&n) => Ok(n),
+        Some(_) => Err(format!(
+            "'{}' is out of range for {} ({}-{})",
+            text, name, low, high
+        )),
+        None => Err(format!("'{}' isn't a valid {}", text, name)),
+    }
+}
+
+fn full(field: usize) -> Vec<u32> {
+    let (_, low, high) = FIELDS[field];
+    match field {
+        5 => (0..=6).collect(),
+        _ => (low..=high).collect(),
+    }
+}
+
+// The step, when values are 0, n, 2n... all the way round
+fn evenly_spaced(values: &[u32], size: u32) -> Option<u32> {
+    let step = *values.get(1)? - values[0];
+    let expected: Vec<u32> = (0..size).step_by(step as usize).collect();
+    (values == expected.as_slice() && step > 1).then_some(step)
+}
+
+// Runs of three or more in a row collapse to "Monday to Friday"
+fn runs<T: ToString>(values: &[u32], label: impl Fn(u32) -> T) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < values.len() {
+        let mut j = i;
+        while j + 1 < values.len() && values[j + 1] == values[j] + 1 {
+            j += 1;
+        }
+        if j - i >= 2 {
+            out.push(format!(
+                "{} to {}",
+                label(values[i]).to_string(),
+                label(values[j]).to_string()
+            ));
+        } else {
+            out.extend((i..=j).map(|k| label(values[k]).to_string()));
+        }
+        i = j + 1;
+    }
+    out
+}
+
+// "a", "a and b", "a, b and c"
+fn and_list(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [only] => only.clone(),
+        [rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+    }
+}
@@ -0,0 +1,140 @@
+// src/deadline.rs
+
+// An overall time limit for a form or flow. While one runs with a deadline,
+// every prompt checks it before asking, and a stdin read waits no longer
+// than the time that's left. Waiting like that needs a thread that does the
+// reading, started the first time. It reads a line only when asked for one,
+// so once the deadline is over stdin is left alone again - except for a read
+// that timed out, which is still waiting on stdin. Its line answers the next
+// prompt, and until it's in the line editor sits out, since it can't share
+// stdin with a blocked read.
+
+use std::cell::Cell;
+use std::io::{self, BufRead};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// What a form or flow does once its deadline has passed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AfterDeadline {
+    /// Stop with a `Timeout` error
+    #[default]
+    Abort,
+    /// Take the defaults for the rest and skip optional fields. Fails with
+    /// `Timeout` on a field that has neither.
+    UseDefaults,
+}
+
+thread_local! {
+    // The deadline of the innermost running form or flow
+    static CURRENT: Cell<Option<(Instant, AfterDeadline)>> = const { Cell::new(None) };
+}
+
+// The reader thread, once there is one
+static READER: OnceLock<Mutex<Reader>> = OnceLock::new();
+
+// Whether the reader thread is reading a line nobody has taken yet
+static PENDING: AtomicBool = AtomicBool::new(false);
+
+struct Reader {
+    // Asks for a line
+    ask: Sender<()>,
+    lines: Receiver<io::Result<Option<String>>>,
+}
+
+// Runs until dropped, with an outer deadline still holding if it's sooner
+pub(crate) struct Scope {
+    outer: Option<(Instant, AfterDeadline)>,
+}
+
+pub(crate) fn scope(limit: Option<Duration>, then: AfterDeadline) -> Scope {
+    let outer = CURRENT.get();
+    if let Some(limit) = limit {
+        let at = Instant::now() + limit;
+        let sooner = match outer {
+            Some((outer_at, _)) if outer_at <= at => outer,
+            _ => Some((at, then)),
+        };
+        CURRENT.set(sooner);
+    }
+    Scope { outer }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        CURRENT.set(self.outer);
+    }
+}
+
+// Time left, if there's a deadline
+pub(crate) fn remaining() -> Option<Duration> {
+    CURRENT
+        .get()
+        .map(|(at, _)| at.saturating_duration_since(Instant::now()))
+}
+
+pub(crate) fn expired() -> bool {
+    remaining() == Some(Duration::ZERO)
+}
+
+// Whether a timed out field should fall back to its default
+pub(crate) fn use_defaults() -> bool {
+    matches!(CURRENT.get(), Some((_, AfterDeadline::UseDefaults)))
+}
+
+// Whether stdin has to be read through the reader thread
+pub(crate) fn threaded() -> bool {
+    PENDING.load(Ordering::SeqCst) || (remaining().is_some() && !cfg!(target_family = "wasm"))
+}
+
+// The next line from stdin, line ending trimmed, waiting no longer than the
+// deadline allows (`TimedOut` past it). `None` at end of input.
+pub(crate) fn read_line() -> io::Result<Option<String>> {
+    let reader = READER.get_or_init(|| {
+        let (ask, asked) = mpsc::channel();
+        let (send, receive) = mpsc::channel();
+        std::thread::spawn(move || {
+            let stdin = io::stdin();
+            while asked.recv().is_ok() {
+                let mut line = String::new();
+                let read = stdin.lock().read_line(&mut line).map(|read| {
+                    (read > 0).then(|| line.trim_end_matches(['\n', '\r']).to_string())
+                });
+                let done = !matches!(read, Ok(Some(_)));
+                if send.send(read).is_err() || done {
+                    return;
+                }
+            }
+        });
+        Mutex::new(Reader {
+            ask,
+            lines: receive,
+        })
+    });
+    let reader = reader
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    // A line asked for before and not in yet is the one to wait for
+    if !PENDING.swap(true, Ordering::SeqCst) && reader.ask.send(()).is_err() {
+        PENDING.store(false, Ordering::SeqCst);
+        return Ok(None);
+    }
+    let line = match remaining() {
+        Some(left) => reader.lines.recv_timeout(left),
+        None => reader
+            .lines
+            .recv()
+            .map_err(|_| RecvTimeoutError::Disconnected),
+    };
+    if !matches!(line, Err(RecvTimeoutError::Timeout)) {
+        PENDING.store(false, Ordering::SeqCst);
+    }
+    match line {
+        Ok(line) => line,
+        Err(RecvTimeoutError::Timeout) => Err(io::ErrorKind::TimedOut.into()),
+        // The reader stops after end of input or an error
+        Err(RecvTimeoutError::Disconnected) => Ok(None),
+    }
+}
@@ -0,0 +1,222 @@
+// src/debug.rs
+
+// `??` typed at a prompt. Instead of being taken as an answer, it lists what
+// the prompt is after and how it's being run: the field's key, the type it
+// parses to, the default, what the validator checks, limits, the attempt
+// it's on, and where input is coming from. Enough for a user stuck on a
+// rejected answer to see why, or to paste into a support ticket.
+//
+// It doesn't count as an attempt, and the prompt is asked again after it.
+
+use crate::core::{KeepTyped, PromptOptions, STRICT, summary};
+use crate::script::out;
+use crate::{deadline, editor, headless, protocol, redact, script, session};
+use std::cell::RefCell;
+use std::fmt::{Display, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+// What's typed to get a prompt's debug info
+const ASKED: &str = "??";
+
+thread_local! {
+    // The key of the form or flow field being asked, empty outside one
+    static FIELD: RefCell<String> = const { RefCell::new(String::new()) };
+    // What the prompt being asked said about itself. The buffers are reused,
+    // so asking doesn't allocate for either.
+    static ABOUT: RefCell<About> = const { RefCell::new(About::new()) };
+}
+
+struct About {
+    described: bool,
+    prompt: String,
+    // The type it parses to, or what a menu takes
+    expects: &'static str,
+    default: Option<String>,
+    // What the validator's error says, the only description it has
+    checks: String,
+    // 1-based
+    attempt: u32,
+}
+
+impl About {
+    const fn new() -> Self {
+        Self {
+            described: false,
+            prompt: String::new(),
+            expects: "",
+            default: None,
+            checks: String::new(),
+            attempt: 1,
+        }
+    }
+}
+
+/// Turn `??` debug info at prompts on or off (it's on)
+///
+/// With it off, `??` is an answer like any other - for prompts where it
+/// could really be one.
+///
+/// ```
+/// use velvetio::{run_scripted, set_debug_info, try_ask};
+///
+/// let run = run_scripted(&["??", "8080"], || try_ask::<u16>("Port"));
+/// assert_eq!(run.value.unwrap(), 8080);
+/// assert!(run.transcript.contains("Expects: positive integer"));
+///
+/// set_debug_info(false);
+/// let run = run_scripted(&["??"], || try_ask::<String>("Pattern"));
+/// assert_eq!(run.value.unwrap(), "??");
+/// set_debug_info(true);
+/// ```
+pub fn set_debug_info(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub(crate) fn requested(input: &str) -> bool {
+    input == ASKED && ENABLED.load(Ordering::Relaxed)
+}
+
+// Names the field being asked until dropped
+pub(crate) struct Field;
+
+pub(crate) fn field(key: &str) -> Field {
+    FIELD.with_borrow_mut(|field| {
+        field.clear();
+        field.push_str(key);
+    });
+    Field
+}
+
+impl Drop for Field {
+    fn drop(&mut self) {
+        FIELD.with_borrow_mut(String::clear);
+    }
+}
+
+// Describes the prompt being asked until dropped. Without one, `??` shows
+// what the line being read knows.
+pub(crate) struct Described;
+
+pub(crate) fn describe(
+    prompt: &str,
+    expects: &'static str,
+    default: Option<&dyn Display>,
+    checks: &str,
+) -> Described {
+    ABOUT.with_borrow_mut(|about| {
+        about.described = true;
+        about.prompt.clear();
+        about.prompt.push_str(prompt);
+        about.expects = expects;
+        match (default, &mut about.default) {
+            (Some(default), Some(kept)) => {
+                kept.clear();
+                let _ = write!(kept, "{}", default);
+            }
+            (Some(default), kept) => *kept = Some(default.to_string()),
+            (None, kept) => *kept = None,
+        }
+        about.checks.clear();
+        about.checks.push_str(checks);
+        about.attempt = 1;
+    });
+    Described
+}
+
+// The prompt being described is on its `n`th try
+pub(crate) fn attempt(n: u32) {
+    ABOUT.with_borrow_mut(|about| about.attempt = n);
+}
+
+impl Drop for Described {
+    fn drop(&mut self) {
+        ABOUT.with_borrow_mut(|about| about.described = false);
+    }
+}
+
+// Lists what's known about the prompt `??` was typed at. `shown` is the line
+// it was typed after.
+pub(crate) fn show(shown: &str, options: &PromptOptions) {
+    KeepTyped::unread();
+    let hidden = options.redact_default || options.secret || redact::hidden(shown);
+    let mut pairs: Vec<(&str, String)> = Vec::new();
+    ABOUT.with_borrow(|about| {
+        let prompt = match about.described {
+            true => about.prompt.as_str(),
+            false => shown.trim_end().trim_end_matches(':'),
+        };
+        pairs.push(("Prompt", prompt.to_string()));
+        let key = FIELD.with_borrow(String::clone);
+        if !key.is_empty() {
+            pairs.push(("Key", key));
+        }
+        if !about.described {
+            return;
+        }
+        pairs.push(("Expects", about.expects.to_string()));
+        let default = match &about.default {
+            Some(_) if hidden || redact::hidden(&about.prompt) => crate::REDACTED.to_string(),
+            Some(default) => default.clone(),
+            None => "none".to_string(),
+        };
+        pairs.push(("Default", default));
+        if !about.checks.is_empty() {
+            pairs.push(("Checks", about.checks.clone()));
+        }
+    });
+    let mut limits = Vec::new();
+    if let Some(max) = options.max_chars {
+        limits.push(format!("at most {} characters", max));
+    }
+    if let Some(allowed) = &options.allowed {
+        limits.push(allowed.describe());
+    }
+    if !limits.is_empty() {
+        pairs.push(("Limits", limits.join("; ")));
+    }
+    let attempt = ABOUT.with_borrow(|about| match about.described {
+        true => about.attempt,
+        false => 1,
+    });
+    let attempt = match options.policy.max_attempts() {
+        Some(max) => format!("{} of {}", attempt, max),
+        None => format!("{}, no limit", attempt),
+    };
+    pairs.push(("Attempt", attempt));
+    pairs.push(("Mode", mode(options)));
+    pairs.push(("Version", format!("velvetio {}", env!("CARGO_PKG_VERSION"))));
+    out!("{}", summary("Debug info", &pairs));
+}
+
+// Where answers come from, and anything that changes how they're read
+fn mode(options: &PromptOptions) -> String {
+    let source = if script::active() {
+        "scripted"
+    } else if protocol::is_json_protocol() {
+        "protocol"
+    } else if headless::is_headless() {
+        "headless"
+    } else if session::has_transport() {
+        "remote"
+    } else if editor::available() {
+        "terminal, line editor"
+    } else {
+        "piped"
+    };
+    let mut mode = vec![source.to_string()];
+    if options.secret {
+        mode.push("hidden typing".to_string());
+    }
+    if let Some(left) = deadline::remaining() {
+        mode.push(format!("{}s left", left.as_secs()));
+    }
+    if let Some(locale) = crate::locale() {
+        mode.push(format!("locale {}", locale));
+    }
+    if STRICT {
+        mode.push("v2-strict".to_string());
+    }
+    mode.join(", ")
+}
@@ -0,0 +1,353 @@
+// src/demo.rs
+
+// A menu of runnable prompts. `Demo::gallery()` has one entry for every kind
+// of prompt the crate offers, set up with the options worth seeing, and the
+// theme can be switched between runs to see how each one renders. It's the
+// gallery example, and the first thing to run after touching the terminal
+// code.
+
+use crate::core::report;
+use crate::script::outln;
+use crate::{
+    AttemptPolicy, Choice, OffStep, PickOptions, Progress, Result, SelectRules, Session, Theme,
+    Version,
+};
+use std::fmt::Debug;
+use std::time::Duration;
+
+/// Named prompt demos to pick from a menu and run
+///
+/// ```no_run
+/// use velvetio::Demo;
+///
+/// Demo::gallery()
+///     .add("Deploy", "our own deploy wizard", || {
+///         let _ = velvetio::try_confirm("Deploy to production?");
+///     })
+///     .run();
+/// ```
+pub struct Demo {
+    entries: Vec<Entry>,
+}
+
+struct Entry {
+    name: String,
+    about: String,
+    run: Box<dyn Fn()>,
+}
+
+// What the menu can do
+#[derive(Clone)]
+enum Pick {
+    Run(usize),
+    Theme,
+    Quit,
+}
+
+impl Demo {
+    /// No demos yet - add your own
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Every prompt kind, each with the options that change how it behaves
+    ///
+    /// Prompts from optional features are included when they're enabled.
+    pub fn gallery() -> Self {
+        let demo = Self::new()
+            .add("Text", "a plain answer", || {
+                show(crate::try_ask::<String>("Project name"))
+            })
+            .add("Number", "parsed and range checked", || {
+                show(
+                    crate::input::<u16>("Port")
+                        .validate(|port| *port >= 1024, "Pick a port from 1024 up")
+                        .try_ask(),
+                )
+            })
+            .add("Live check", "checked on every key", || {
+                show(
+                    crate::input::<u16>("Port")
+                        .validate(|port| *port >= 1024, "Pick a port from 1024 up")
+                        .live_validation()
+                        .try_ask(),
+                )
+            })
+            .add("Default", "enter keeps the default", || {
+                show(crate::try_ask_with_default("Workers", 4u32))
+            })
+            .add("Optional", "empty or \"none\" skips it", || {
+                show(crate::try_ask_opt::<String>("Nickname"))
+            })
+            .add("List", "comma, space or semicolon separated", || {
+                show(crate::try_ask::<Vec<u32>>("Favorite numbers"))
+            })
+            .add("Secret", "typing isn't echoed", || {
+                let password = crate::input::<String>("Password").secret().try_ask();
+                show(password.map(|p| format!("{} characters", p.chars().count())))
+            })
+            .add("Limits", "a length limit and allowed characters", || {
+                show(
+                    crate::input::<String>("Slug")
+                        .max_chars(20)
+                        .charset("a-z0-9-")
+                        .try_ask(),
+                )
+            })
+            .add("Steps", "suggested values, snapping to the nearest", || {
+                show(
+                    crate::input::<u32>("Memory (MB)")
+                        .suggest_steps(&[256, 512, 1024, 2048])
+                        .off_step(OffStep::Snap)
+                        .try_ask(),
+                )
+            })
+            .add("Retries", "two tries, a second apart", || {
+                show(
+                    crate::input::<u8>("Pick 1 to 9")
+                        .validate(|n| (1..=9).contains(n), "Between 1 and 9")
+                        .policy(AttemptPolicy::limited(2).delay(Duration::from_secs(1)))
+                        .try_ask(),
+                )
+            })
+            .add("Confirm", "yes or no", || {
+                show(crate::try_confirm("Enable telemetry?"))
+            })
+            .add("Confirm each", "one yes or no per item", || {
+                show(crate::try_confirm_each(
+                    "Delete",
+                    ["build/", "cache/", "notes.txt"],
+                ))
+            })
+            .add("Review", "check a summary before going on", || {
+                let pairs = [("Name", "demo"), ("Port", "8080"), ("Tier", "pro")];
+                show(Ok(crate::confirm_values("Settings", &pairs)))
+            })
+            .add("Choose", "one of a list", || {
+                show(crate::try_choose("Tier", &["free", "pro", "team"]))
+            })
+            .add("Choose with default", "enter takes the marked one", || {
+                show(crate::try_choose_with_default(
+                    "Region",
+                    &["us-east", "eu-west", "ap-south"],
+                    1,
+                ))
+            })
+            .add("Choose a value", "labels mapped to values", || {
+                let choices = [
+                    Choice::new("Small (1 CPU)", 1),
+                    Choice::new("Medium (2 CPUs)", 2),
+                    Choice::new("Large (8 CPUs)", 8).detect(|| true),
+                ];
+                show(crate::try_choose_value("Size", &choices))
+            })
+            .add("Multi-select", "any number of a list", || {
+                show(crate::try_multi_select(
+                    "Languages",
+                    &["Rust", "Go", "Python", "TypeScript"],
+                ))
+            })
+            .add("Multi-select with rules", "requires and conflicts", || {
+                let rules = SelectRules::new()
+                    .requires("TLS", "OpenSSL")
+                    .conflicts("OpenSSL", "Minimal");
+                show(crate::try_multi_select_with_rules(
+                    "Build options",
+                    &["TLS", "OpenSSL", "Minimal", "Docs"],
+                    &rules,
+                ))
+            })
+            .add("Lazy list", "a million options, a page at a time", || {
+                show(crate::try_choose_lazy("Package", |page, filter| {
+                    (0..1_000_000)
+                        .map(|n| format!("package-{}", n))
+                        .filter(|name| name.contains(filter))
+                        .skip(page * 10)
+                        .take(10)
+                        .collect()
+                }))
+            })
+            .add("Matrix", "one choice per item", || {
+                show(crate::try_ask_choice_matrix(
+                    "Access",
+                    ["alice", "bob"],
+                    &["read", "write", "admin"],
+                ))
+            })
+            .add("Form", "several fields in one go", || {
+                show(
+                    crate::form()
+                        .text("name", "Name")
+                        .number("age", "Age")
+                        .boolean("admin", "Admin")
+                        .choice("tier", "Tier", &["free", "pro"])
+                        .optional("note", "Note")
+                        .try_collect(),
+                )
+            })
+            .add("Context", "prompts labelled by section", || {
+                show(crate::with_context("Database", || {
+                    crate::with_context("Replica", || crate::try_ask::<String>("Host"))
+                }))
+            })
+            .add("Money", "exact amounts", || {
+                show(crate::try_ask_money("Budget", 2).map(|m| m.to_string()))
+            })
+            .add("Units", "a number with a unit", || {
+                show(crate::try_ask_with_unit("Timeout", "s"))
+            })
+            .add("Card", "a checked card number", || {
+                show(crate::try_ask_card("Card number").map(|c| c.to_string()))
+            })
+            .add("Key", "pasted or from a file, never echoed", || {
+                show(crate::try_ask_key("SSH key").map(|k| k.to_string()))
+            })
+            .add("Version bump", "patch, minor or major", || {
+                let current = Version::new(1, 4, 2);
+                show(crate::try_ask_version_bump(&current).map(|v| v.to_string()))
+            })
+            .add("Path", "an existing file, tab completes", || {
+                show(crate::try_pick_path("Config file", PickOptions::default()))
+            })
+            .add("Progress", "step counter", || {
+                let mut progress = Progress::steps(3);
+                for step in ["Creating project", "Installing", "Writing config"] {
+                    progress.advance(step);
+                    std::thread::sleep(Duration::from_millis(400));
+                }
+                progress.finish("Project ready");
+            })
+            .add("Spinner", "something slow", || {
+                crate::with_spinner("Checking connectivity...", || {
+                    std::thread::sleep(Duration::from_secs(2))
+                });
+            });
+
+        #[cfg(feature = "countries")]
+        let demo = demo.add("Country", "searchable country list", || {
+            show(crate::try_choose_country("Country"))
+        });
+        #[cfg(feature = "cron")]
+        let demo = demo.add("Cron", "a schedule, read back in words", || {
+            show(crate::try_ask_cron("Schedule").map(|c| c.describe()))
+        });
+        #[cfg(feature = "git")]
+        let demo = demo.add("Git branch", "branches of this repository", || {
+            show(crate::try_choose_branch("Branch"))
+        });
+        #[cfg(feature = "locales")]
+        let demo = demo.add("Locale", "searchable locale list", || {
+            show(crate::try_choose_locale("Locale"))
+        });
+        #[cfg(feature = "phone")]
+        let demo = demo.add("Phone", "normalized phone number", || {
+            show(crate::try_ask::<crate::PhoneNumber>("Phone"))
+        });
+        #[cfg(feature = "regex")]
+        let demo = demo.add("Regex", "a pattern that compiles", || {
+            show(crate::try_ask_regex("Pattern").map(|r| r.to_string()))
+        });
+        #[cfg(feature = "timezones")]
+        let demo = demo.add("Time zone", "searchable time zone list", || {
+            show(crate::try_choose_timezone("Time zone"))
+        });
+        demo
+    }
+
+    /// Add a demo, listed after the others
+    pub fn add(mut self, name: &str, about: &str, run: impl Fn() + 'static) -> Self {
+        self.entries.push(Entry {
+            name: name.to_string(),
+            about: about.to_string(),
+            run: Box::new(run),
+        });
+        self
+    }
+
+    /// Demo names in menu order
+    pub fn names(&self) -> Vec<&str> {
+        self.entries
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect()
+    }
+
+    /// Run the demo called `name` (any case), if there is one
+    pub fn run_one(&self, name: &str) -> bool {
+        let found = self
+            .entries
+            .iter()
+            .find(|entry| entry.name.eq_ignore_ascii_case(name));
+        if let Some(entry) = found {
+            (entry.run)();
+        }
+        found.is_some()
+    }
+
+    /// Pick demos from a menu until the user quits or input closes
+    ///
+    /// The menu has a theme switch too. Demos run with the chosen theme,
+    /// and so does the menu itself.
+    pub fn run(&self) {
+        let themes = [
+            ("default", Theme::default()),
+            ("plain", Theme::plain()),
+            ("accessible", Theme::accessible()),
+            ("minimal", Theme::minimal()),
+        ];
+        let mut current = 0;
+
+        loop {
+            let mut session = Session::new().theme(themes[current].1.clone());
+            let keep_going = session.run(|| {
+                let mut menu: Vec<Choice<Pick>> = self
+                    .entries
+                    .iter()
+                    .enumerate()
+                    .map(|(i, entry)| {
+                        let label = format!("{} - {}", entry.name, entry.about);
+                        Choice::new(label, Pick::Run(i))
+                    })
+                    .collect();
+                let label = format!("Switch theme (now {})", themes[current].0);
+                menu.push(Choice::new(label, Pick::Theme));
+                menu.push(Choice::new("Quit", Pick::Quit));
+
+                match crate::try_choose_value("Pick a demo", &menu) {
+                    Ok(Pick::Run(i)) => {
+                        (self.entries[i].run)();
+                        outln!();
+                        true
+                    }
+                    Ok(Pick::Theme) => {
+                        let names: Vec<&str> = themes.iter().map(|(name, _)| *name).collect();
+                        if let Ok(name) = crate::try_choose("Theme", &names) {
+                            current = names.iter().position(|n| *n == name).unwrap_or(0);
+                        }
+                        true
+                    }
+                    Ok(Pick::Quit) | Err(_) => false,
+                }
+            });
+            if !keep_going {
+                return;
+            }
+        }
+    }
+}
+
+impl Default for Demo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// What a demo got back, or why it didn't
+fn show<T: Debug>(answer: Result<T>) {
+    match answer {
+        Ok(value) => outln!("→ {:?}", value),
+        Err(e) => report(&e),
+    }
+}
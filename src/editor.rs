@@ -0,0 +1,422 @@
+// src/editor.rs
+
+// A minimal line editor for raw mode, used when a prompt needs to react to
+// each key (refusing characters past a limit or outside a charset, grouping
+// digits, completing, editing a previous answer). Only the basics: typing,
+// backspace and delete, the arrows plus Home/End (Ctrl+A/Ctrl+E) to move,
+// Ctrl+U to clear, tab to complete, enter to submit. Yes/no questions in
+// the toggle and checkbox styles are read here too, a key at a time.
+
+use crate::core::PromptOptions;
+use crate::theme::with_theme;
+use crate::{ConfirmStyle, Result, VelvetIOError, deadline, layout, term, tty};
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::rc::Rc;
+use std::sync::Arc;
+
+const CTRL_C: u8 = 0x03;
+const CTRL_A: u8 = 0x01;
+const CTRL_D: u8 = 0x04;
+const CTRL_E: u8 = 0x05;
+const CTRL_U: u8 = 0x15;
+const CTRL_Z: u8 = 0x1a;
+const BACKSPACE: u8 = 0x7f;
+const CTRL_H: u8 = 0x08;
+const TAB: u8 = 0x09;
+const ESC: u8 = 0x1b;
+
+// Tab completion: every full answer that could follow from the text so far
+#[derive(Clone)]
+pub(crate) struct Completer(pub(crate) Arc<CompleteFn>);
+
+type CompleteFn = dyn Fn(&str) -> Vec<String> + Send + Sync;
+
+impl fmt::Debug for Completer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Completer")
+    }
+}
+
+// Checks the text so far as it's typed: `None` if it would be accepted,
+// otherwise why not
+#[derive(Clone)]
+pub(crate) struct LiveCheck(pub(crate) Rc<LiveFn>);
+
+type LiveFn = dyn Fn(&str) -> Option<String>;
+
+impl fmt::Debug for LiveCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LiveCheck")
+    }
+}
+
+// Escape sequences we act on
+enum Key {
+    Left,
+    Right,
+    Home,
+    End,
+    Delete,
+}
+
+/// Whether we can take over the terminal for a prompt. Not once stdin is
+/// read on another thread for a deadline.
+pub(crate) fn available() -> bool {
+    term::raw_keys()
+        && term::cursor_control()
+        && !deadline::threaded()
+        && !with_theme(|theme| theme.accessible)
+}
+
+/// Reads one line after `prompt` (rendered, suffix included), refusing keys
+/// the options don't allow. The line starts out as `options.prefill`. With a
+/// length limit a `12/80` counter follows the cursor (not for secrets), and
+/// with a live check a ✓, or ✗ and the reason. `None` if the terminal
+/// couldn't be switched to raw mode - the caller should read a normal line
+/// instead.
+pub(crate) fn read(prompt: &str, options: &PromptOptions) -> Option<Result<String>> {
+    let guard = tty::ModeGuard::raw();
+    if !guard.is_active() {
+        return None;
+    }
+
+    let mut text: Vec<char> = options.prefill.as_deref().unwrap_or("").chars().collect();
+    let mut cursor = text.len();
+    // Secret and grouped text don't line up with the screen, so typing
+    // always goes at the end there
+    let movable = !options.secret && options.group_digits.is_none();
+    let mut stdin = io::stdin().lock();
+    let result = loop {
+        let tail = match movable {
+            true => &text[cursor..],
+            false => &[],
+        };
+        draw(prompt, &shown(&text, options), &text, tail, options);
+
+        let byte = match next_byte(&mut stdin) {
+            Ok(Some(byte)) => byte,
+            Ok(None) => break Err(VelvetIOError::eof()),
+            Err(e) => break Err(e.into()),
+        };
+
+        match byte {
+            b'\r' | b'\n' => break Ok(text.iter().collect()),
+            CTRL_C => break Err(VelvetIOError::cancelled()),
+            CTRL_D if text.is_empty() => break Err(VelvetIOError::eof()),
+            // Suspended with the terminal put back, and drawn again on `fg`
+            CTRL_Z => {
+                let _ = write!(io::stdout(), "\r\n");
+                let _ = io::stdout().flush();
+                guard.suspend();
+            }
+            CTRL_U => {
+                text.clear();
+                cursor = 0;
+            }
+            BACKSPACE | CTRL_H if cursor > 0 => {
+                cursor -= 1;
+                text.remove(cursor);
+            }
+            BACKSPACE | CTRL_H => {}
+            CTRL_A if movable => cursor = 0,
+            CTRL_E => cursor = text.len(),
+            TAB => match &options.complete {
+                Some(complete) => {
+                    text = completed(prompt, &text, complete);
+                    cursor = text.len();
+                }
+                None => bell(),
+            },
+            ESC => match (read_escape(&mut stdin), movable) {
+                (Some(Key::Left), true) => cursor = cursor.saturating_sub(1),
+                (Some(Key::Right), true) => cursor = (cursor + 1).min(text.len()),
+                (Some(Key::Home), true) => cursor = 0,
+                (Some(Key::End), _) => cursor = text.len(),
+                (Some(Key::Delete), true) if cursor < text.len() => {
+                    text.remove(cursor);
+                }
+                _ => {}
+            },
+            byte if byte < 0x20 => {}
+            byte => {
+                if let Some(c) = decode(byte, &mut stdin) {
+                    // Grouped digits bring their own spacing
+                    if options.group_digits.is_some() && matches!(c, ' ' | '-') {
+                        continue;
+                    }
+                    let fits = options.max_chars.is_none_or(|max| text.len() < max);
+                    let allowed = options.allowed.as_ref().is_none_or(|set| set.contains(c));
+                    if fits && allowed {
+                        text.insert(cursor, c);
+                        cursor += 1;
+                    } else {
+                        bell();
+                    }
+                }
+            }
+        }
+    };
+
+    // Leave the answer on screen without the counter
+    draw_final(prompt, &shown(&text, options));
+    drop(guard);
+    Some(result)
+}
+
+/// Reads a yes or no as a toggle (the arrows pick, `y` and `n` too) or a
+/// checkbox (space ticks it), starting at no. `None` if the terminal couldn't
+/// be switched to raw mode.
+pub(crate) fn read_yes_no(prompt: &str, style: ConfirmStyle) -> Option<Result<bool>> {
+    let guard = tty::ModeGuard::raw();
+    if !guard.is_active() {
+        return None;
+    }
+
+    let mut yes = false;
+    let mut stdin = io::stdin().lock();
+    let result = loop {
+        draw_yes_no(prompt, style, yes, false);
+
+        let byte = match next_byte(&mut stdin) {
+            Ok(Some(byte)) => byte,
+            Ok(None) => break Err(VelvetIOError::eof()),
+            Err(e) => break Err(e.into()),
+        };
+
+        match byte {
+            b'\r' | b'\n' => break Ok(yes),
+            CTRL_C => break Err(VelvetIOError::cancelled()),
+            CTRL_D => break Err(VelvetIOError::eof()),
+            CTRL_Z => {
+                let _ = write!(io::stdout(), "\r\n");
+                let _ = io::stdout().flush();
+                guard.suspend();
+            }
+            b' ' | TAB => yes = !yes,
+            b'y' | b'Y' => yes = true,
+            b'n' | b'N' => yes = false,
+            // Yes is on the left
+            ESC => match (read_escape(&mut stdin), style) {
+                (Some(Key::Left), ConfirmStyle::Toggle) => yes = true,
+                (Some(Key::Right), ConfirmStyle::Toggle) => yes = false,
+                _ => {}
+            },
+            _ => bell(),
+        }
+    };
+
+    draw_yes_no(prompt, style, yes, true);
+    drop(guard);
+    Some(result)
+}
+
+// `Deploy? [Yes]  No ` or `[x] Deploy?`, with how to answer after it until
+// it's answered
+fn draw_yes_no(prompt: &str, style: ConfirmStyle, yes: bool, done: bool) {
+    let accent = |text: &str| with_theme(|theme| theme.accent(text));
+    let line = match style {
+        ConfirmStyle::Checkbox => {
+            let tick = match yes {
+                true => accent("[x]"),
+                false => "[ ]".to_string(),
+            };
+            format!("{} {}", tick, prompt)
+        }
+        _ if done => format!("{} {}", prompt, accent(if yes { "Yes" } else { "No" })),
+        _ => {
+            let (left, right) = match yes {
+                true => (accent("[Yes]"), paint("2", " No ")),
+                false => (paint("2", " Yes "), accent("[No]")),
+            };
+            format!("{} {} {}", prompt, left, right)
+        }
+    };
+    let hint = match style {
+        _ if done || with_theme(|theme| theme.minimal) => "",
+        ConfirmStyle::Checkbox => "space to tick, enter to answer",
+        _ => "arrows to pick, enter to answer",
+    };
+    let mut out = io::stdout();
+    let _ = match hint {
+        "" => write!(out, "\r\x1b[2K{}", line),
+        hint => write!(out, "\r\x1b[2K{}  {}", line, paint("2", hint)),
+    };
+    if done {
+        let _ = write!(out, "\r\n");
+    }
+    let _ = out.flush();
+}
+
+// The text after a tab: the only candidate, or as much as all the candidates
+// share. When that adds nothing, the candidates are listed under the prompt.
+fn completed(prompt: &str, text: &[char], complete: &Completer) -> Vec<char> {
+    let typed: String = text.iter().collect();
+    let candidates = (complete.0)(&typed);
+    let Some(first) = candidates.first() else {
+        bell();
+        return text.to_vec();
+    };
+
+    let mut common: Vec<char> = first.chars().collect();
+    for candidate in &candidates[1..] {
+        let shared = common
+            .iter()
+            .zip(candidate.chars())
+            .take_while(|(a, b)| **a == *b)
+            .count();
+        common.truncate(shared);
+    }
+    if common.len() > text.len() || candidates.len() == 1 {
+        return common;
+    }
+
+    // Only the part after the shared prefix's last separator is news
+    let cut = typed.rfind('/').map_or(0, |at| at + 1);
+    let names: Vec<&str> = candidates
+        .iter()
+        .map(|c| c.get(cut..).unwrap_or(c))
+        .collect();
+    let mut out = io::stdout();
+    let _ = write!(
+        out,
+        "\r\x1b[2K{}{}\r\n{}\r\n",
+        prompt,
+        typed,
+        names.join("  ")
+    );
+    text.to_vec()
+}
+
+// What's on screen for the typed text: nothing when secret, grouped digits
+// like `4242 4242 42` when asked for
+fn shown(text: &[char], options: &PromptOptions) -> String {
+    if options.secret {
+        return String::new();
+    }
+    match options.group_digits {
+        Some(size) if size > 0 => {
+            let groups: Vec<String> = text.chunks(size).map(|g| g.iter().collect()).collect();
+            groups.join(" ")
+        }
+        _ => text.iter().collect(),
+    }
+}
+
+// `tail` is the text after the cursor, which is left where it's typing
+fn draw(prompt: &str, shown: &str, text: &[char], tail: &[char], options: &PromptOptions) {
+    let mut out = io::stdout();
+    let line = format!("{}{}", prompt, layout::isolated(shown));
+    let _ = write!(out, "\r\x1b[2K{}", line);
+
+    // Notes two spaces after the text, then back to the end of the text
+    let mut notes = String::new();
+    // A counter would give a secret's length away
+    if let Some(max_chars) = options.max_chars.filter(|_| !options.secret) {
+        notes.push_str(&format!(
+            "  {}",
+            paint("2", &format!("{}/{}", text.len(), max_chars))
+        ));
+    }
+    if let Some(live) = options.live.as_ref().filter(|_| !text.is_empty()) {
+        let (ok, failed) = match term::unicode_terminal() {
+            true => ("✓", "✗"),
+            false => ("ok", "x"),
+        };
+        let status = match (live.0)(&text.iter().collect::<String>()) {
+            None => paint("32", ok),
+            Some(why) => {
+                // Cut short rather than wrap, so the cursor can find its way back
+                let used = layout::text_width(&line) + layout::text_width(&notes);
+                let room = layout::width().unwrap_or(80).saturating_sub(used + 5);
+                match room {
+                    0..8 => paint("31", failed),
+                    _ => paint(
+                        "31",
+                        &format!("{} {}", failed, layout::truncate(&why, room)),
+                    ),
+                }
+            }
+        };
+        notes.push_str(&format!("  {}", status));
+    }
+    let back = layout::text_width(&notes) + layout::text_width(&tail.iter().collect::<String>());
+    if back > 0 {
+        let _ = write!(out, "{}\x1b[{}D", notes, back);
+    }
+    let _ = out.flush();
+}
+
+// `text` in an SGR style when colors are on
+fn paint(codes: &str, text: &str) -> String {
+    match with_theme(|theme| theme.color) {
+        true => format!("\x1b[{}m{}\x1b[0m", codes, text),
+        false => text.to_string(),
+    }
+}
+
+fn draw_final(prompt: &str, shown: &str) {
+    let mut out = io::stdout();
+    let _ = write!(out, "\r\x1b[2K{}{}\r\n", prompt, shown);
+    let _ = out.flush();
+}
+
+fn next_byte(input: &mut impl Read) -> io::Result<Option<u8>> {
+    let mut byte = [0u8; 1];
+    match input.read(&mut byte)? {
+        0 => Ok(None),
+        _ => Ok(Some(byte[0])),
+    }
+}
+
+// Arrow keys and friends: ESC [ ... final, or ESC O x. Anything we don't
+// know (up, down, function keys) is read and dropped.
+fn read_escape(input: &mut impl Read) -> Option<Key> {
+    let (params, last) = match next_byte(input).ok()?? {
+        b'[' => {
+            let mut params = Vec::new();
+            loop {
+                let byte = next_byte(input).ok()??;
+                if (0x40..=0x7e).contains(&byte) {
+                    break (params, byte);
+                }
+                params.push(byte);
+            }
+        }
+        b'O' => (Vec::new(), next_byte(input).ok()??),
+        _ => return None,
+    };
+    match (params.as_slice(), last) {
+        (b"", b'D') => Some(Key::Left),
+        (b"", b'C') => Some(Key::Right),
+        (b"", b'H') | (b"1" | b"7", b'~') => Some(Key::Home),
+        (b"", b'F') | (b"4" | b"8", b'~') => Some(Key::End),
+        (b"3", b'~') => Some(Key::Delete),
+        _ => None,
+    }
+}
+
+// The rest of a UTF-8 character starting with `first`
+fn decode(first: u8, input: &mut impl Read) -> Option<char> {
+    let len = match first {
+        0x00..=0x7f => 1,
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => return None,
+    };
+
+    let mut bytes = vec![first];
+    for _ in 1..len {
+        bytes.push(next_byte(input).ok()??);
+    }
+    std::str::from_utf8(&bytes).ok()?.chars().next()
+}
+
+fn bell() {
+    if !crate::theme::quiet() {
+        let mut out = io::stdout();
+        let _ = write!(out, "\x07");
+        let _ = out.flush();
+    }
+}
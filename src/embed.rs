@@ -0,0 +1,289 @@
+// src/embed.rs
+
+// Prompts without the terminal, for apps that already own one (ratatui,
+// cursive and friends). Each prompt is a small state machine: feed it the
+// keys your event loop gets, draw it however you like from its accessors,
+// and take the value once it says it's done. Parsing, validation and menu
+// matching are the same ones the terminal prompts use, so answers mean the
+// same thing either way.
+//
+// Nothing in here reads input or prints.
+
+use crate::core::pick_choice;
+use crate::{Parse, VelvetIOError};
+use std::fmt::Display;
+
+/// A key from your UI's event loop, for an embedded prompt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptEvent {
+    /// A typed character
+    Char(char),
+    Backspace,
+    Enter,
+    Up,
+    Down,
+    /// Escape, Ctrl+C - whatever your app uses to back out
+    Cancel,
+}
+
+/// Where an embedded prompt is after an event
+#[derive(Debug, Clone, PartialEq)]
+pub enum PromptState<T> {
+    /// Still being answered. A rejected answer shows up in `error()`.
+    Pending,
+    /// Answered
+    Done(T),
+    Cancelled,
+}
+
+/// A typed, validated answer as an embeddable component
+///
+/// ```
+/// use velvetio::{PromptEvent, PromptState, TextPrompt};
+///
+/// let mut port = TextPrompt::<u16>::new("Port").validate(|p| *p >= 1024, "Pick 1024 or up");
+/// for c in "80".chars() {
+///     port.poll(PromptEvent::Char(c));
+/// }
+/// assert_eq!(port.poll(PromptEvent::Enter), PromptState::Pending);
+/// assert_eq!(port.error().unwrap().message, "Pick 1024 or up");
+///
+/// port.poll(PromptEvent::Char('8'));
+/// port.poll(PromptEvent::Char('0'));
+/// assert_eq!(port.poll(PromptEvent::Enter), PromptState::Done(8080));
+/// ```
+pub struct TextPrompt<T> {
+    prompt: String,
+    input: String,
+    default: Option<String>,
+    validator: Box<dyn Fn(&T) -> bool>,
+    error_msg: String,
+    error: Option<VelvetIOError>,
+    attempts: u32,
+}
+
+impl<T: Parse> TextPrompt<T> {
+    pub fn new(prompt: &str) -> Self {
+        Self {
+            prompt: prompt.to_string(),
+            input: String::new(),
+            default: None,
+            validator: Box::new(|_| true),
+            error_msg: "Invalid input".to_string(),
+            error: None,
+            attempts: 0,
+        }
+    }
+
+    /// Only accept answers that pass the check
+    pub fn validate<F>(mut self, validator: F, error_msg: &str) -> Self
+    where
+        F: Fn(&T) -> bool + 'static,
+    {
+        self.validator = Box::new(validator);
+        self.error_msg = error_msg.to_string();
+        self
+    }
+
+    /// What enter on an empty answer gives
+    pub fn default(mut self, value: impl Display) -> Self {
+        self.default = Some(value.to_string());
+        self
+    }
+
+    pub fn poll(&mut self, event: PromptEvent) -> PromptState<T> {
+        match event {
+            PromptEvent::Char(c) => {
+                self.input.push(c);
+                self.error = None;
+            }
+            PromptEvent::Backspace => {
+                self.input.pop();
+                self.error = None;
+            }
+            PromptEvent::Enter => match self.answer() {
+                Ok(value) => return PromptState::Done(value),
+                Err(error) => self.error = Some(error),
+            },
+            PromptEvent::Cancel => return PromptState::Cancelled,
+            PromptEvent::Up | PromptEvent::Down => {}
+        }
+        PromptState::Pending
+    }
+
+    fn answer(&mut self) -> crate::Result<T> {
+        self.attempts += 1;
+        let typed = self.input.trim();
+        let answer = match (typed, &self.default) {
+            ("", Some(default)) => default.as_str(),
+            _ => typed,
+        };
+        let value = T::parse(answer).map_err(|e| e.during(&self.prompt, self.attempts))?;
+        match (self.validator)(&value) {
+            true => Ok(value),
+            false => Err(
+                VelvetIOError::validation_error(answer, self.error_msg.as_str())
+                    .during(&self.prompt, self.attempts),
+            ),
+        }
+    }
+
+    pub fn prompt(&self) -> &str {
+        &self.prompt
+    }
+
+    /// What's been typed so far
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    pub fn default_value(&self) -> Option<&str> {
+        self.default.as_deref()
+    }
+
+    /// Why the last answer was rejected, until the user types again
+    pub fn error(&self) -> Option<&VelvetIOError> {
+        self.error.as_ref()
+    }
+}
+
+/// A yes or no question as an embeddable component
+///
+/// Answers read like [`confirm`](crate::confirm)'s: y, yes, true, 1 and so on.
+pub struct ConfirmPrompt {
+    text: TextPrompt<bool>,
+}
+
+impl ConfirmPrompt {
+    pub fn new(prompt: &str) -> Self {
+        Self {
+            text: TextPrompt::new(prompt),
+        }
+    }
+
+    /// What enter on an empty answer gives
+    pub fn default(self, yes: bool) -> Self {
+        Self {
+            text: self.text.default(if yes { "y" } else { "n" }),
+        }
+    }
+
+    pub fn poll(&mut self, event: PromptEvent) -> PromptState<bool> {
+        self.text.poll(event)
+    }
+
+    pub fn prompt(&self) -> &str {
+        self.text.prompt()
+    }
+
+    pub fn input(&self) -> &str {
+        self.text.input()
+    }
+
+    pub fn default_value(&self) -> Option<bool> {
+        self.text.default_value().map(|default| default == "y")
+    }
+
+    pub fn error(&self) -> Option<&VelvetIOError> {
+        self.text.error()
+    }
+}
+
+/// One of a list as an embeddable component
+///
+/// Up and down move the highlight and enter takes it. Typing works too, like
+/// a terminal menu: a number, or a label or unambiguous start of one.
+///
+/// ```
+/// use velvetio::{PromptEvent, PromptState, SelectPrompt};
+///
+/// let mut tier = SelectPrompt::new("Tier", &["free", "pro", "team"]);
+/// tier.poll(PromptEvent::Down);
+/// assert_eq!(tier.highlighted(), 1);
+/// assert_eq!(tier.poll(PromptEvent::Enter), PromptState::Done("pro"));
+/// ```
+pub struct SelectPrompt<T> {
+    prompt: String,
+    choices: Vec<T>,
+    highlighted: usize,
+    input: String,
+    error: Option<VelvetIOError>,
+    attempts: u32,
+}
+
+impl<T: Display + Clone> SelectPrompt<T> {
+    pub fn new(prompt: &str, choices: &[T]) -> Self {
+        Self {
+            prompt: prompt.to_string(),
+            choices: choices.to_vec(),
+            highlighted: 0,
+            input: String::new(),
+            error: None,
+            attempts: 0,
+        }
+    }
+
+    /// Start with this choice highlighted
+    pub fn highlight(mut self, index: usize) -> Self {
+        self.highlighted = index.min(self.choices.len().saturating_sub(1));
+        self
+    }
+
+    pub fn poll(&mut self, event: PromptEvent) -> PromptState<T> {
+        let count = self.choices.len().max(1);
+        match event {
+            // Wrapping around at the ends
+            PromptEvent::Up => self.highlighted = (self.highlighted + count - 1) % count,
+            PromptEvent::Down => self.highlighted = (self.highlighted + 1) % count,
+            PromptEvent::Char(c) => {
+                self.input.push(c);
+                self.error = None;
+            }
+            PromptEvent::Backspace => {
+                self.input.pop();
+                self.error = None;
+            }
+            PromptEvent::Enter => match self.answer() {
+                Ok(index) => return PromptState::Done(self.choices[index].clone()),
+                Err(error) => self.error = Some(error),
+            },
+            PromptEvent::Cancel => return PromptState::Cancelled,
+        }
+        PromptState::Pending
+    }
+
+    fn answer(&mut self) -> crate::Result<usize> {
+        self.attempts += 1;
+        if self.choices.is_empty() {
+            return Err(VelvetIOError::no_choices());
+        }
+        match self.input.trim() {
+            "" => Ok(self.highlighted),
+            typed => {
+                pick_choice(typed, &self.choices).map_err(|e| e.during(&self.prompt, self.attempts))
+            }
+        }
+    }
+
+    pub fn prompt(&self) -> &str {
+        &self.prompt
+    }
+
+    pub fn choices(&self) -> &[T] {
+        &self.choices
+    }
+
+    pub fn highlighted(&self) -> usize {
+        self.highlighted
+    }
+
+    /// What's been typed so far
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Why the last answer was rejected, until the user types again
+    pub fn error(&self) -> Option<&VelvetIOError> {
+        self.error.as_ref()
+    }
+}
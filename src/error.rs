@@ -1,15 +1,67 @@
 // src/error.rs
 
 use std::fmt;
+use std::io;
+use std::sync::Arc;
 
 pub type Result<T> = std::result::Result<T, VelvetIOError>;
 
+/// What went wrong, for callers that need to react differently
+///
+/// More kinds may be added, so a `match` needs a `_` arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ErrorKind {
+    /// Input couldn't be parsed as the requested type
+    Parse,
+    /// Input parsed but failed validation
+    Validation,
+    /// Reading from or writing to the terminal failed
+    Io,
+    /// Input ended (Ctrl+D or a closed pipe) before an answer was given
+    Eof,
+    /// The user cancelled the prompt
+    Cancelled,
+    /// There was nothing to choose from
+    NoChoices,
+    /// Too many invalid answers in a row
+    TooManyAttempts,
+    /// A form or flow ran past its deadline
+    Timeout,
+    /// Anything else
+    Other,
+}
+
 /// Error type for VelvetIO operations
+///
+/// Build one with [`new`](Self::new) or the other constructors - it has
+/// private fields, so not with a struct literal.
+///
+/// With the `serde` feature it serializes to a flat object, for CLIs that
+/// report failures as JSON.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct VelvetIOError {
+    pub kind: ErrorKind,
     pub message: String,
     pub input: String,
     pub expected: String,
+    // Where it happened - boxed so `Result`s carrying this error stay small
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    context: Box<Context>,
+    // The terminal error behind an `Io` error, shared so the error stays `Clone`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    io: Option<Arc<io::Error>>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct Context {
+    key: Option<String>,
+    prompt: Option<String>,
+    attempt: Option<u32>,
 }
 
 impl VelvetIOError {
@@ -19,9 +71,12 @@ impl VelvetIOError {
         expected: impl Into<String>,
     ) -> Self {
         Self {
+            kind: ErrorKind::Other,
             message: message.into(),
             input: input.into(),
             expected: expected.into(),
+            context: Box::default(),
+            io: None,
         }
     }
 
@@ -31,20 +86,181 @@ impl VelvetIOError {
         let expected_type = expected_type.into();
 
         Self {
+            kind: ErrorKind::Parse,
             message: format!("Cannot parse '{}' as {}", input, expected_type),
             input,
             expected: expected_type,
+            context: Box::default(),
+            io: None,
         }
     }
 
     /// Create validation error with custom message
     pub fn validation_error(input: impl Into<String>, custom_message: impl Into<String>) -> Self {
         Self {
+            kind: ErrorKind::Validation,
             message: custom_message.into(),
             input: input.into(),
             expected: "valid input".to_string(),
+            context: Box::default(),
+            io: None,
+        }
+    }
+
+    /// Input ended before we got an answer
+    pub fn eof() -> Self {
+        Self {
+            kind: ErrorKind::Eof,
+            message: "Input ended before an answer was given".to_string(),
+            input: String::new(),
+            expected: "an answer".to_string(),
+            context: Box::default(),
+            io: None,
+        }
+    }
+
+    /// User backed out of the prompt
+    pub fn cancelled() -> Self {
+        Self {
+            kind: ErrorKind::Cancelled,
+            message: "Cancelled".to_string(),
+            input: String::new(),
+            expected: "an answer".to_string(),
+            context: Box::default(),
+            io: None,
+        }
+    }
+
+    /// Asked to choose from an empty list
+    pub fn no_choices() -> Self {
+        Self {
+            kind: ErrorKind::NoChoices,
+            message: "Cannot choose from empty list".to_string(),
+            input: String::new(),
+            expected: "at least one choice".to_string(),
+            context: Box::default(),
+            io: None,
+        }
+    }
+
+    /// Ran out of time, past a form's or flow's deadline
+    pub fn timeout() -> Self {
+        Self {
+            kind: ErrorKind::Timeout,
+            message: "Ran out of time before all the answers were in".to_string(),
+            input: String::new(),
+            expected: "an answer in time".to_string(),
+            context: Box::default(),
+            io: None,
+        }
+    }
+
+    /// Gave up after too many invalid answers
+    pub fn too_many_attempts(attempts: u32) -> Self {
+        Self {
+            kind: ErrorKind::TooManyAttempts,
+            message: format!("Gave up after {} invalid answers", attempts),
+            input: String::new(),
+            expected: "valid input".to_string(),
+            context: Box::new(Context {
+                attempt: Some(attempts),
+                ..Context::default()
+            }),
+            io: None,
+        }
+    }
+
+    /// Tag the error with the key of the prompt that produced it
+    ///
+    /// Errors from a key that looks sensitive lose the answer they quote.
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        let key = key.into();
+        if crate::redact::is_sensitive(&key) {
+            self.redact();
+        }
+        self.context.key = Some(key);
+        self
+    }
+
+    // Fills in where the error came from, keeping anything already set.
+    // Answers to sensitive prompts are blanked out on the way.
+    pub(crate) fn during(mut self, prompt: &str, attempt: u32) -> Self {
+        if crate::redact::hidden(prompt) {
+            self.redact();
+        }
+        let context = &mut self.context;
+        context
+            .prompt
+            .get_or_insert_with(|| crate::markup::strip(prompt));
+        context.attempt.get_or_insert(attempt);
+        self
+    }
+
+    // The answer, and where the message quotes it, as `***`
+    pub(crate) fn redact(&mut self) {
+        if self.input.is_empty() || self.input == crate::REDACTED {
+            return;
+        }
+        let quoted = format!("'{}'", self.input);
+        self.message = self
+            .message
+            .replace(&quoted, &format!("'{}'", crate::REDACTED));
+        self.input = crate::REDACTED.to_string();
+    }
+
+    /// Key of the keyed prompt or form field that failed
+    pub fn key(&self) -> Option<&str> {
+        self.context.key.as_deref()
+    }
+
+    /// Text of the prompt that failed
+    pub fn prompt(&self) -> Option<&str> {
+        self.context.prompt.as_deref()
+    }
+
+    /// Which answer the error came from, counting from 1
+    pub fn attempt(&self) -> Option<u32> {
+        self.context.attempt
+    }
+
+    pub fn is_eof(&self) -> bool {
+        self.kind == ErrorKind::Eof
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.kind == ErrorKind::Cancelled
+    }
+
+    pub fn is_timeout(&self) -> bool {
+        self.kind == ErrorKind::Timeout
+    }
+
+    /// Process exit status for this error: 130 when cancelled (like Ctrl+C),
+    /// 141 for a broken pipe, 124 past a deadline (like `timeout`), 74 for
+    /// other terminal failures, otherwise 1
+    pub fn exit_code(&self) -> i32 {
+        match self.kind {
+            ErrorKind::Cancelled => 130,
+            _ if self.is_broken_pipe() => 141,
+            ErrorKind::Timeout => 124,
+            ErrorKind::Io => 74,
+            _ => 1,
         }
     }
+
+    /// A read was interrupted by a signal (EINTR), usually Ctrl+C
+    pub fn is_interrupted(&self) -> bool {
+        self.io_kind() == Some(io::ErrorKind::Interrupted)
+    }
+
+    /// The other end of a pipe went away
+    pub fn is_broken_pipe(&self) -> bool {
+        self.io_kind() == Some(io::ErrorKind::BrokenPipe)
+    }
+
+    fn io_kind(&self) -> Option<io::ErrorKind> {
+        self.io.as_ref().map(|error| error.kind())
+    }
 }
 
 impl fmt::Display for VelvetIOError {
@@ -53,14 +269,33 @@ impl fmt::Display for VelvetIOError {
     }
 }
 
-impl std::error::Error for VelvetIOError {}
+impl std::error::Error for VelvetIOError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.io
+            .as_deref()
+            .map(|error| error as &(dyn std::error::Error + 'static))
+    }
+}
+
+impl From<io::Error> for VelvetIOError {
+    fn from(error: io::Error) -> Self {
+        // Past a deadline, or a transport that gave up waiting
+        if error.kind() == io::ErrorKind::TimedOut {
+            return Self::timeout();
+        }
+        let kind = match error.kind() {
+            io::ErrorKind::UnexpectedEof => ErrorKind::Eof,
+            io::ErrorKind::Interrupted => ErrorKind::Cancelled,
+            _ => ErrorKind::Io,
+        };
 
-impl From<std::io::Error> for VelvetIOError {
-    fn from(error: std::io::Error) -> Self {
         Self {
+            kind,
             message: format!("Input error: {}", error),
             input: String::new(),
             expected: "valid input".to_string(),
+            context: Box::default(),
+            io: Some(Arc::new(error)),
         }
     }
 }
@@ -0,0 +1,415 @@
+// src/flow.rs
+
+// Wizards declared up front instead of written out as code. A flow is a list
+// of steps - questions, and yes/no branches with a flow for each side - so it
+// can be looked at before anything is asked: printed as a dry run, or
+// exported as JSON for docs and other frontends. Running it asks the steps
+// the way a form would, with the same field types, defaults and caching.
+
+use crate::cache::push_json_string;
+use crate::core::{FieldType, Form, FormField, MapFn, PromptOptions, give_up};
+use crate::{AfterDeadline, AttemptPolicy, MAX_ATTEMPTS, Result, deadline, lock};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Start a flow
+///
+/// ```no_run
+/// use velvetio::{Question, flow};
+///
+/// let answers = flow()
+///     .ask("name", Question::text("Project name"))
+///     .branch("docker", "Use Docker?", |yes| match yes {
+///         true => flow().ask("image", Question::text("Base image").default("alpine")),
+///         false => flow(),
+///     })
+///     .ask("tier", Question::choice("Tier", &["free", "pro"]))
+///     .run();
+/// ```
+pub fn flow() -> Flow {
+    Flow::new()
+}
+
+/// Steps to ask, built by [`flow`]
+pub struct Flow {
+    steps: Vec<Step>,
+    deadline: Option<Duration>,
+    after_deadline: AfterDeadline,
+}
+
+enum Step {
+    Ask(FormField),
+    // Asked as yes or no, then one flow or the other
+    Branch {
+        field: FormField,
+        yes: Flow,
+        no: Flow,
+    },
+}
+
+/// One question in a [`Flow`]
+pub struct Question {
+    prompt: String,
+    field_type: FieldType,
+    default: Option<String>,
+    sensitive: bool,
+    map: Option<Box<MapFn>>,
+    max_attempts: Option<u32>,
+    fallback: Option<String>,
+}
+
+impl Question {
+    fn new(prompt: impl Into<String>, field_type: FieldType) -> Self {
+        Self {
+            prompt: prompt.into(),
+            field_type,
+            default: None,
+            sensitive: false,
+            map: None,
+            max_attempts: None,
+            fallback: None,
+        }
+    }
+
+    pub fn text(prompt: &str) -> Self {
+        Self::new(prompt, FieldType::Text)
+    }
+
+    pub fn number(prompt: &str) -> Self {
+        Self::new(prompt, FieldType::Number)
+    }
+
+    pub fn confirm(prompt: &str) -> Self {
+        Self::new(prompt, FieldType::Boolean)
+    }
+
+    pub fn choice(prompt: &str, choices: &[&str]) -> Self {
+        Self::new(prompt, FieldType::Choice(strings(choices)))
+    }
+
+    pub fn multi_choice(prompt: &str, choices: &[&str]) -> Self {
+        Self::new(prompt, FieldType::MultiChoice(strings(choices)))
+    }
+
+    /// Text that can be skipped, left out of the answers when it is
+    pub fn optional(prompt: &str) -> Self {
+        Self::new(format!("{} (optional)", prompt), FieldType::Optional)
+    }
+
+    /// What enter gives, written like an answer
+    pub fn default(mut self, value: &str) -> Self {
+        self.default = Some(value.to_string());
+        self
+    }
+
+    /// Keep the answer out of transcripts, caches and error messages, like
+    /// `Form::sensitive`
+    pub fn sensitive(mut self) -> Self {
+        self.sensitive = true;
+        self
+    }
+
+    /// Store the answer run through `map`, like `Form::map`
+    pub fn map(mut self, map: impl Fn(&str) -> String + 'static) -> Self {
+        self.map = Some(Box::new(map));
+        self
+    }
+
+    /// Give up after `max` invalid answers, like `Form::max_attempts`
+    pub fn max_attempts(mut self, max: u32) -> Self {
+        self.max_attempts = Some(max.max(1));
+        self
+    }
+
+    /// What the question gets when nobody answers it, like `Form::fallback`
+    pub fn fallback(mut self, value: &str) -> Self {
+        self.fallback = Some(value.to_string());
+        self
+    }
+
+    fn field(self, key: &str) -> FormField {
+        FormField {
+            default: self.default,
+            sensitive: self.sensitive,
+            map: self.map,
+            max_attempts: self.max_attempts,
+            fallback: self.fallback,
+            ..FormField::new(key, self.prompt, self.field_type)
+        }
+    }
+}
+
+impl Flow {
+    pub fn new() -> Self {
+        Self {
+            steps: Vec::new(),
+            deadline: None,
+            after_deadline: AfterDeadline::Abort,
+        }
+    }
+
+    /// Ask `question`, filing the answer under `key`
+    pub fn ask(mut self, key: &str, question: Question) -> Self {
+        self.steps.push(Step::Ask(question.field(key)));
+        self
+    }
+
+    /// Ask a yes or no question, then go on with the flow `next` gives for
+    /// the answer
+    ///
+    /// `next` is called for both answers up front, so the whole flow can be
+    /// described before it runs. The branch's own answer is filed under `key`
+    /// as `true` or `false`.
+    pub fn branch(mut self, key: &str, prompt: &str, next: impl Fn(bool) -> Flow) -> Self {
+        self.steps.push(Step::Branch {
+            field: Question::confirm(prompt).field(key),
+            yes: next(true),
+            no: next(false),
+        });
+        self
+    }
+
+    /// Go on with another flow's steps
+    pub fn then(mut self, next: Flow) -> Self {
+        self.steps.extend(next.steps);
+        self
+    }
+
+    /// Give the whole flow this long, like [`Form::deadline`]
+    ///
+    /// Only the deadline of the flow that's run counts, not ones on the
+    /// flows it branches to.
+    pub fn deadline(mut self, limit: Duration) -> Self {
+        self.deadline = Some(limit);
+        self
+    }
+
+    /// What to do with the rest of the flow once its deadline has passed
+    pub fn after_deadline(mut self, then: AfterDeadline) -> Self {
+        self.after_deadline = then;
+        self
+    }
+
+    /// Ask the steps in order and collect the answers
+    ///
+    /// Exits the program if input is closed partway through, or if a choice
+    /// question has no choices.
+    pub fn run(self) -> HashMap<String, String> {
+        self.run_inner(AttemptPolicy::unlimited())
+            .unwrap_or_else(|e| give_up(e))
+    }
+
+    /// Like `run`, but returns the first error instead of exiting
+    ///
+    /// Each question accepts up to `MAX_ATTEMPTS` invalid answers.
+    pub fn try_run(self) -> Result<HashMap<String, String>> {
+        self.run_inner(AttemptPolicy::limited(MAX_ATTEMPTS))
+    }
+
+    fn run_inner(&self, policy: AttemptPolicy) -> Result<HashMap<String, String>> {
+        let _turn = lock::hold();
+        let _deadline = deadline::scope(self.deadline, self.after_deadline);
+        let mut answers = HashMap::new();
+        let options = PromptOptions::with_policy(policy.clone());
+        self.walk(&mut answers, &policy, &options)?;
+        Ok(answers)
+    }
+
+    fn walk(
+        &self,
+        answers: &mut HashMap<String, String>,
+        policy: &AttemptPolicy,
+        options: &PromptOptions,
+    ) -> Result<()> {
+        for step in &self.steps {
+            match step {
+                Step::Ask(field) => {
+                    Form::answer(field, answers, None, policy, options)?;
+                }
+                Step::Branch { field, yes, no } => {
+                    Form::answer(field, answers, None, policy, options)?;
+                    match answers.get(&field.key).map(String::as_str) {
+                        Some("true") => yes.walk(answers, policy, options)?,
+                        _ => no.walk(answers, policy, options)?,
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// What the flow would ask, one line per question, branches indented
+    /// under their answers - a dry run
+    ///
+    /// ```
+    /// use velvetio::{Question, flow};
+    ///
+    /// let setup = flow()
+    ///     .ask("name", Question::text("Project name"))
+    ///     .branch("docker", "Use Docker?", |yes| match yes {
+    ///         true => flow().ask("image", Question::text("Base image").default("alpine")),
+    ///         false => flow(),
+    ///     });
+    /// assert_eq!(setup.describe(), "\
+    /// name: Project name (text)
+    /// docker: Use Docker? (yes/no)
+    ///   yes:
+    ///     image: Base image (text) [alpine]
+    ///   no: nothing more
+    /// ");
+    /// ```
+    pub fn describe(&self) -> String {
+        let mut out = String::new();
+        self.describe_into(&mut out, 0);
+        out
+    }
+
+    fn describe_into(&self, out: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth);
+        for step in &self.steps {
+            let field = match step {
+                Step::Ask(field) | Step::Branch { field, .. } => field,
+            };
+            describe_field(out, &indent, field);
+
+            if let Step::Branch { yes, no, .. } = step {
+                for (answer, next) in [("yes", yes), ("no", no)] {
+                    match next.steps.is_empty() {
+                        true => out.push_str(&format!("{}  {}: nothing more\n", indent, answer)),
+                        false => {
+                            out.push_str(&format!("{}  {}:\n", indent, answer));
+                            next.describe_into(out, depth + 2);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// The flow as JSON, for docs or another frontend to render
+    ///
+    /// `{"steps": [...]}`, where each step has a `key`, `prompt` and `type`
+    /// (`text`, `number`, `confirm`, `choice`...), with `choices` and
+    /// `default` when it has them (`sensitive` ones say so, and their default
+    /// is `***`). A branch is a `confirm` step with `yes` and `no` flows of
+    /// their own.
+    pub fn schema(&self) -> String {
+        let mut json = String::from("{\"steps\":[");
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            let field = match step {
+                Step::Ask(field) | Step::Branch { field, .. } => field,
+            };
+            let (_, choices) = describe_type(&field.field_type);
+            json.push_str("{\"key\":");
+            push_json_string(&mut json, &field.key);
+            json.push_str(",\"prompt\":");
+            push_json_string(&mut json, &field.prompt);
+            json.push_str(",\"type\":");
+            push_json_string(&mut json, schema_type(&field.field_type));
+            if let Some(choices) = choices {
+                json.push_str(",\"choices\":[");
+                for (i, choice) in choices.iter().enumerate() {
+                    if i > 0 {
+                        json.push(',');
+                    }
+                    push_json_string(&mut json, choice);
+                }
+                json.push(']');
+            }
+            if let Some(default) = &field.default {
+                json.push_str(",\"default\":");
+                match field.is_sensitive() {
+                    true => push_json_string(&mut json, crate::REDACTED),
+                    false => push_json_string(&mut json, default),
+                }
+            }
+            if field.is_sensitive() {
+                json.push_str(",\"sensitive\":true");
+            }
+            if let Step::Branch { yes, no, .. } = step {
+                json.push_str(&format!(",\"yes\":{},\"no\":{}", yes.schema(), no.schema()));
+            }
+            json.push('}');
+        }
+        json.push_str("]}");
+        json
+    }
+}
+
+impl Default for Flow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// One dry run line: `key: Prompt (type: choices) [default]`. Forms describe
+// their fields the same way.
+pub(crate) fn describe_field(out: &mut String, indent: &str, field: &FormField) {
+    let (kind, choices) = describe_type(&field.field_type);
+    out.push_str(&format!(
+        "{}{}: {} ({}",
+        indent, field.key, field.prompt, kind
+    ));
+    if let Some(choices) = choices {
+        out.push_str(&format!(": {}", choices.join(", ")));
+    }
+    out.push(')');
+    if let Some(default) = &field.default {
+        let default = match field.is_sensitive() {
+            true => crate::REDACTED,
+            false => default.as_str(),
+        };
+        out.push_str(&format!(" [{}]", default));
+    }
+    out.push('\n');
+}
+
+fn strings(choices: &[&str]) -> Vec<String> {
+    choices.iter().map(|s| s.to_string()).collect()
+}
+
+// How a dry run names a field type, with its choices when it has them
+fn describe_type(field_type: &FieldType) -> (&'static str, Option<Vec<&str>>) {
+    match field_type {
+        FieldType::Text => ("text", None),
+        FieldType::Number => ("number", None),
+        FieldType::Boolean => ("yes/no", None),
+        FieldType::Choice(choices) => ("one of", labels(choices)),
+        FieldType::ValueChoice(choices) => {
+            ("one of", Some(choices.iter().map(|c| c.label()).collect()))
+        }
+        FieldType::MultiChoice(choices) => ("any of", labels(choices)),
+        // Their prompts already say they're optional
+        FieldType::Optional => ("text", None),
+        FieldType::OptionalNumber => ("number", None),
+        FieldType::OptionalChoice(choices) => ("one of", labels(choices)),
+        FieldType::ValidatedText { .. } => ("text", None),
+        #[cfg(feature = "phone")]
+        FieldType::Phone => ("phone number", None),
+        FieldType::DependentChoice(_) => ("one of, from earlier answers", None),
+    }
+}
+
+fn labels(choices: &[String]) -> Option<Vec<&str>> {
+    Some(choices.iter().map(String::as_str).collect())
+}
+
+fn schema_type(field_type: &FieldType) -> &'static str {
+    match field_type {
+        FieldType::Text | FieldType::ValidatedText { .. } => "text",
+        FieldType::Number => "number",
+        FieldType::Boolean => "confirm",
+        FieldType::Choice(_) | FieldType::ValueChoice(_) | FieldType::DependentChoice(_) => {
+            "choice"
+        }
+        FieldType::MultiChoice(_) => "multi_choice",
+        FieldType::Optional => "optional",
+        FieldType::OptionalNumber => "optional_number",
+        FieldType::OptionalChoice(_) => "optional_choice",
+        #[cfg(feature = "phone")]
+        FieldType::Phone => "phone",
+    }
+}
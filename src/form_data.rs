@@ -0,0 +1,192 @@
+// src/form_data.rs
+
+use crate::{Parse, Result, VelvetIOError};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// A form's answers by key, parsed into types on the way out
+///
+/// Anything that collects a form converts into it:
+///
+/// ```no_run
+/// use velvetio::{FormData, quick_form};
+///
+/// let data: FormData = quick_form! {
+///     "host" => "Host",
+///     "port" => "Port",
+/// }
+/// .into();
+///
+/// let port: u16 = data.get_as("port")?;
+/// # Ok::<(), velvetio::VelvetIOError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct FormData {
+    answers: HashMap<String, String>,
+    // The lines behind the answers, from `Form::collect_data`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    typed: HashMap<String, String>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    timings: HashMap<String, PromptTiming>,
+}
+
+/// What answering one field took: how long from being asked to being
+/// answered, and how many answers were typed before one was accepted
+///
+/// A field asked again in a review adds to its first time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PromptTiming {
+    pub elapsed: Duration,
+    /// 1 for an answer taken first time, 0 when none was typed (a deadline
+    /// filled it in)
+    pub attempts: u32,
+}
+
+impl PromptTiming {
+    pub(crate) fn add(&mut self, other: PromptTiming) {
+        self.elapsed += other.elapsed;
+        self.attempts += other.attempts;
+    }
+}
+
+impl FormData {
+    pub(crate) fn with_typed(
+        answers: HashMap<String, String>,
+        typed: HashMap<String, String>,
+        timings: Vec<(String, PromptTiming)>,
+    ) -> Self {
+        Self {
+            answers,
+            typed,
+            timings: timings.into_iter().collect(),
+        }
+    }
+
+    /// The answer for `key`, normalized the way the form stores it: `true`
+    /// for a typed `y`, the label for a menu number, the default for enter
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.answers.get(key).map(|answer| answer.as_str())
+    }
+
+    /// Exactly what was typed for `key`, before trimming or any other
+    /// cleanup: `" y"`, `"2"`, or `""` when enter took the default
+    ///
+    /// Only forms collected with [`Form::collect_data`](crate::Form::collect_data)
+    /// keep this. It's the last line read for the field, so after a retry or
+    /// a review it's the one that was accepted. Sensitive answers are in here
+    /// as typed too.
+    ///
+    /// ```
+    /// use velvetio::{form, run_scripted};
+    ///
+    /// let run = run_scripted(&[" yes ", "2"], || {
+    ///     form()
+    ///         .boolean("backups", "Backups")
+    ///         .choice("tier", "Tier", &["free", "pro"])
+    ///         .collect_data()
+    /// });
+    /// assert_eq!(run.value.get("backups"), Some("true"));
+    /// assert_eq!(run.value.raw("backups"), Some(" yes "));
+    /// assert_eq!(run.value.get("tier"), Some("pro"));
+    /// assert_eq!(run.value.raw("tier"), Some("2"));
+    /// ```
+    pub fn raw(&self, key: &str) -> Option<&str> {
+        self.typed.get(key).map(|line| line.as_str())
+    }
+
+    /// How long `key` took to answer, and in how many tries
+    ///
+    /// Like [`raw`](Self::raw), only kept by
+    /// [`Form::collect_data`](crate::Form::collect_data).
+    ///
+    /// ```
+    /// use velvetio::{form, run_scripted};
+    ///
+    /// let run = run_scripted(&["eighty", "80", ""], || {
+    ///     form()
+    ///         .number("port", "Port")
+    ///         .text("host", "Host")
+    ///         .default("localhost")
+    ///         .collect_data()
+    /// });
+    /// assert_eq!(run.value.timing("port").unwrap().attempts, 2);
+    /// assert_eq!(run.value.timing("host").unwrap().attempts, 1);
+    /// ```
+    pub fn timing(&self, key: &str) -> Option<PromptTiming> {
+        self.timings.get(key).copied()
+    }
+
+    /// Every field's timing, slowest first - where people get stuck
+    pub fn timings(&self) -> Vec<(&str, PromptTiming)> {
+        let mut timings: Vec<(&str, PromptTiming)> = self
+            .timings
+            .iter()
+            .map(|(key, timing)| (key.as_str(), *timing))
+            .collect();
+        timings.sort_by(|a, b| b.1.elapsed.cmp(&a.1.elapsed).then(a.0.cmp(b.0)));
+        timings
+    }
+
+    /// The answer for `key` parsed as `T`
+    ///
+    /// Fails if there's no answer for `key` (a skipped optional field, or a
+    /// typo) or it doesn't parse. The error carries the key.
+    pub fn get_as<T: Parse>(&self, key: &str) -> Result<T> {
+        match self.get(key) {
+            Some(answer) => T::parse(answer).map_err(|e| e.with_key(key)),
+            None => Err(
+                VelvetIOError::new(format!("No answer for '{}'", key), "", T::type_name())
+                    .with_key(key),
+            ),
+        }
+    }
+
+    /// Like `get_as`, but no answer for `key` is `Ok(None)`
+    pub fn get_opt<T: Parse>(&self, key: &str) -> Result<Option<T>> {
+        match self.get(key) {
+            Some(answer) => T::parse(answer).map(Some).map_err(|e| e.with_key(key)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.answers.contains_key(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.answers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+
+    /// Keys and answers, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.answers
+            .iter()
+            .map(|(key, answer)| (key.as_str(), answer.as_str()))
+    }
+
+    pub fn into_map(self) -> HashMap<String, String> {
+        self.answers
+    }
+}
+
+impl From<HashMap<String, String>> for FormData {
+    fn from(answers: HashMap<String, String>) -> Self {
+        Self {
+            answers,
+            typed: HashMap::new(),
+            timings: HashMap::new(),
+        }
+    }
+}
+
+impl From<FormData> for HashMap<String, String> {
+    fn from(data: FormData) -> Self {
+        data.answers
+    }
+}
@@ -0,0 +1,251 @@
+// src/forward.rs
+
+// Prompts asked on behalf of a child process. The child runs in protocol
+// mode, so its prompts come out of its stdout as JSON lines; they're asked
+// here with this process's theme, session and terminal (or script, or
+// transport), and the answers go back down its stdin. Wrappers and
+// orchestrators can run several tools and still show one consistent
+// interactive surface.
+//
+// Each forwarded prompt holds the prompt lock like any other, so children
+// forwarded from several threads take turns instead of talking over each
+// other.
+
+use crate::cache::{push_json_string, read_json_string, skip_whitespace};
+use crate::core::{
+    PromptOptions, accept_any, ask_loop, choose_inner, multi_select_inner, report, saved_picks,
+    with_default,
+};
+use crate::script::{errln, out};
+use crate::{AttemptPolicy, Result, VelvetIOError, protocol};
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, ExitStatus, Stdio};
+
+/// Run `command` with its prompts asked here
+///
+/// The child is started in protocol mode (`VELVETIO_PROTOCOL=json`) with
+/// its stdin and stdout piped. Its prompts are asked the way this program
+/// asks its own - menus as menus, secrets hidden - and what it prints is
+/// printed here. Validation is still the child's: a rejected answer comes
+/// back as an error, and the prompt is asked again.
+///
+/// If a prompt here fails (cancelled, or input closed), the child's prompt
+/// is cancelled too, and it's up to the child what happens next. Returns
+/// once the child has exited. Run it inside [`Session::run`](crate::Session::run)
+/// (or use [`Session::forward`](crate::Session::forward)) to answer with a
+/// session's settings.
+///
+/// ```no_run
+/// use std::process::Command;
+///
+/// let status = velvetio::forward_prompts(Command::new("my-tool").arg("init"))?;
+/// # Ok::<(), velvetio::VelvetIOError>(())
+/// ```
+pub fn forward_prompts(command: &mut Command) -> Result<ExitStatus> {
+    let mut child = command
+        .env("VELVETIO_PROTOCOL", "json")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+    let events = child.stdout.take().map(BufReader::new);
+    let answers = child.stdin.take();
+    if let (Some(events), Some(answers)) = (events, answers) {
+        forward_prompts_from(events, answers)?;
+    }
+    Ok(child.wait()?)
+}
+
+/// Like [`forward_prompts`], for a child that's already running, or one
+/// talking over something other than pipes
+///
+/// Reads protocol events from `events` until it ends, writing an answer to
+/// `answers` for each prompt. Lines that aren't events are printed as they
+/// are.
+pub fn forward_prompts_from(events: impl BufRead, mut answers: impl Write) -> Result<()> {
+    for line in events.lines() {
+        let line = line?;
+        let Some(event) = Event::parse(&line) else {
+            out!("{}\n", line);
+            continue;
+        };
+        match event.kind.as_str() {
+            "prompt" => {
+                let reply = match event.ask() {
+                    Ok(answer) => {
+                        let mut reply = format!("{{\"id\":{},\"answer\":", event.id);
+                        push_json_string(&mut reply, &answer);
+                        reply.push('}');
+                        reply
+                    }
+                    Err(_) => format!("{{\"id\":{},\"cancel\":true}}", event.id),
+                };
+                // A child that's gone just stops sending events
+                if writeln!(answers, "{}", reply)
+                    .and_then(|_| answers.flush())
+                    .is_err()
+                {
+                    break;
+                }
+            }
+            "output" if event.stream == "stderr" => errln!("{}", event.text.trim_end_matches('\n')),
+            "output" => out!("{}", event.text),
+            "error" => report(&VelvetIOError::new(event.message, event.input, "")),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+// One protocol event, with what the forwarder uses of it
+#[derive(Debug, Default)]
+struct Event {
+    kind: String,
+    id: u64,
+    text: String,
+    default: Option<String>,
+    choices: Vec<String>,
+    multiple: bool,
+    secret: bool,
+    stream: String,
+    message: String,
+    input: String,
+}
+
+impl Event {
+    // A flat JSON object. `None` for anything else, or an object without a
+    // `type`.
+    fn parse(line: &str) -> Option<Self> {
+        let mut chars = line.trim().chars().peekable();
+        if chars.next()? != '{' {
+            return None;
+        }
+        let mut event = Event::default();
+        loop {
+            skip_whitespace(&mut chars);
+            match chars.peek()? {
+                '}' => break,
+                ',' => {
+                    chars.next();
+                    continue;
+                }
+                _ => {}
+            }
+            let key = read_json_string(&mut chars)?;
+            skip_whitespace(&mut chars);
+            if chars.next()? != ':' {
+                return None;
+            }
+            skip_whitespace(&mut chars);
+            match chars.peek()? {
+                '"' => {
+                    let value = read_json_string(&mut chars)?;
+                    match key.as_str() {
+                        "type" => event.kind = value,
+                        "text" => event.text = value,
+                        "default" => event.default = Some(value),
+                        "stream" => event.stream = value,
+                        "message" => event.message = value,
+                        "input" => event.input = value,
+                        _ => {}
+                    }
+                }
+                '[' => {
+                    chars.next();
+                    let mut items = Vec::new();
+                    loop {
+                        skip_whitespace(&mut chars);
+                        match chars.peek()? {
+                            ']' => {
+                                chars.next();
+                                break;
+                            }
+                            ',' => {
+                                chars.next();
+                            }
+                            _ => items.push(read_json_string(&mut chars)?),
+                        }
+                    }
+                    if key == "choices" {
+                        event.choices = items;
+                    }
+                }
+                // Numbers, true, false and null
+                _ => {
+                    let mut word = String::new();
+                    while let Some(&c) = chars
+                        .peek()
+                        .filter(|c| c.is_ascii_alphanumeric() || "+-.".contains(**c))
+                    {
+                        word.push(c);
+                        chars.next();
+                    }
+                    match key.as_str() {
+                        "id" => event.id = word.parse().ok()?,
+                        "multiple" => event.multiple = word == "true",
+                        "secret" => event.secret = word == "true",
+                        _ if word.is_empty() => return None,
+                        _ => {}
+                    }
+                }
+            }
+        }
+        (!event.kind.is_empty()).then_some(event)
+    }
+
+    // Asks the prompt here. Menus are answered with numbers, which the child
+    // reads the same way whatever its labels look like.
+    fn ask(&self) -> Result<String> {
+        let policy = AttemptPolicy::unlimited();
+        if self.choices.is_empty() {
+            // The default is only shown - it may be masked, so enter sends ""
+            // back and the child fills in its own
+            let json = protocol::is_json_protocol();
+            let text = match &self.default {
+                Some(default) if !json => with_default(&self.text, default),
+                _ => self.text.clone(),
+            };
+            let options =
+                PromptOptions {
+                    secret: self.secret,
+                    details: self.default.clone().filter(|_| json).map(|default| {
+                        protocol::Details {
+                            default: Some(default),
+                            ..protocol::Details::default()
+                        }
+                    }),
+                    ..PromptOptions::with_policy(policy)
+                };
+            return Ok(ask_loop::<String>(&text, None, &accept_any, "", &options)?.0);
+        }
+
+        let number = |picked: &String| {
+            let index = self.choices.iter().position(|choice| choice == picked);
+            index.map_or(String::new(), |index| (index + 1).to_string())
+        };
+        if self.multiple {
+            let default = self
+                .default
+                .as_deref()
+                .map(|default| saved_picks(default, &self.choices));
+            let picked = multi_select_inner(
+                &self.text,
+                &self.choices,
+                default.as_deref(),
+                &policy,
+                false,
+            )?;
+            // An empty answer would ask the child for its default instead
+            if picked.is_empty() {
+                return Ok("none".to_string());
+            }
+            let numbers: Vec<String> = picked.iter().map(number).collect();
+            return Ok(numbers.join(","));
+        }
+        let default = self
+            .default
+            .as_ref()
+            .and_then(|default| self.choices.iter().position(|choice| choice == default));
+        let picked = choose_inner(&self.text, &self.choices, default, &policy)?;
+        Ok(number(&picked))
+    }
+}
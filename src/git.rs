@@ -0,0 +1,129 @@
+// src/git.rs
+
+// Pickers for the branches, tags and remotes of the repository we're run
+// from. They're read with the git command line, so there's no libgit2 to
+// build, and anything git itself can open works.
+
+use crate::core::{PromptOptions, ask_loop, choose_inner, give_up};
+use crate::search::fuzzy_matches;
+use crate::{AttemptPolicy, MAX_ATTEMPTS, Result, VelvetIOError};
+use std::process::Command;
+
+// Longer lists ask for a filter before showing the menu
+const FILTER_OVER: usize = 10;
+
+/// Pick a local branch, most recently committed first
+///
+/// The checked-out branch is the default. Long lists ask for a filter first,
+/// where "rlfx" finds `release/fix-login`. Exits the program if this isn't a
+/// git repository or input is closed.
+///
+/// ```no_run
+/// let branch = velvetio::choose_branch("Deploy which branch");
+/// ```
+pub fn choose_branch(prompt: &str) -> String {
+    branch_loop(prompt, AttemptPolicy::unlimited()).unwrap_or_else(|e| give_up(e))
+}
+
+/// `choose_branch` that gives up after `MAX_ATTEMPTS` invalid answers, and
+/// returns an error outside a git repository
+pub fn try_choose_branch(prompt: &str) -> Result<String> {
+    branch_loop(prompt, AttemptPolicy::limited(MAX_ATTEMPTS))
+}
+
+/// Pick a tag, newest first (the default)
+pub fn choose_tag(prompt: &str) -> String {
+    tag_loop(prompt, AttemptPolicy::unlimited()).unwrap_or_else(|e| give_up(e))
+}
+
+/// `choose_tag` that gives up after `MAX_ATTEMPTS` invalid answers
+pub fn try_choose_tag(prompt: &str) -> Result<String> {
+    tag_loop(prompt, AttemptPolicy::limited(MAX_ATTEMPTS))
+}
+
+/// Pick a remote, with `origin` as the default when there is one
+pub fn choose_remote(prompt: &str) -> String {
+    remote_loop(prompt, AttemptPolicy::unlimited()).unwrap_or_else(|e| give_up(e))
+}
+
+/// `choose_remote` that gives up after `MAX_ATTEMPTS` invalid answers
+pub fn try_choose_remote(prompt: &str) -> Result<String> {
+    remote_loop(prompt, AttemptPolicy::limited(MAX_ATTEMPTS))
+}
+
+fn branch_loop(prompt: &str, policy: AttemptPolicy) -> Result<String> {
+    let branches = git(&[
+        "for-each-ref",
+        "--sort=-committerdate",
+        "--format=%(refname:short)",
+        "refs/heads",
+    ])?;
+    // Detached HEAD has no current branch, so no default
+    let current = git(&["symbolic-ref", "--quiet", "--short", "HEAD"]).unwrap_or_default();
+    pick_ref(prompt, &branches, current.first(), policy)
+}
+
+fn tag_loop(prompt: &str, policy: AttemptPolicy) -> Result<String> {
+    let tags = git(&[
+        "for-each-ref",
+        "--sort=-creatordate",
+        "--format=%(refname:short)",
+        "refs/tags",
+    ])?;
+    pick_ref(prompt, &tags, tags.first(), policy)
+}
+
+fn remote_loop(prompt: &str, policy: AttemptPolicy) -> Result<String> {
+    let remotes = git(&["remote"])?;
+    let origin = remotes.iter().find(|remote| *remote == "origin");
+    pick_ref(prompt, &remotes, origin, policy)
+}
+
+fn pick_ref(
+    prompt: &str,
+    names: &[String],
+    default: Option<&String>,
+    policy: AttemptPolicy,
+) -> Result<String> {
+    let _turn = crate::lock::hold();
+    let default_in = |names: &[String]| default.and_then(|d| names.iter().position(|n| n == d));
+    if names.len() <= FILTER_OVER {
+        return choose_inner(prompt, names, default_in(names), &policy);
+    }
+
+    let filter = format!("{} - filter (enter for all {})", prompt, names.len());
+    let matches_some = |query: &String| !fuzzy_matches(query, names).is_empty();
+    let options = PromptOptions::with_policy(policy.clone());
+    let (query, _) = ask_loop(
+        &filter,
+        None,
+        &matches_some,
+        "Nothing matches that",
+        &options,
+    )?;
+
+    let matching: Vec<String> = fuzzy_matches(&query, names)
+        .into_iter()
+        .map(|index| names[index].clone())
+        .collect();
+    choose_inner(prompt, &matching, default_in(&matching), &policy)
+}
+
+// Non-empty output lines of a git command
+fn git(args: &[&str]) -> Result<Vec<String>> {
+    let output = Command::new("git").args(args).output().map_err(|e| {
+        VelvetIOError::new(format!("Couldn't run git: {}", e), "", "git on the PATH")
+    })?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = format!("git {} failed: {}", args[0], stderr.trim());
+        return Err(VelvetIOError::new(message, "", "a git repository"));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
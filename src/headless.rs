@@ -0,0 +1,66 @@
+// src/headless.rs
+
+// Running wizards where nobody's at the keyboard. Headless mode never touches
+// the terminal: menus, checkboxes and limits all fall back to plain line mode,
+// without colors, bells, animation or cursor movement, so the log reads the
+// same on every run. Answers can be injected up front, from code or from a
+// file named in VELVETIO_ANSWERS, and each one is echoed after its prompt.
+//
+// It's on when `set_headless` was called, VELVETIO_ANSWERS is set, the
+// `headless` feature is enabled, or prompts are talking JSON (protocol mode).
+
+use std::collections::VecDeque;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+static ANSWERS: OnceLock<Mutex<Option<VecDeque<String>>>> = OnceLock::new();
+
+/// Turn on headless mode with `answers` given to the prompts in order
+///
+/// Prompts take their answers from the list instead of stdin, from any
+/// thread. Once it runs out, input counts as closed. Calling it again
+/// replaces whatever's left.
+///
+/// ```no_run
+/// velvetio::set_headless(["my-app", "8080", "y"]);
+/// let name: String = velvetio::ask("Project name");  // "my-app"
+/// ```
+pub fn set_headless<I, S>(answers: I)
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    *answers_lock() = Some(answers.into_iter().map(Into::into).collect());
+}
+
+/// Whether prompts are running headless, in which case they never touch the
+/// terminal
+pub fn is_headless() -> bool {
+    cfg!(feature = "headless") || answers_lock().is_some() || crate::is_json_protocol()
+}
+
+// The next injected answer. `None` when nothing was injected (answers come
+// from stdin); `Some(None)` once they've run out.
+pub(crate) fn answer() -> Option<Option<String>> {
+    answers_lock().as_mut().map(VecDeque::pop_front)
+}
+
+// First use reads VELVETIO_ANSWERS, one answer per line
+fn answers_lock() -> MutexGuard<'static, Option<VecDeque<String>>> {
+    let answers = ANSWERS.get_or_init(|| {
+        let injected = std::env::var_os("VELVETIO_ANSWERS").map(|path| {
+            let text = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+                crate::core::warn(format!(
+                    "Couldn't read VELVETIO_ANSWERS ({}): {}",
+                    path.to_string_lossy(),
+                    e
+                ));
+                String::new()
+            });
+            text.lines().map(str::to_string).collect()
+        });
+        Mutex::new(injected)
+    });
+    answers
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
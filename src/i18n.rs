@@ -0,0 +1,375 @@
+// src/i18n.rs
+
+// Translated prompts. An application registers a bundle of messages per
+// locale, keyed by prompt id, and forms and flows look each field's prompt
+// up by its key - the text in the code is the fallback, so a missing
+// translation just means the original wording.
+//
+// Messages use a small part of ICU MessageFormat: `{name}` for an argument,
+// `{count, plural, =0 {none} one {# file} other {# files}}` with the
+// locale's plural rules, and `{tier, select, pro {...} other {...}}`. In
+// forms, the arguments are the answers so far.
+
+use crate::{Result, VelvetIOError, redact};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Translated prompts for one locale, keyed by prompt id
+///
+/// ```
+/// use velvetio::{Bundle, add_bundle, set_locale, translate};
+///
+/// add_bundle(
+///     Bundle::new("de")
+///         .message("files", "{count, plural, one {# Datei} other {# Dateien}} löschen?"),
+/// );
+/// set_locale("de-AT");
+/// assert_eq!(
+///     translate("files", "Delete {count} files?", &[("count", "3")]),
+///     "3 Dateien löschen?"
+/// );
+/// // No translation, so the fallback, filled in
+/// assert_eq!(translate("dirs", "Delete {count} folders?", &[("count", "1")]), "Delete 1 folders?");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Bundle {
+    locale: String,
+    messages: HashMap<String, String>,
+}
+
+impl Bundle {
+    /// An empty bundle for `locale`, like `pt-BR` or `de`
+    pub fn new(locale: &str) -> Self {
+        Self {
+            locale: normalize(locale),
+            messages: HashMap::new(),
+        }
+    }
+
+    /// The message for prompt `id`
+    pub fn message(mut self, id: &str, text: &str) -> Self {
+        self.messages.insert(id.to_string(), text.to_string());
+        self
+    }
+
+    /// Read `id = message` lines, for bundles kept in files
+    ///
+    /// Blank lines and lines starting with `#` are skipped. Fails on a line
+    /// without an `=`, naming it.
+    pub fn parse(locale: &str, source: &str) -> Result<Self> {
+        let mut bundle = Self::new(locale);
+        for (number, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((id, text)) = line.split_once('=') else {
+                let mut error = VelvetIOError::parse_error(line, "an `id = message` line");
+                error.message = format!("Line {} of the {} bundle has no '='", number + 1, locale);
+                return Err(error);
+            };
+            bundle = bundle.message(id.trim(), text.trim());
+        }
+        Ok(bundle)
+    }
+
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+}
+
+static BUNDLES: Mutex<Vec<Bundle>> = Mutex::new(Vec::new());
+
+// `None` until set, meaning the environment's
+static LOCALE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Register translated prompts. A second bundle for the same locale adds to
+/// the first, replacing messages with the same id.
+pub fn add_bundle(bundle: Bundle) {
+    let mut bundles = lock(&BUNDLES);
+    match bundles.iter_mut().find(|b| b.locale == bundle.locale) {
+        Some(existing) => existing.messages.extend(bundle.messages),
+        None => bundles.push(bundle),
+    }
+}
+
+/// Pick the locale prompts are shown in
+///
+/// Until this is called it comes from `LC_ALL`, `LC_MESSAGES` or `LANG`.
+/// A locale without a bundle of its own uses its language's (`pt-BR` falls
+/// back to `pt`), then the prompts as written.
+///
+/// Decimal answers go by it too. `2,5` is always 2.5, but `1,000` is only
+/// 1.0 where decimals are written with commas - elsewhere it could be a
+/// thousand, so it's asked again.
+///
+/// ```
+/// use velvetio::{Parse, set_locale};
+///
+/// set_locale("de-DE");
+/// assert_eq!(f64::parse("1,000").unwrap(), 1.0);
+/// set_locale("en-US");
+/// let error = f64::parse("1,000").unwrap_err();
+/// assert_eq!(error.message, "Is '1,000' 1000 or 1.000? Type it without the comma");
+/// ```
+pub fn set_locale(locale: &str) {
+    *lock(&LOCALE) = Some(normalize(locale));
+}
+
+/// The locale prompts are shown in: the running session's, the one set with
+/// `set_locale`, or the environment's
+pub fn locale() -> Option<String> {
+    if let Some(locale) = crate::session::locale().or_else(|| lock(&LOCALE).clone()) {
+        return Some(locale);
+    }
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .find_map(|name| std::env::var(name).ok().filter(|value| !value.is_empty()))
+        // "C.UTF-8" and "POSIX" aren't languages
+        .map(|value| normalize(value.split(['.', '@']).next().unwrap_or_default()))
+        .filter(|locale| locale != "C" && locale != "POSIX")
+}
+
+/// The message for `id` in the current locale, or `fallback`, with
+/// `{name}` arguments, plurals and selects filled in from `args`
+pub fn translate(id: &str, fallback: &str, args: &[(&str, &str)]) -> String {
+    let args: HashMap<String, String> = args
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    let (message, locale) = match lookup(id) {
+        Some((message, locale)) => (message, locale),
+        // Plural rules for a fallback are English's, as good a guess as any
+        None => (fallback.to_string(), String::new()),
+    };
+    format(&message, &language(&locale), &args)
+}
+
+// A form field's prompt: translated when there's a message for its key,
+// otherwise as written with `{key}` filled. Sensitive answers show as `***`
+// either way.
+pub(crate) fn prompt(key: &str, written: &str, answers: &HashMap<String, String>) -> String {
+    let Some((message, locale)) = lookup(key) else {
+        return crate::core::fill(written, answers);
+    };
+    let shown: HashMap<String, String> = answers
+        .iter()
+        .map(|(name, answer)| match redact::hidden(name) {
+            true => (name.clone(), crate::REDACTED.to_string()),
+            false => (name.clone(), answer.clone()),
+        })
+        .collect();
+    format(&message, &language(&locale), &shown)
+}
+
+// Whether the locale writes decimals with a comma, like 2,5
+pub(crate) fn decimal_comma() -> bool {
+    let Some(locale) = locale() else {
+        return false;
+    };
+    matches!(
+        language(&locale).as_str(),
+        "de" | "fr"
+            | "es"
+            | "it"
+            | "pt"
+            | "nl"
+            | "ru"
+            | "uk"
+            | "pl"
+            | "cs"
+            | "sk"
+            | "sv"
+            | "da"
+            | "nb"
+            | "nn"
+            | "no"
+            | "fi"
+            | "tr"
+            | "el"
+            | "hu"
+            | "ro"
+            | "bg"
+            | "hr"
+            | "sl"
+            | "id"
+    )
+}
+
+// The message for `id` and the locale it's from
+fn lookup(id: &str) -> Option<(String, String)> {
+    let bundles = lock(&BUNDLES);
+    // Most programs have none, and forms ask on every field
+    if bundles.is_empty() {
+        return None;
+    }
+    let locale = locale()?;
+    let language = language(&locale);
+    [locale.as_str(), language.as_str()]
+        .iter()
+        .find_map(|wanted| {
+            let bundle = bundles.iter().find(|b| b.locale == *wanted)?;
+            let message = bundle.messages.get(id)?;
+            Some((message.clone(), bundle.locale.clone()))
+        })
+}
+
+// `pt_br` and `PT-BR` as `pt-BR`
+pub(crate) fn normalize(locale: &str) -> String {
+    let mut parts = locale.trim().split(['-', '_']);
+    let mut out = parts.next().unwrap_or_default().to_ascii_lowercase();
+    for part in parts {
+        out.push('-');
+        match part.len() {
+            2 => out.push_str(&part.to_ascii_uppercase()),
+            _ => out.push_str(part),
+        }
+    }
+    out
+}
+
+fn language(locale: &str) -> String {
+    locale.split('-').next().unwrap_or_default().to_string()
+}
+
+// Fills in `{name}`, `{name, plural, ...}` and `{name, select, ...}`. `#`
+// inside a plural branch is the number. Anything that doesn't parse is left
+// as written.
+fn format(message: &str, language: &str, args: &HashMap<String, String>) -> String {
+    format_branch(message, language, args, None)
+}
+
+fn format_branch(
+    message: &str,
+    language: &str,
+    args: &HashMap<String, String>,
+    number: Option<&str>,
+) -> String {
+    let mut out = String::with_capacity(message.len());
+    let mut rest = message;
+    while let Some(at) = rest.find(['{', '#']) {
+        out.push_str(&rest[..at]);
+        rest = &rest[at..];
+        if let Some(after) = rest.strip_prefix('#') {
+            out.push_str(number.unwrap_or("#"));
+            rest = after;
+            continue;
+        }
+        match closing(rest) {
+            Some(end) => {
+                match argument(&rest[1..end], language, args, number) {
+                    Some(filled) => out.push_str(&filled),
+                    None => out.push_str(&rest[..=end]),
+                }
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push_str(rest);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+// Where the `}` matching the `{` that `text` starts with is
+fn closing(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (at, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 1 => return Some(at),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+// One `{...}`, braces off. `None` for an unknown argument or a malformed
+// plural or select. A select inside a plural keeps its `#`.
+fn argument(
+    inner: &str,
+    language: &str,
+    args: &HashMap<String, String>,
+    number: Option<&str>,
+) -> Option<String> {
+    let mut parts = inner.splitn(3, ',');
+    let name = parts.next()?.trim();
+    let value = args.get(name)?;
+    let Some(kind) = parts.next() else {
+        return Some(value.clone());
+    };
+    let branches = branches(parts.next()?)?;
+    let chosen = match kind.trim() {
+        "plural" => {
+            let n: f64 = value.trim().parse().ok()?;
+            let exact = branches.iter().find(|(key, _)| {
+                key.strip_prefix('=').and_then(|k| k.parse::<f64>().ok()) == Some(n)
+            });
+            let category = plural_category(language, n);
+            exact
+                .or_else(|| branches.iter().find(|(key, _)| *key == category))
+                .or_else(|| branches.iter().find(|(key, _)| *key == "other"))?
+                .1
+        }
+        "select" => {
+            branches
+                .iter()
+                .find(|(key, _)| *key == value.as_str())
+                .or_else(|| branches.iter().find(|(key, _)| *key == "other"))?
+                .1
+        }
+        _ => return None,
+    };
+    let number = match kind.trim() {
+        "plural" => Some(value.trim()),
+        _ => number,
+    };
+    Some(format_branch(chosen, language, args, number))
+}
+
+// `one {# file} other {# files}` as pairs
+fn branches(text: &str) -> Option<Vec<(&str, &str)>> {
+    let mut branches = Vec::new();
+    let mut rest = text.trim_start();
+    while !rest.is_empty() {
+        let open = rest.find('{')?;
+        let key = rest[..open].trim();
+        let end = closing(&rest[open..])? + open;
+        branches.push((key, &rest[open + 1..end]));
+        rest = rest[end + 1..].trim_start();
+    }
+    Some(branches)
+}
+
+// CLDR's cardinal categories for the common languages. Everything else gets
+// English's: one for exactly 1, other for the rest.
+fn plural_category(language: &str, n: f64) -> &'static str {
+    let whole = n.fract() == 0.0 && n >= 0.0;
+    let i = n.abs().trunc() as u64;
+    let (ones, tens) = (i % 10, i % 100);
+    match language {
+        "ja" | "zh" | "ko" | "vi" | "th" | "id" | "ms" => "other",
+        "fr" | "pt" if i <= 1 => "one",
+        "fr" | "pt" => "other",
+        "ru" | "uk" | "be" if !whole => "other",
+        "ru" | "uk" | "be" if ones == 1 && tens != 11 => "one",
+        "pl" if !whole => "other",
+        "pl" if i == 1 => "one",
+        "ru" | "uk" | "be" | "pl" if (2..=4).contains(&ones) && !(12..=14).contains(&tens) => "few",
+        "ru" | "uk" | "be" | "pl" => "many",
+        "cs" | "sk" if !whole => "many",
+        "cs" | "sk" if i == 1 => "one",
+        "cs" | "sk" if (2..=4).contains(&i) => "few",
+        _ if whole && i == 1 => "one",
+        _ => "other",
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -0,0 +1,335 @@
+// src/input.rs
+
+use crate::charset::Charset;
+use crate::core::{PromptOptions, ask_loop, give_up, warn};
+use crate::editor::LiveCheck;
+use crate::{AttemptPolicy, Parse, Result, parser};
+use std::fmt::Display;
+use std::ops::Sub;
+use std::rc::Rc;
+
+/// Start building a single prompt with per-prompt settings
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use velvetio::{AttemptPolicy, input};
+///
+/// let password: String = input("Password")
+///     .secret()
+///     .validate(|p: &String| p.len() >= 8, "At least 8 characters")
+///     .policy(AttemptPolicy::limited(3).delay(Duration::from_secs(1)))
+///     .ask();
+/// ```
+pub fn input<T: Parse>(prompt: &str) -> Input<T> {
+    Input {
+        prompt: prompt.to_string(),
+        validator: Rc::new(|_| true),
+        error_msg: "Invalid input".to_string(),
+        options: PromptOptions::default(),
+        steps: None,
+        off_step: OffStep::Allow,
+        live: None,
+        default: None,
+    }
+}
+
+/// What `suggest_steps` does with an answer that isn't one of the steps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OffStep {
+    /// Take it as typed
+    #[default]
+    Allow,
+    /// Take it as typed, but say which step is nearest
+    Warn,
+    /// Use the nearest step instead, and say so
+    Snap,
+}
+
+/// A prompt with its settings, built by [`input`]
+pub struct Input<T> {
+    prompt: String,
+    validator: Rc<dyn Fn(&T) -> bool>,
+    error_msg: String,
+    options: PromptOptions,
+    steps: Option<Steps<T>>,
+    // Kept apart from the steps, so it can be set before them
+    off_step: OffStep,
+    // Set by `live_validation`, where T is known to be 'static
+    live: Option<fn(&Input<T>) -> LiveCheck>,
+    // As it would be typed
+    default: Option<String>,
+}
+
+struct Steps<T> {
+    values: Vec<T>,
+    // Shown after the prompt
+    listed: String,
+    // Set where T is known to be a number, so `try_ask` needn't be
+    nearest: fn(&[T], &T) -> Option<T>,
+    settle: fn(&Steps<T>, OffStep, T) -> T,
+}
+
+impl<T: Parse> Input<T> {
+    /// Only accept answers that pass the check
+    pub fn validate<F>(mut self, validator: F, error_msg: &str) -> Self
+    where
+        F: Fn(&T) -> bool + 'static,
+    {
+        self.validator = Rc::new(validator);
+        self.error_msg = error_msg.to_string();
+        self
+    }
+
+    /// How many tries the user gets, and how long to wait between them
+    pub fn policy(mut self, policy: AttemptPolicy) -> Self {
+        self.options.policy = policy;
+        self
+    }
+
+    /// Limit answers to `max` characters
+    ///
+    /// On a terminal, typing stops at the limit and a `12/80` counter shows
+    /// how much room is left, except for secrets. Otherwise longer answers are
+    /// rejected and asked again.
+    pub fn max_chars(mut self, max: usize) -> Self {
+        self.options.max_chars = Some(max);
+        self
+    }
+
+    /// Only accept digits - other keys are ignored on a terminal
+    pub fn digits_only(self) -> Self {
+        self.allowed(Charset::digits())
+    }
+
+    /// Only accept characters from `spec`, written like `a-z0-9-`
+    ///
+    /// On a terminal other keys are ignored as they're typed. Otherwise answers
+    /// with other characters are rejected and asked again.
+    pub fn charset(self, spec: &str) -> Self {
+        self.allowed(Charset::parse(spec))
+    }
+
+    fn allowed(mut self, charset: Charset) -> Self {
+        self.options.allowed = Some(charset);
+        self
+    }
+
+    /// Words that mean "no value" when asking for an `Option`, in place of
+    /// the global ones. Pass `&[]` so only an empty answer means `None`.
+    pub fn none_words(mut self, words: &[&str]) -> Self {
+        self.options.none_words = Some(words.iter().map(|word| word.to_string()).collect());
+        self
+    }
+
+    /// Don't echo what's typed (Unix terminals only)
+    pub fn secret(mut self) -> Self {
+        self.options.secret = true;
+        self
+    }
+
+    /// Keep the answer out of transcripts, caches and error messages, which
+    /// show `***` instead. Prompts that match a sensitive pattern, and
+    /// secret ones, already are.
+    pub fn sensitive(mut self) -> Self {
+        self.options.sensitive = true;
+        self
+    }
+
+    /// What enter gives, shown after the prompt as `[8080]`
+    pub fn default(mut self, value: T) -> Self
+    where
+        T: Display,
+    {
+        self.default = Some(value.to_string());
+        self.options.redact_default = false;
+        self
+    }
+
+    /// A default that's sensitive, like a saved API key: enter still gives
+    /// it, but only the last 4 characters are shown, as `[••••1234]`
+    ///
+    /// ```no_run
+    /// use velvetio::input;
+    ///
+    /// let saved = std::env::var("API_KEY").unwrap_or_default();
+    /// let key: String = input("API key").secret().default_redacted(saved).ask();
+    /// ```
+    pub fn default_redacted(mut self, value: T) -> Self
+    where
+        T: Display,
+    {
+        self.default = Some(value.to_string());
+        self.options.redact_default = true;
+        self
+    }
+
+    /// What to do with answers that aren't one of the `suggest_steps`
+    pub fn off_step(mut self, off_step: OffStep) -> Self {
+        self.off_step = off_step;
+        self
+    }
+
+    /// Check the answer on every key while it's typed, showing ✓ or ✗ and
+    /// what's wrong next to it
+    ///
+    /// Runs the parser and the `validate` check each time, so keep the check
+    /// cheap. Only on a terminal - otherwise answers are checked after enter
+    /// as usual.
+    ///
+    /// ```no_run
+    /// use velvetio::input;
+    ///
+    /// let port: u16 = input("Port")
+    ///     .validate(|p| *p >= 1024, "Pick a port from 1024 up")
+    ///     .live_validation()
+    ///     .ask();
+    /// // Port: 80  ✗ Pick a port from 1024 up
+    /// ```
+    pub fn live_validation(mut self) -> Self
+    where
+        T: 'static,
+    {
+        self.live = Some(live_check);
+        self
+    }
+
+    /// Ask until the policy runs out, then exit
+    pub fn ask(self) -> T {
+        self.try_ask().unwrap_or_else(|e| give_up(e))
+    }
+
+    /// Ask until the policy runs out, then return the error
+    pub fn try_ask(self) -> Result<T> {
+        let prompt = match &self.steps {
+            Some(steps) => format!("{} ({})", self.prompt, steps.listed),
+            None => self.prompt.clone(),
+        };
+        let live_options;
+        let options = match self.live {
+            Some(live) => {
+                live_options = PromptOptions {
+                    live: Some(live(&self)),
+                    ..self.options.clone()
+                };
+                &live_options
+            }
+            None => &self.options,
+        };
+        // A snapped answer is checked as the step it'll become
+        let validator = |value: &T| match (&self.steps, self.off_step) {
+            (Some(steps), OffStep::Snap) => match (steps.nearest)(&steps.values, value) {
+                Some(nearest) => (self.validator)(&nearest),
+                None => (self.validator)(value),
+            },
+            _ => (self.validator)(value),
+        };
+        let (value, _) = ask_loop(
+            &prompt,
+            self.default.as_deref(),
+            &validator,
+            &self.error_msg,
+            options,
+        )?;
+        Ok(match &self.steps {
+            Some(steps) => (steps.settle)(steps, self.off_step, value),
+            None => value,
+        })
+    }
+}
+
+impl<T> Input<T>
+where
+    T: Parse + Display + Copy + PartialOrd + Sub<Output = T>,
+{
+    /// Show suggested values, like memory sizes, next to the prompt
+    ///
+    /// Any number is still accepted unless `off_step` says otherwise.
+    ///
+    /// ```no_run
+    /// use velvetio::{OffStep, input};
+    ///
+    /// let memory: u32 = input("Memory (MB)")
+    ///     .suggest_steps(&[256, 512, 1024, 2048])
+    ///     .off_step(OffStep::Snap)
+    ///     .ask();
+    /// // Memory (MB) (256, 512, 1024, 2048): 1000
+    /// // ⚠️ Using 1024, the nearest suggested value
+    /// ```
+    pub fn suggest_steps(mut self, steps: &[T]) -> Self {
+        if steps.is_empty() {
+            return self;
+        }
+        let listed: Vec<String> = steps.iter().map(|step| step.to_string()).collect();
+        self.steps = Some(Steps {
+            values: steps.to_vec(),
+            listed: listed.join(", "),
+            nearest: nearest_step,
+            settle: settle_step,
+        });
+        self
+    }
+}
+
+// What the editor runs on every key: the parser, then the validator
+pub(crate) fn live_check<T: Parse + 'static>(input: &Input<T>) -> LiveCheck {
+    let validator = Rc::clone(&input.validator);
+    let error_msg = input.error_msg.clone();
+    let none_words = input.options.none_words.clone();
+    LiveCheck(Rc::new(move |text| {
+        match parser::with_none_words(none_words.as_deref(), || T::parse(text)) {
+            Ok(value) if validator(&value) => None,
+            Ok(_) => Some(error_msg.clone()),
+            Err(e) => Some(e.message),
+        }
+    }))
+}
+
+// An answer off the steps, dealt with as `off_step` says
+fn settle_step<T>(steps: &Steps<T>, off_step: OffStep, value: T) -> T
+where
+    T: Display + Copy + PartialOrd + Sub<Output = T>,
+{
+    let Some(nearest) = nearest_step(&steps.values, &value) else {
+        return value;
+    };
+    match off_step {
+        OffStep::Allow => value,
+        OffStep::Warn => {
+            warn(format!(
+                "{} isn't a suggested value - the nearest is {}",
+                value, nearest
+            ));
+            value
+        }
+        OffStep::Snap => {
+            warn(format!("Using {}, the nearest suggested value", nearest));
+            nearest
+        }
+    }
+}
+
+// The step closest to `value`, or None when it's a step already. Ties go to
+// the larger step, since sizing questions would rather have too much.
+pub(crate) fn nearest_step<T>(steps: &[T], value: &T) -> Option<T>
+where
+    T: Copy + PartialOrd + Sub<Output = T>,
+{
+    let distance = |step: T| match step > *value {
+        true => step - *value,
+        false => *value - step,
+    };
+    let mut nearest = *steps.first()?;
+    for &step in steps {
+        if step == *value {
+            return None;
+        }
+        let (closer, tied) = (
+            distance(step) < distance(nearest),
+            distance(step) == distance(nearest),
+        );
+        if closer || (tied && step > nearest) {
+            nearest = step;
+        }
+    }
+    Some(nearest)
+}
@@ -0,0 +1,51 @@
+// src/interactive.rs
+
+use crate::core::{PromptOptions, ask_loop, choose_inner, give_up};
+use crate::{AttemptPolicy, MAX_ATTEMPTS, Parse, Result};
+
+/// Types built by asking the user, usually through `#[derive(Interactive)]`
+/// (with the `derive` feature)
+///
+/// Deriving it for an enum shows the variants as a menu, then asks for each
+/// field of the one picked:
+///
+/// ```ignore
+/// use velvetio::Interactive;
+///
+/// #[derive(Interactive)]
+/// enum Action {
+///     Deploy { environment: String, replicas: u32 },
+///     Rollback(String),
+///     Status,
+/// }
+///
+/// let action = Action::interact("What now?");
+/// ```
+pub trait Interactive: Sized {
+    /// Ask for a value, retrying invalid answers as `policy` allows
+    fn interact_with(prompt: &str, policy: &AttemptPolicy) -> Result<Self>;
+
+    /// Keep asking until every answer is valid
+    ///
+    /// Exits the program if input is closed first.
+    fn interact(prompt: &str) -> Self {
+        Self::interact_with(prompt, &AttemptPolicy::unlimited()).unwrap_or_else(|e| give_up(e))
+    }
+
+    /// Give up after `MAX_ATTEMPTS` invalid answers to any one question
+    fn try_interact(prompt: &str) -> Result<Self> {
+        Self::interact_with(prompt, &AttemptPolicy::limited(MAX_ATTEMPTS))
+    }
+}
+
+// Used by the derived code - index of the variant picked from the menu
+pub fn variant(prompt: &str, labels: &[&str], policy: &AttemptPolicy) -> Result<usize> {
+    let label = choose_inner(prompt, labels, None, policy)?;
+    Ok(labels.iter().position(|l| *l == label).unwrap_or_default())
+}
+
+// Used by the derived code - one field's value
+pub fn field<T: Parse>(prompt: &str, policy: &AttemptPolicy) -> Result<T> {
+    let options = PromptOptions::with_policy(policy.clone());
+    ask_loop(prompt, None, &|_: &T| true, "", &options).map(|(value, _)| value)
+}
@@ -0,0 +1,286 @@
+// src/layout.rs
+
+// Fitting text to the terminal. Width is counted in terminal columns: CJK
+// and emoji take two, combining marks and invisible controls none. The table
+// below covers the common blocks; the `unicode-width` feature swaps in the
+// full Unicode one.
+//
+// Right-to-left labels (Hebrew, Arabic...) are wrapped in bidi isolates on a
+// terminal, so the terminal's reordering stays inside the label and the
+// number before it and anything after it stay where they are.
+
+use crate::theme::with_theme;
+use crate::{markup, script, tty};
+use std::borrow::Cow;
+
+/// Columns to wrap at, or `None` when output isn't going to a terminal
+///
+/// `COLUMNS` wins when it's set, so scripts and tests can pin the width.
+pub(crate) fn width() -> Option<usize> {
+    if let Some(width) = script::pinned_width() {
+        return Some(width);
+    }
+    if let Some(columns) = std::env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.trim().parse::<usize>().ok())
+        .filter(|&c| c > 0)
+    {
+        return Some(columns);
+    }
+
+    if !tty::stdout_is_tty() {
+        return None;
+    }
+    Some(tty::size().map(|(_, columns)| columns).unwrap_or(80))
+}
+
+/// Terminal rows `text` takes up once the terminal wraps it
+pub(crate) fn rows(text: &str, width: usize) -> usize {
+    text.split('\n')
+        .map(|line| text_width(line).div_ceil(width.max(1)).max(1))
+        .sum()
+}
+
+/// `prefix` followed by `text`, wrapped at word boundaries with later lines
+/// indented to line up under the start of the text. Markup in `text` is
+/// resolved for the theme.
+pub(crate) fn hanging<'a>(prefix: &str, text: &'a str) -> Cow<'a, str> {
+    let text = markup::render(text, with_theme(|theme| theme.color));
+    match width() {
+        Some(width) => Cow::Owned(wrap(prefix, &text, width)),
+        None if prefix.is_empty() => text,
+        None => Cow::Owned(format!("{}{}", prefix, text)),
+    }
+}
+
+pub(crate) fn wrap(prefix: &str, text: &str, width: usize) -> String {
+    let indent = text_width(prefix);
+    // Always leave room for a few characters, even on absurdly narrow terminals
+    let room = width.saturating_sub(indent).max(10);
+
+    let mut lines: Vec<String> = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        let mut used = 0;
+
+        for word in paragraph.split_whitespace() {
+            let mut word = word.to_string();
+
+            // Words that can't fit on any line get split wherever they overflow
+            while text_width(&word) > room {
+                if used > 0 {
+                    lines.push(std::mem::take(&mut line));
+                    used = 0;
+                }
+                let rest = word.split_off(fitting(&word, room));
+                lines.push(word);
+                word = rest;
+            }
+
+            let word_width = text_width(&word);
+            if used > 0 && used + 1 + word_width > room {
+                lines.push(std::mem::take(&mut line));
+                used = 0;
+            }
+            if used > 0 {
+                line.push(' ');
+                used += 1;
+            }
+            line.push_str(&word);
+            used += word_width;
+        }
+        lines.push(line);
+    }
+
+    let padding = " ".repeat(indent);
+    let mut out = String::from(prefix);
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+            out.push_str(&padding);
+        }
+        out.push_str(line);
+    }
+    out
+}
+
+/// Lays short cells out in columns, filled top to bottom like `ls`
+///
+/// Returns `None` when there aren't enough cells to bother or fewer than two
+/// columns fit, so the caller can fall back to one per line.
+pub(crate) fn columns(cells: &[String], width: usize) -> Option<Vec<String>> {
+    const MIN_CELLS: usize = 8;
+    const GAP: usize = 3;
+
+    if cells.len() < MIN_CELLS || cells.iter().any(|cell| cell.contains('\n')) {
+        return None;
+    }
+
+    let cell_width = cells.iter().map(|cell| text_width(cell)).max()?;
+    let fit = (width + GAP) / (cell_width + GAP);
+    if fit < 2 {
+        return None;
+    }
+
+    let rows = cells.len().div_ceil(fit);
+    let gap = " ".repeat(GAP);
+    let lines = (0..rows)
+        .map(|row| {
+            let line: Vec<String> = cells
+                .iter()
+                .skip(row)
+                .step_by(rows)
+                .map(|cell| pad(cell, cell_width))
+                .collect();
+            line.join(&gap).trim_end().to_string()
+        })
+        .collect();
+    Some(lines)
+}
+
+/// `text` followed by spaces to fill `width` columns
+pub(crate) fn pad(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(text_width(text));
+    format!("{}{}", text, " ".repeat(padding))
+}
+
+/// Columns `text` takes up on a terminal, not counting escape codes
+pub(crate) fn text_width(text: &str) -> usize {
+    widths(text).map(|(_, width)| width).sum()
+}
+
+/// `text` cut down to `width` columns, ending in `…` when something was cut
+pub(crate) fn truncate(text: &str, width: usize) -> String {
+    if text_width(text) <= width {
+        return text.to_string();
+    }
+    let kept = fitting(text, width.saturating_sub(1));
+    format!("{}…", &text[..kept])
+}
+
+// Byte length of the longest start of `text` that fits in `room` columns,
+// at least one character so splitting always gets somewhere
+fn fitting(text: &str, room: usize) -> usize {
+    let mut used = 0;
+    for (at, width) in widths(text) {
+        used += width;
+        if used > room && at > 0 {
+            return at;
+        }
+    }
+    text.len()
+}
+
+// Where each character starts and the columns it takes. Escape sequences
+// (colors, mostly) take none.
+fn widths(text: &str) -> impl Iterator<Item = (usize, usize)> + '_ {
+    #[derive(PartialEq)]
+    enum State {
+        Text,
+        Escape,
+        Csi,
+    }
+    let mut state = State::Text;
+    text.char_indices().map(move |(at, c)| {
+        let width = match state {
+            State::Text if c == '\x1b' => {
+                state = State::Escape;
+                0
+            }
+            State::Text => char_width(c),
+            State::Escape => {
+                state = if c == '[' { State::Csi } else { State::Text };
+                0
+            }
+            State::Csi => {
+                if ('@'..='~').contains(&c) {
+                    state = State::Text;
+                }
+                0
+            }
+        };
+        (at, width)
+    })
+}
+
+#[cfg(feature = "unicode-width")]
+pub(crate) fn char_width(c: char) -> usize {
+    unicode_width::UnicodeWidthChar::width(c).unwrap_or(0)
+}
+
+#[cfg(not(feature = "unicode-width"))]
+pub(crate) fn char_width(c: char) -> usize {
+    match c as u32 {
+        // Controls, combining marks, zero-width spaces and joiners, bidi
+        // controls and variation selectors
+        0x00..=0x1f
+        | 0x7f..=0x9f
+        | 0x0300..=0x036f
+        | 0x0483..=0x0489
+        | 0x0591..=0x05bd
+        | 0x0610..=0x061a
+        | 0x064b..=0x065f
+        | 0x1ab0..=0x1aff
+        | 0x1dc0..=0x1dff
+        | 0x200b..=0x200f
+        | 0x202a..=0x202e
+        | 0x2060..=0x2069
+        | 0x20d0..=0x20ff
+        | 0xfe00..=0xfe0f
+        | 0xfe20..=0xfe2f
+        | 0xfeff => 0,
+        // Hangul Jamo, CJK, Hangul syllables, fullwidth forms, emoji
+        0x1100..=0x115f
+        | 0x231a..=0x231b
+        | 0x23e9..=0x23ec
+        | 0x2614..=0x2615
+        | 0x26a1
+        | 0x26bd..=0x26be
+        | 0x26d4
+        | 0x2705
+        | 0x270a..=0x270b
+        | 0x274c
+        | 0x2753..=0x2755
+        | 0x2795..=0x2797
+        | 0x2b50
+        | 0x2e80..=0x303e
+        | 0x3041..=0x33ff
+        | 0x3400..=0x4dbf
+        | 0x4e00..=0x9fff
+        | 0xa000..=0xa4cf
+        | 0xac00..=0xd7a3
+        | 0xf900..=0xfaff
+        | 0xfe30..=0xfe4f
+        | 0xff00..=0xff60
+        | 0xffe0..=0xffe6
+        | 0x1f300..=0x1f64f
+        | 0x1f680..=0x1f6ff
+        | 0x1f900..=0x1f9ff
+        | 0x1fa70..=0x1faff
+        | 0x20000..=0x3fffd => 2,
+        _ => 1,
+    }
+}
+
+/// `text` wrapped in a bidi isolate when it has right-to-left letters and
+/// is going to a terminal
+pub(crate) fn isolate(text: &str) -> Cow<'_, str> {
+    match tty::stdout_is_tty() {
+        true => isolated(text),
+        false => Cow::Borrowed(text),
+    }
+}
+
+// First strong isolate ... pop directional isolate. Both are zero width.
+pub(crate) fn isolated(text: &str) -> Cow<'_, str> {
+    if text.chars().any(right_to_left) {
+        Cow::Owned(format!("\u{2068}{}\u{2069}", text))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+// Hebrew, Arabic, Syriac, Thaana, NKo and their presentation forms
+fn right_to_left(c: char) -> bool {
+    matches!(c as u32, 0x0590..=0x08ff | 0xfb1d..=0xfdff | 0xfe70..=0xfefc | 0x10800..=0x10fff | 0x1e800..=0x1efff)
+}
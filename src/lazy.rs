@@ -0,0 +1,137 @@
+// src/lazy.rs
+
+// Choosing from lists too big to build up front. The caller hands us a
+// function that makes one page of options for a filter, and only that page
+// is ever held in memory.
+
+use crate::core::{
+    PromptOptions, choose_prompt, give_up, next_answer, out_of_attempts, pick_choice,
+    print_choices, report,
+};
+use crate::script::outln;
+use crate::{AttemptPolicy, MAX_ATTEMPTS, Result, VelvetIOError, layout, lock};
+use std::fmt::Display;
+
+/// Pick one option from a list that's produced a page at a time
+///
+/// `source(page, filter)` returns the options on `page` (counting from 0) of
+/// those matching `filter`, and nothing past the last page. Answer with a
+/// number or name to pick, `>` and `<` to turn pages, `/text` to filter and
+/// `/` to clear the filter.
+///
+/// Exits the program if input is closed before an option is picked, or if the
+/// first page is empty - use `try_choose_lazy` for sources that might have
+/// nothing in them.
+///
+/// ```no_run
+/// let package = velvetio::choose_lazy("Package", |page, filter| {
+///     (0..1_000_000)
+///         .map(|n| format!("package-{}", n))
+///         .filter(|name| name.contains(filter))
+///         .skip(page * 10)
+///         .take(10)
+///         .collect()
+/// });
+/// ```
+pub fn choose_lazy<T, F>(prompt: &str, source: F) -> T
+where
+    T: Display,
+    F: FnMut(usize, &str) -> Vec<T>,
+{
+    lazy_inner(prompt, source, &AttemptPolicy::unlimited()).unwrap_or_else(|e| give_up(e))
+}
+
+/// `choose_lazy` that returns an error instead of retrying forever
+///
+/// Fails on an empty first page, end of input, cancellation, or after
+/// `MAX_ATTEMPTS` invalid answers.
+pub fn try_choose_lazy<T, F>(prompt: &str, source: F) -> Result<T>
+where
+    T: Display,
+    F: FnMut(usize, &str) -> Vec<T>,
+{
+    lazy_inner(prompt, source, &AttemptPolicy::limited(MAX_ATTEMPTS))
+}
+
+// What an answer asks for: an option on this page, or another page
+enum Answer {
+    Pick(usize),
+    Turn(usize, String),
+}
+
+pub(crate) fn lazy_inner<T, F>(prompt: &str, mut source: F, policy: &AttemptPolicy) -> Result<T>
+where
+    T: Display,
+    F: FnMut(usize, &str) -> Vec<T>,
+{
+    let _turn = lock::hold();
+    let mut items = source(0, "");
+    if items.is_empty() {
+        return Err(VelvetIOError::no_choices().during(prompt, 1));
+    }
+
+    let mut page = 0;
+    let mut filter = String::new();
+    print_choices(prompt, &items);
+    outln!(
+        "{}",
+        layout::hanging("", "Enter > for more, < to go back, or /text to filter:")
+    );
+
+    let options = PromptOptions::with_policy(policy.clone());
+    let mut attempts = 0;
+    let mut ask = || loop {
+        let (input, closed) = next_answer(&choose_prompt(items.len()), &options)?;
+        let error = match read(&input, page, &filter, &items) {
+            Ok(Answer::Pick(index)) => return Ok(items.swap_remove(index)),
+            Ok(Answer::Turn(next_page, next_filter)) => {
+                let next = source(next_page, &next_filter);
+                if next.is_empty() {
+                    let message = if next_filter != filter || next_page == 0 {
+                        format!("Nothing matches '{}'", next_filter)
+                    } else {
+                        "That was the last page".to_string()
+                    };
+                    VelvetIOError::validation_error(input, message)
+                } else {
+                    (page, filter, items) = (next_page, next_filter, next);
+                    print_choices(&heading(prompt, page, &filter), &items);
+                    continue;
+                }
+            }
+            Err(e) => e,
+        };
+
+        if closed {
+            return Err(VelvetIOError::eof());
+        }
+        report(&error.during(prompt, attempts + 1));
+        out_of_attempts(&mut attempts, policy)?;
+    };
+    let result = ask();
+    result.map_err(|e| e.during(prompt, attempts + 1))
+}
+
+fn read<T: Display>(input: &str, page: usize, filter: &str, items: &[T]) -> Result<Answer> {
+    match input {
+        ">" => Ok(Answer::Turn(page + 1, filter.to_string())),
+        "<" if page == 0 => Err(VelvetIOError::validation_error(
+            input,
+            "Already on the first page",
+        )),
+        "<" => Ok(Answer::Turn(page - 1, filter.to_string())),
+        _ => match input.strip_prefix('/') {
+            Some(text) => Ok(Answer::Turn(0, text.trim().to_string())),
+            None => pick_choice(input, items).map(Answer::Pick),
+        },
+    }
+}
+
+// `Package (page 2, matching "foo")`
+fn heading(prompt: &str, page: usize, filter: &str) -> String {
+    match (page, filter) {
+        (0, "") => prompt.to_string(),
+        (page, "") => format!("{} (page {})", prompt, page + 1),
+        (page, filter) => format!("{} (page {}, matching '{}')", prompt, page + 1, filter),
+    }
+}
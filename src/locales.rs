@@ -0,0 +1,343 @@
+// src/locales.rs
+
+// Locales as BCP 47 tags (`de-DE`), generated from the glibc locale list with
+// its language and territory names. Script and modifier variants are left out.
+
+use crate::core::give_up;
+use crate::search::choose_entry;
+use crate::{AttemptPolicy, MAX_ATTEMPTS, Result};
+
+/// Pick a locale and get its BCP 47 tag, like `"pt-BR"`
+///
+/// Type `/` and part of a language, a country or a tag to search:
+/// `/portuguese`, `/brazil` and `/pt_BR` all find `pt-BR`. Exits the program
+/// if input is closed before a locale is picked.
+///
+/// ```no_run
+/// let locale = velvetio::choose_locale("Language");
+/// ```
+pub fn choose_locale(prompt: &str) -> &'static str {
+    locale_loop(prompt, &AttemptPolicy::unlimited()).unwrap_or_else(|e| give_up(e))
+}
+
+/// `choose_locale` that gives up after `MAX_ATTEMPTS` invalid answers
+pub fn try_choose_locale(prompt: &str) -> Result<&'static str> {
+    locale_loop(prompt, &AttemptPolicy::limited(MAX_ATTEMPTS))
+}
+
+fn locale_loop(prompt: &str, policy: &AttemptPolicy) -> Result<&'static str> {
+    let entries: Vec<_> = LOCALES
+        .iter()
+        .map(|(tag, name)| (*tag, format!("{} ({})", name, tag), tag.replace('-', "_")))
+        .collect();
+    choose_entry(prompt, &entries, policy)
+}
+
+// (tag, language and territory)
+const LOCALES: &[(&str, &str)] = &[
+    ("aa-DJ", "Afar (Djibouti)"),
+    ("aa-ER", "Afar (Eritrea)"),
+    ("aa-ET", "Afar (Ethiopia)"),
+    ("ab-GE", "Abkhazian (Georgia)"),
+    ("af-ZA", "Afrikaans (South Africa)"),
+    ("agr-PE", "Aguaruna (Peru)"),
+    ("ak-GH", "Akan (Ghana)"),
+    ("am-ET", "Amharic (Ethiopia)"),
+    ("an-ES", "Aragonese (Spain)"),
+    ("anp-IN", "Angika (India)"),
+    ("ar-AE", "Arabic (United Arab Emirates)"),
+    ("ar-BH", "Arabic (Bahrain)"),
+    ("ar-DZ", "Arabic (Algeria)"),
+    ("ar-EG", "Arabic (Egypt)"),
+    ("ar-IN", "Arabic (India)"),
+    ("ar-IQ", "Arabic (Iraq)"),
+    ("ar-JO", "Arabic (Jordan)"),
+    ("ar-KW", "Arabic (Kuwait)"),
+    ("ar-LB", "Arabic (Lebanon)"),
+    ("ar-LY", "Arabic (Libya)"),
+    ("ar-MA", "Arabic (Morocco)"),
+    ("ar-OM", "Arabic (Oman)"),
+    ("ar-QA", "Arabic (Qatar)"),
+    ("ar-SA", "Arabic (Saudi Arabia)"),
+    ("ar-SD", "Arabic (Sudan)"),
+    ("ar-SS", "Arabic (South Sudan)"),
+    ("ar-SY", "Arabic (Syria)"),
+    ("ar-TN", "Arabic (Tunisia)"),
+    ("ar-YE", "Arabic (Yemen)"),
+    ("as-IN", "Assamese (India)"),
+    ("ast-ES", "Asturian (Spain)"),
+    ("ayc-PE", "Aymara (Peru)"),
+    ("az-AZ", "Azerbaijani (Azerbaijan)"),
+    ("az-IR", "South Azerbaijani (Iran)"),
+    ("be-BY", "Belarusian (Belarus)"),
+    ("bem-ZM", "Bemba (Zambia)"),
+    ("ber-DZ", "Berber (Algeria)"),
+    ("ber-MA", "Berber (Morocco)"),
+    ("bg-BG", "Bulgarian (Bulgaria)"),
+    ("bhb-IN", "Bhili (India)"),
+    ("bho-IN", "Bhojpuri (India)"),
+    ("bho-NP", "Bhojpuri (Nepal)"),
+    ("bi-VU", "Bislama (Vanuatu)"),
+    ("bn-BD", "Bangla (Bangladesh)"),
+    ("bn-IN", "Bangla (India)"),
+    ("bo-CN", "Tibetan (China)"),
+    ("bo-IN", "Tibetan (India)"),
+    ("br-FR", "Breton (France)"),
+    ("brx-IN", "Bodo (India)"),
+    ("bs-BA", "Bosnian (Bosnia & Herzegovina)"),
+    ("byn-ER", "Blin (Eritrea)"),
+    ("ca-AD", "Catalan (Andorra)"),
+    ("ca-ES", "Catalan (Spain)"),
+    ("ca-FR", "Catalan (France)"),
+    ("ca-IT", "Catalan (Italy)"),
+    ("ce-RU", "Chechen (Russia)"),
+    ("chr-US", "Cherokee (United States)"),
+    ("ckb-IQ", "Central Kurdish (Iraq)"),
+    ("cmn-TW", "Mandarin Chinese (Taiwan)"),
+    ("crh-UA", "Crimean Tatar (Ukraine)"),
+    ("cs-CZ", "Czech (Czech Republic)"),
+    ("csb-PL", "Kashubian (Poland)"),
+    ("cv-RU", "Chuvash (Russia)"),
+    ("cy-GB", "Welsh (United Kingdom)"),
+    ("da-DK", "Danish (Denmark)"),
+    ("de-AT", "Austrian German (Austria)"),
+    ("de-BE", "German (Belgium)"),
+    ("de-CH", "Swiss High German (Switzerland)"),
+    ("de-DE", "German (Germany)"),
+    ("de-IT", "German (Italy)"),
+    ("de-LI", "German (Liechtenstein)"),
+    ("de-LU", "German (Luxembourg)"),
+    ("doi-IN", "Dogri (India)"),
+    ("dsb-DE", "Lower Sorbian (Germany)"),
+    ("dv-MV", "Divehi (Maldives)"),
+    ("dz-BT", "Dzongkha (Bhutan)"),
+    ("el-CY", "Greek (Cyprus)"),
+    ("el-GR", "Greek (Greece)"),
+    ("en-AG", "English (Antigua & Barbuda)"),
+    ("en-AU", "Australian English (Australia)"),
+    ("en-BW", "English (Botswana)"),
+    ("en-CA", "Canadian English (Canada)"),
+    ("en-DK", "English (Denmark)"),
+    ("en-GB", "British English (United Kingdom)"),
+    ("en-HK", "English (Hong Kong SAR China)"),
+    ("en-IE", "English (Ireland)"),
+    ("en-IL", "English (Israel)"),
+    ("en-IN", "English (India)"),
+    ("en-NG", "English (Nigeria)"),
+    ("en-NZ", "English (New Zealand)"),
+    ("en-PH", "English (Philippines)"),
+    ("en-SC", "English (Seychelles)"),
+    ("en-SG", "English (Singapore)"),
+    ("en-US", "American English (United States)"),
+    ("en-ZA", "English (South Africa)"),
+    ("en-ZM", "English (Zambia)"),
+    ("en-ZW", "English (Zimbabwe)"),
+    ("es-AR", "Spanish (Argentina)"),
+    ("es-BO", "Spanish (Bolivia)"),
+    ("es-CL", "Spanish (Chile)"),
+    ("es-CO", "Spanish (Colombia)"),
+    ("es-CR", "Spanish (Costa Rica)"),
+    ("es-CU", "Spanish (Cuba)"),
+    ("es-DO", "Spanish (Dominican Republic)"),
+    ("es-EC", "Spanish (Ecuador)"),
+    ("es-ES", "European Spanish (Spain)"),
+    ("es-GT", "Spanish (Guatemala)"),
+    ("es-HN", "Spanish (Honduras)"),
+    ("es-MX", "Mexican Spanish (Mexico)"),
+    ("es-NI", "Spanish (Nicaragua)"),
+    ("es-PA", "Spanish (Panama)"),
+    ("es-PE", "Spanish (Peru)"),
+    ("es-PR", "Spanish (Puerto Rico)"),
+    ("es-PY", "Spanish (Paraguay)"),
+    ("es-SV", "Spanish (El Salvador)"),
+    ("es-US", "Spanish (United States)"),
+    ("es-UY", "Spanish (Uruguay)"),
+    ("es-VE", "Spanish (Venezuela)"),
+    ("et-EE", "Estonian (Estonia)"),
+    ("eu-ES", "Basque (Spain)"),
+    ("eu-FR", "Basque (France)"),
+    ("fa-IR", "Persian (Iran)"),
+    ("ff-SN", "Fulah (Senegal)"),
+    ("fi-FI", "Finnish (Finland)"),
+    ("fil-PH", "Filipino (Philippines)"),
+    ("fo-FO", "Faroese (Faroe Islands)"),
+    ("fr-BE", "French (Belgium)"),
+    ("fr-CA", "Canadian French (Canada)"),
+    ("fr-CH", "Swiss French (Switzerland)"),
+    ("fr-FR", "French (France)"),
+    ("fr-LU", "French (Luxembourg)"),
+    ("fur-IT", "Friulian (Italy)"),
+    ("fy-DE", "Western Frisian (Germany)"),
+    ("fy-NL", "Western Frisian (Netherlands)"),
+    ("ga-IE", "Irish (Ireland)"),
+    ("gd-GB", "Scottish Gaelic (United Kingdom)"),
+    ("gez-ER", "Geez (Eritrea)"),
+    ("gez-ET", "Geez (Ethiopia)"),
+    ("gl-ES", "Galician (Spain)"),
+    ("gu-IN", "Gujarati (India)"),
+    ("gv-GB", "Manx (United Kingdom)"),
+    ("ha-NG", "Hausa (Nigeria)"),
+    ("hak-TW", "Hakka Chinese (Taiwan)"),
+    ("he-IL", "Hebrew (Israel)"),
+    ("hi-IN", "Hindi (India)"),
+    ("hif-FJ", "Fiji Hindi (Fiji)"),
+    ("hne-IN", "Chhattisgarhi (India)"),
+    ("hr-HR", "Croatian (Croatia)"),
+    ("hsb-DE", "Upper Sorbian (Germany)"),
+    ("ht-HT", "Haitian Creole (Haiti)"),
+    ("hu-HU", "Hungarian (Hungary)"),
+    ("hy-AM", "Armenian (Armenia)"),
+    ("ia-FR", "Interlingua (France)"),
+    ("id-ID", "Indonesian (Indonesia)"),
+    ("ig-NG", "Igbo (Nigeria)"),
+    ("ik-CA", "Inupiaq (Canada)"),
+    ("is-IS", "Icelandic (Iceland)"),
+    ("it-CH", "Italian (Switzerland)"),
+    ("it-IT", "Italian (Italy)"),
+    ("iu-CA", "Inuktitut (Canada)"),
+    ("ja-JP", "Japanese (Japan)"),
+    ("ka-GE", "Georgian (Georgia)"),
+    ("kab-DZ", "Kabyle (Algeria)"),
+    ("kk-KZ", "Kazakh (Kazakhstan)"),
+    ("kl-GL", "Kalaallisut (Greenland)"),
+    ("km-KH", "Khmer (Cambodia)"),
+    ("kn-IN", "Kannada (India)"),
+    ("ko-KR", "Korean (South Korea)"),
+    ("kok-IN", "Konkani (India)"),
+    ("ks-IN", "Kashmiri (India)"),
+    ("ku-TR", "Kurdish (Turkey)"),
+    ("kw-GB", "Cornish (United Kingdom)"),
+    ("ky-KG", "Kyrgyz (Kyrgyzstan)"),
+    ("lb-LU", "Luxembourgish (Luxembourg)"),
+    ("lg-UG", "Ganda (Uganda)"),
+    ("li-BE", "Limburgish (Belgium)"),
+    ("li-NL", "Limburgish (Netherlands)"),
+    ("lij-IT", "Ligurian (Italy)"),
+    ("ln-CD", "Lingala (Democratic Republic of the Congo)"),
+    ("lo-LA", "Lao (Laos)"),
+    ("lt-LT", "Lithuanian (Lithuania)"),
+    ("lv-LV", "Latvian (Latvia)"),
+    ("lzh-TW", "Literary Chinese (Taiwan)"),
+    ("mag-IN", "Magahi (India)"),
+    ("mai-IN", "Maithili (India)"),
+    ("mai-NP", "Maithili (Nepal)"),
+    ("mfe-MU", "Morisyen (Mauritius)"),
+    ("mg-MG", "Malagasy (Madagascar)"),
+    ("mhr-RU", "Meadow Mari (Russia)"),
+    ("mi-NZ", "Maori (New Zealand)"),
+    ("miq-NI", "Miskito (Nicaragua)"),
+    ("mjw-IN", "Karbi (India)"),
+    ("mk-MK", "Macedonian (Macedonia)"),
+    ("ml-IN", "Malayalam (India)"),
+    ("mn-MN", "Mongolian (Mongolia)"),
+    ("mni-IN", "Manipuri (India)"),
+    ("mnw-MM", "Mon (Myanmar)"),
+    ("mr-IN", "Marathi (India)"),
+    ("ms-MY", "Malay (Malaysia)"),
+    ("mt-MT", "Maltese (malta)"),
+    ("my-MM", "Burmese (Myanmar (Burma))"),
+    ("nan-TW", "Min Nan Chinese (Taiwan)"),
+    ("nb-NO", "Norwegian Bokm<U00E5>l (Norway)"),
+    ("nds-DE", "Low German (Germany)"),
+    ("nds-NL", "Low Saxon (Netherlands)"),
+    ("ne-NP", "Nepali (Nepal)"),
+    ("nhn-MX", "Central Nahuatl (Mexico)"),
+    ("niu-NU", "Niuean (Niue)"),
+    ("niu-NZ", "Niuean (New Zealand)"),
+    ("nl-AW", "Dutch (Aruba)"),
+    ("nl-BE", "Flemish (Belgium)"),
+    ("nl-NL", "Dutch (Netherlands)"),
+    ("nn-NO", "Norwegian Nynorsk (Norway)"),
+    ("nr-ZA", "South Ndebele (South Africa)"),
+    ("nso-ZA", "Northern Sotho (South Africa)"),
+    ("oc-FR", "Occitan (France)"),
+    ("om-ET", "Oromo (Ethiopia)"),
+    ("om-KE", "Oromo (Kenya)"),
+    ("or-IN", "Odia (India)"),
+    ("os-RU", "Ossetic (Russia)"),
+    ("pa-IN", "Punjabi (India)"),
+    ("pa-PK", "Punjabi (Pakistan)"),
+    ("pap-AW", "Papiamento (Aruba)"),
+    ("pap-CW", "Papiamento (Cura<U00E7>ao)"),
+    ("pl-PL", "Polish (Poland)"),
+    ("ps-AF", "Pashto (Afghanistan)"),
+    ("pt-BR", "Brazilian Portuguese (Brazil)"),
+    ("pt-PT", "European Portuguese (Portugal)"),
+    ("quz-PE", "Cusco Quechua (Peru)"),
+    ("raj-IN", "Rajasthani (India)"),
+    ("rif-MA", "Tarifit (Morocco)"),
+    ("ro-RO", "Romanian (Romania)"),
+    ("ru-RU", "Russian (Russia)"),
+    ("ru-UA", "Russian (Ukraine)"),
+    ("rw-RW", "Kinyarwanda (Rwanda)"),
+    ("sa-IN", "Sanskrit (India)"),
+    ("sah-RU", "Sakha (Russian Federation)"),
+    ("sat-IN", "Santali (India)"),
+    ("sc-IT", "Sardinian (Italy)"),
+    ("sd-IN", "Sindhi (India)"),
+    ("se-NO", "Northern Sami (Norway)"),
+    ("sgs-LT", "Samogitian (Lithuania)"),
+    ("shn-MM", "Shan (Myanmar)"),
+    ("shs-CA", "Shuswap (Canada)"),
+    ("si-LK", "Sinhala (Sri Lanka)"),
+    ("sid-ET", "Sidamo (Ethiopia)"),
+    ("sk-SK", "Slovak (Slovakia)"),
+    ("sl-SI", "Slovenian (Slovenia)"),
+    ("sm-WS", "Samoan (Samoa)"),
+    ("so-DJ", "Somali (Djibouti)"),
+    ("so-ET", "Somali (Ethiopia)"),
+    ("so-KE", "Somali (Kenya)"),
+    ("so-SO", "Somali (Somalia)"),
+    ("sq-AL", "Albanian (Albania)"),
+    ("sq-MK", "Albanian (Macedonia)"),
+    ("sr-ME", "Serbian (Montenegro)"),
+    ("sr-RS", "Serbian (Serbia)"),
+    ("ss-ZA", "Swati (South Africa)"),
+    ("st-ZA", "Southern Sotho (South Africa)"),
+    ("sv-FI", "Swedish (Finland)"),
+    ("sv-SE", "Swedish (Sweden)"),
+    ("sw-KE", "Swahili (Kenya)"),
+    ("sw-TZ", "Swahili (Tanzania)"),
+    ("szl-PL", "Silesian (Poland)"),
+    ("ta-IN", "Tamil (India)"),
+    ("ta-LK", "Tamil (Sri Lanka)"),
+    ("tcy-IN", "Tulu (India)"),
+    ("te-IN", "Telugu (India)"),
+    ("tg-TJ", "Tajik (Tajikistan)"),
+    ("th-TH", "Thai (Thailand)"),
+    ("the-NP", "Chitwania Tharu (Nepal)"),
+    ("ti-ER", "Tigrinya (Eritrea)"),
+    ("ti-ET", "Tigrinya (Ethiopia)"),
+    ("tig-ER", "Tigre (Eritrea)"),
+    ("tk-TM", "Turkmen (Turkmenistan)"),
+    ("tl-PH", "Tagalog (Philippines)"),
+    ("tn-ZA", "Tswana (South Africa)"),
+    ("to-TO", "Tongan (Tonga)"),
+    ("tpi-PG", "Tok Pisin (Papua New Guinea)"),
+    ("tr-CY", "Turkish (Cyprus)"),
+    ("tr-TR", "Turkish (Turkey)"),
+    ("ts-ZA", "Tsonga (South Africa)"),
+    ("tt-RU", "Tatar (Russia)"),
+    ("ug-CN", "Uyghur (China)"),
+    ("uk-UA", "Ukrainian (Ukraine)"),
+    ("unm-US", "Unami Delaware (United States)"),
+    ("ur-IN", "Urdu (India)"),
+    ("ur-PK", "Urdu (Pakistan)"),
+    ("uz-UZ", "Uzbek (Uzbekistan)"),
+    ("ve-ZA", "Venda (South Africa)"),
+    ("vi-VN", "Vietnamese (Vietnam)"),
+    ("wa-BE", "Walloon (Belgium)"),
+    ("wae-CH", "Walser (Switzerland)"),
+    ("wal-ET", "Wolaytta (Ethiopia)"),
+    ("wo-SN", "Wolof (Senegal)"),
+    ("xh-ZA", "Xhosa (South Africa)"),
+    ("yi-US", "Yiddish (United States)"),
+    ("yo-NG", "Yoruba (Nigeria)"),
+    ("yue-HK", "Cantonese (Hong Kong SAR China)"),
+    ("yuw-PG", "Yau (Papua New Guinea)"),
+    ("zh-CN", "Chinese (China)"),
+    ("zh-HK", "Chinese (Hong Kong SAR China)"),
+    ("zh-SG", "Chinese (Singapore)"),
+    ("zh-TW", "Chinese (Taiwan)"),
+    ("zu-ZA", "Zulu (South Africa)"),
+];
@@ -0,0 +1,77 @@
+// src/lock.rs
+
+// One prompt at a time across threads. A prompt prints its question and then
+// waits on stdin, so two running at once garble each other's output and race
+// for the answer. The lock is reentrant: a form holds it for all its fields,
+// and each field's prompt takes it again on the same thread.
+
+use std::marker::PhantomData;
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::thread::{self, ThreadId};
+
+static OWNER: Mutex<Owner> = Mutex::new(Owner {
+    thread: None,
+    depth: 0,
+});
+static RELEASED: Condvar = Condvar::new();
+
+struct Owner {
+    thread: Option<ThreadId>,
+    depth: usize,
+}
+
+/// Run `f` without prompts from other threads getting in between
+///
+/// Every prompt already takes this lock while it runs, so output never
+/// interleaves. Wrap several prompts in it when they belong together.
+///
+/// ```no_run
+/// use velvetio::prelude::*;
+///
+/// let (user, password) = velvetio::with_prompt_lock(|| {
+///     (ask!("User"), velvetio::input::<String>("Password").secret().ask())
+/// });
+/// ```
+pub fn with_prompt_lock<T>(f: impl FnOnce() -> T) -> T {
+    let _turn = hold();
+    f()
+}
+
+// Held while a prompt runs. Not `Send`: it has to be released by the thread
+// that took it.
+pub(crate) struct Turn {
+    _thread: PhantomData<*const ()>,
+}
+
+// Waits until no other thread is prompting
+pub(crate) fn hold() -> Turn {
+    let me = thread::current().id();
+    let mut owner = lock();
+    while owner.thread.is_some_and(|thread| thread != me) {
+        owner = RELEASED
+            .wait(owner)
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+    }
+    owner.thread = Some(me);
+    owner.depth += 1;
+    Turn {
+        _thread: PhantomData,
+    }
+}
+
+impl Drop for Turn {
+    fn drop(&mut self) {
+        let mut owner = lock();
+        owner.depth -= 1;
+        if owner.depth == 0 {
+            owner.thread = None;
+            RELEASED.notify_one();
+        }
+    }
+}
+
+fn lock() -> MutexGuard<'static, Owner> {
+    OWNER
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -0,0 +1,116 @@
+// src/markup.rs
+
+// A tiny inline markup for prompt text: `[bold]project name[/]`. Tags are
+// turned into ANSI codes when the theme has colors and dropped when it
+// doesn't, so prompts never carry raw escape codes into logs, transcripts or
+// width calculations. Anything in brackets that isn't a known style stays as
+// typed, so `Port [8080]` is still just text.
+
+use crate::theme::with_theme;
+use std::borrow::Cow;
+
+// The styles a tag can name, with their SGR parameters
+const STYLES: &[(&str, &str)] = &[
+    ("bold", "1"),
+    ("dim", "2"),
+    ("italic", "3"),
+    ("underline", "4"),
+    ("red", "31"),
+    ("green", "32"),
+    ("yellow", "33"),
+    ("blue", "34"),
+    ("magenta", "35"),
+    ("cyan", "36"),
+    // What the theme highlights with
+    ("accent", "1;36"),
+];
+
+/// Resolve markup in `text` against the current theme
+///
+/// `[bold]`, `[dim]`, `[italic]`, `[underline]`, a color (`[red]`, `[green]`,
+/// `[yellow]`, `[blue]`, `[magenta]`, `[cyan]`) or `[accent]` start a style,
+/// several can share a tag (`[bold red]`), and `[/]` ends the last one.
+/// Write `[[` for a literal `[`. With colors off the tags are just removed.
+///
+/// Prompts do this themselves; it's here for your own output.
+///
+/// ```
+/// velvetio::set_theme(velvetio::Theme::plain());
+/// assert_eq!(velvetio::render_markup("Enter the [bold]name[/]"), "Enter the name");
+/// ```
+pub fn render_markup(text: &str) -> String {
+    render(text, with_theme(|theme| theme.color)).into_owned()
+}
+
+pub(crate) fn render(text: &str, color: bool) -> Cow<'_, str> {
+    // Nothing to do for the usual prompt
+    if !text.contains('[') {
+        return Cow::Borrowed(text);
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut open: Vec<String> = Vec::new();
+    let mut rest = text;
+    while let Some(at) = rest.find('[') {
+        out.push_str(&rest[..at]);
+        rest = &rest[at..];
+
+        if let Some(after) = rest.strip_prefix("[[") {
+            out.push('[');
+            rest = after;
+            continue;
+        }
+        let Some(end) = rest.find(']') else {
+            break;
+        };
+        let tag = &rest[1..end];
+        if tag == "/" {
+            if open.pop().is_some() && color {
+                // Back to plain, then whatever's still open
+                out.push_str("\x1b[0m");
+                for codes in &open {
+                    out.push_str(&format!("\x1b[{}m", codes));
+                }
+            }
+        } else if let Some(codes) = style(tag) {
+            if color {
+                out.push_str(&format!("\x1b[{}m", codes));
+            }
+            open.push(codes);
+        } else {
+            out.push('[');
+            rest = &rest[1..];
+            continue;
+        }
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+
+    if color && !open.is_empty() {
+        out.push_str("\x1b[0m");
+    }
+    Cow::Owned(out)
+}
+
+/// The text without markup, as it reads with colors off
+pub(crate) fn strip(text: &str) -> String {
+    render(text, false).into_owned()
+}
+
+/// `text` with its brackets escaped, so it's shown as is
+pub(crate) fn escape(text: &str) -> Cow<'_, str> {
+    match text.contains('[') {
+        true => Cow::Owned(text.replace('[', "[[")),
+        false => Cow::Borrowed(text),
+    }
+}
+
+// SGR codes for a tag like `bold red`, if every word is a style
+fn style(tag: &str) -> Option<String> {
+    let mut codes = Vec::new();
+    for word in tag.split_whitespace() {
+        let (_, code) = STYLES.iter().find(|(name, _)| *name == word)?;
+        codes.push(*code);
+    }
+    (!codes.is_empty()).then(|| codes.join(";"))
+}
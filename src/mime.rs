@@ -0,0 +1,334 @@
+// src/mime.rs
+
+// MIME types and file extensions, checked against a table of the common ones.
+// Unknown values are fine - new types turn up all the time - unless they're
+// one slip away from a known one, which is far more likely a typo.
+
+use crate::{Parse, Result, VelvetIOError};
+use std::fmt;
+
+/// A MIME type like `application/json`, lowercased
+///
+/// An extension works as an answer too: `.json` or `json` gives
+/// `application/json`. Parameters (`; charset=utf-8`) are kept.
+///
+/// ```
+/// use velvetio::{MimeType, Parse};
+///
+/// assert_eq!(MimeType::parse("Text/HTML").unwrap().to_string(), "text/html");
+/// assert_eq!(MimeType::parse(".png").unwrap().essence(), "image/png");
+///
+/// let typo = MimeType::parse("application/jsn").unwrap_err();
+/// assert_eq!(typo.message, "Unknown type application/jsn - did you mean application/json?");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MimeType {
+    essence: String,
+    params: String,
+}
+
+impl MimeType {
+    /// Just `type/subtype`, without parameters
+    pub fn essence(&self) -> &str {
+        &self.essence
+    }
+
+    /// The usual extension for this type, like `"json"`
+    pub fn extension(&self) -> Option<&'static str> {
+        KNOWN
+            .iter()
+            .find(|(_, mime)| *mime == self.essence)
+            .map(|(ext, _)| *ext)
+    }
+}
+
+impl fmt::Display for MimeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.params.is_empty() {
+            true => write!(f, "{}", self.essence),
+            false => write!(f, "{}; {}", self.essence, self.params),
+        }
+    }
+}
+
+impl Parse for MimeType {
+    fn parse(input: &str) -> Result<Self> {
+        let trimmed = input.trim();
+        let (essence, params) = trimmed.split_once(';').unwrap_or((trimmed, ""));
+        let essence = essence.trim().to_lowercase();
+        let params = params.trim().to_string();
+
+        // An extension instead of a type
+        if !essence.contains('/') {
+            let extension = FileExtension::parse(&essence)
+                .map_err(|_| VelvetIOError::parse_error(input, Self::type_name()))?;
+            return match extension.mime_type() {
+                Some(mime) => Ok(MimeType { params, ..mime }),
+                None => Err(VelvetIOError::validation_error(
+                    input,
+                    format!("No known type for .{}, enter it like text/plain", extension),
+                )),
+            };
+        }
+
+        let token = |s: &str| {
+            !s.is_empty()
+                && s.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "!#$&-^_.+".contains(c))
+        };
+        match essence.split_once('/') {
+            Some((kind, subtype)) if token(kind) && token(subtype) => {}
+            _ => return Err(VelvetIOError::parse_error(input, Self::type_name())),
+        }
+
+        let known = KNOWN
+            .iter()
+            .map(|(_, mime)| *mime)
+            .chain(EXTRA.iter().copied());
+        if let Some(close) = typo_of(&essence, known) {
+            let message = format!("Unknown type {} - did you mean {}?", essence, close);
+            return Err(VelvetIOError::validation_error(input, message));
+        }
+        Ok(MimeType { essence, params })
+    }
+
+    fn type_name() -> &'static str {
+        "MIME type, like application/json"
+    }
+}
+
+/// A file extension without the dot, lowercased: `.JPG` gives `jpg`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FileExtension {
+    extension: String,
+}
+
+impl FileExtension {
+    pub fn as_str(&self) -> &str {
+        &self.extension
+    }
+
+    /// The MIME type files with this extension usually have
+    pub fn mime_type(&self) -> Option<MimeType> {
+        KNOWN
+            .iter()
+            .find(|(ext, _)| *ext == self.extension)
+            .map(|(_, mime)| MimeType {
+                essence: mime.to_string(),
+                params: String::new(),
+            })
+    }
+}
+
+impl fmt::Display for FileExtension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.extension)
+    }
+}
+
+impl Parse for FileExtension {
+    fn parse(input: &str) -> Result<Self> {
+        let extension = input.trim().trim_start_matches('.').to_lowercase();
+        if extension.is_empty() || !extension.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(VelvetIOError::parse_error(input, Self::type_name()));
+        }
+
+        // Short extensions are too close to each other to guess at
+        let known = KNOWN
+            .iter()
+            .map(|(ext, _)| *ext)
+            .filter(|ext| ext.len() >= 3);
+        if let Some(close) = typo_of(&extension, known).filter(|_| extension.len() >= 3) {
+            let message = format!(
+                "Unknown extension .{} - did you mean .{}?",
+                extension, close
+            );
+            return Err(VelvetIOError::validation_error(input, message));
+        }
+        Ok(FileExtension { extension })
+    }
+
+    fn type_name() -> &'static str {
+        "file extension, like .json"
+    }
+}
+
+/// String is a MIME type (or extension) `MimeType` would accept
+// Takes &String so it can be passed straight to `validate:` for String prompts
+#[allow(clippy::ptr_arg)]
+pub fn is_mime_type(s: &String) -> bool {
+    MimeType::parse(s).is_ok()
+}
+
+/// String is a file extension `FileExtension` would accept
+#[allow(clippy::ptr_arg)]
+pub fn is_file_extension(s: &String) -> bool {
+    FileExtension::parse(s).is_ok()
+}
+
+// The known value one edit away from `value`, when `value` isn't known itself
+fn typo_of<'a>(value: &str, known: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let mut closest = None;
+    for candidate in known {
+        match edit_distance(value, candidate) {
+            0 => return None,
+            1 if closest.is_none() => closest = Some(candidate),
+            _ => {}
+        }
+    }
+    closest
+}
+
+// Levenshtein distance, counting a swap of neighbours as one edit since
+// that's the most common slip of all
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    rows[0] = (0..=b.len()).collect();
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
+// (extension, type), the preferred extension first when a type has several
+const KNOWN: &[(&str, &str)] = &[
+    // Text and code
+    ("txt", "text/plain"),
+    ("html", "text/html"),
+    ("htm", "text/html"),
+    ("css", "text/css"),
+    ("csv", "text/csv"),
+    ("tsv", "text/tab-separated-values"),
+    ("md", "text/markdown"),
+    ("markdown", "text/markdown"),
+    ("ics", "text/calendar"),
+    ("vcf", "text/vcard"),
+    ("js", "text/javascript"),
+    ("mjs", "text/javascript"),
+    ("ts", "application/typescript"),
+    ("rs", "text/x-rust"),
+    ("py", "text/x-python"),
+    ("go", "text/x-go"),
+    ("java", "text/x-java"),
+    ("c", "text/x-c"),
+    ("h", "text/x-c"),
+    ("cpp", "text/x-c++"),
+    ("jsx", "text/jsx"),
+    ("tsx", "text/tsx"),
+    ("scss", "text/x-scss"),
+    ("sass", "text/x-sass"),
+    ("less", "text/less"),
+    ("vue", "text/x-vue"),
+    ("rb", "text/x-ruby"),
+    ("php", "application/x-httpd-php"),
+    ("kt", "text/x-kotlin"),
+    ("swift", "text/x-swift"),
+    ("log", "text/plain"),
+    ("ini", "text/plain"),
+    ("conf", "text/plain"),
+    ("sh", "application/x-sh"),
+    ("sql", "application/sql"),
+    // Data
+    ("json", "application/json"),
+    ("jsonld", "application/ld+json"),
+    ("ndjson", "application/x-ndjson"),
+    ("xml", "application/xml"),
+    ("yaml", "application/yaml"),
+    ("yml", "application/yaml"),
+    ("toml", "application/toml"),
+    ("wasm", "application/wasm"),
+    ("bin", "application/octet-stream"),
+    // Documents
+    ("pdf", "application/pdf"),
+    ("rtf", "application/rtf"),
+    ("doc", "application/msword"),
+    (
+        "docx",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    ),
+    ("xls", "application/vnd.ms-excel"),
+    (
+        "xlsx",
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+    ),
+    ("ppt", "application/vnd.ms-powerpoint"),
+    (
+        "pptx",
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+    ),
+    ("odt", "application/vnd.oasis.opendocument.text"),
+    ("ods", "application/vnd.oasis.opendocument.spreadsheet"),
+    ("epub", "application/epub+zip"),
+    // Archives
+    ("zip", "application/zip"),
+    ("gz", "application/gzip"),
+    ("tar", "application/x-tar"),
+    ("bz2", "application/x-bzip2"),
+    ("xz", "application/x-xz"),
+    ("zst", "application/zstd"),
+    ("7z", "application/x-7z-compressed"),
+    ("rar", "application/vnd.rar"),
+    // Images
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("avif", "image/avif"),
+    ("svg", "image/svg+xml"),
+    ("ico", "image/vnd.microsoft.icon"),
+    ("bmp", "image/bmp"),
+    ("tif", "image/tiff"),
+    ("tiff", "image/tiff"),
+    ("heic", "image/heic"),
+    // Audio
+    ("mp3", "audio/mpeg"),
+    ("wav", "audio/wav"),
+    ("ogg", "audio/ogg"),
+    ("oga", "audio/ogg"),
+    ("flac", "audio/flac"),
+    ("aac", "audio/aac"),
+    ("m4a", "audio/mp4"),
+    ("opus", "audio/opus"),
+    ("weba", "audio/webm"),
+    // Video
+    ("mp4", "video/mp4"),
+    ("webm", "video/webm"),
+    ("ogv", "video/ogg"),
+    ("mov", "video/quicktime"),
+    ("avi", "video/x-msvideo"),
+    ("mkv", "video/x-matroska"),
+    ("mpeg", "video/mpeg"),
+    // Fonts
+    ("woff", "font/woff"),
+    ("woff2", "font/woff2"),
+    ("ttf", "font/ttf"),
+    ("otf", "font/otf"),
+];
+
+// Common types without an extension of their own
+const EXTRA: &[&str] = &[
+    "application/x-www-form-urlencoded",
+    "multipart/form-data",
+    "multipart/mixed",
+    "message/rfc822",
+    "application/problem+json",
+    "application/graphql",
+    "application/json5",
+    "text/event-stream",
+];
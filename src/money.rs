@@ -0,0 +1,225 @@
+// src/money.rs
+
+// Amounts of money, kept as whole minor units (cents) so nothing is lost to
+// floating point on the way in.
+
+use crate::core::{PromptOptions, accept_any, ask_loop, give_up};
+use crate::{AttemptPolicy, MAX_ATTEMPTS, Parse, Result, VelvetIOError};
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+thread_local! {
+    // Decimal places `Parse` allows, changed while `ask_money` asks
+    static PLACES: Cell<u32> = const { Cell::new(2) };
+}
+
+/// An exact amount with a fixed number of decimal places
+///
+/// Parses answers like `$1,234.56`, `1234.5` or `-€20`: a currency symbol and
+/// thousands separators are fine, more decimal places than allowed aren't.
+/// As a plain `Parse` type it allows 2 places; [`ask_money`] picks its own.
+///
+/// Amounts compare by value whatever their places, so 1.5 equals 1.50.
+#[derive(Debug, Clone, Copy)]
+pub struct Money {
+    minor: i64,
+    places: u32,
+}
+
+impl Money {
+    /// `minor` hundredths (or thousandths...) depending on `places`
+    pub fn new(minor: i64, places: u32) -> Self {
+        Self { minor, places }
+    }
+
+    /// The amount in its smallest unit - 1234.56 with 2 places is 123456
+    pub fn minor_units(&self) -> i64 {
+        self.minor
+    }
+
+    pub fn places(&self) -> u32 {
+        self.places
+    }
+
+    /// Close to the amount, for display math only
+    pub fn to_f64(&self) -> f64 {
+        self.minor as f64 / 10f64.powi(self.places as i32)
+    }
+
+    // The same amount without trailing zero places, so 1.50 is 1.5
+    fn trimmed(&self) -> (i64, u32) {
+        let (mut minor, mut places) = (self.minor, self.places);
+        while places > 0 && minor % 10 == 0 {
+            minor /= 10;
+            places -= 1;
+        }
+        (minor, places)
+    }
+
+    // Answers like "$1,234.5", converted to `places` decimal places
+    fn parse_with(input: &str, places: u32) -> Result<Self> {
+        let invalid = || VelvetIOError::parse_error(input, "amount, like 1,234.56");
+
+        // The sign can go before or after the currency symbol
+        let mut text = input.trim();
+        let mut negative = false;
+        for _ in 0..2 {
+            if let Some(rest) = text.strip_prefix('-') {
+                negative = true;
+                text = rest.trim_start();
+            }
+            text = text.trim_start_matches(['$', '€', '£', '¥']).trim_start();
+        }
+
+        let (whole, fraction) = text.split_once('.').unwrap_or((text, ""));
+        let whole = ungrouped(whole).ok_or_else(invalid)?;
+        if (whole.is_empty() && fraction.is_empty())
+            || !fraction.chars().all(|c| c.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+        if fraction.len() > places as usize {
+            let message = format!("Use at most {} decimal places", places);
+            return Err(VelvetIOError::validation_error(input, message));
+        }
+
+        let digits = format!("{}{:0<width$}", whole, fraction, width = places as usize);
+        let minor: i64 = digits.parse().map_err(|_| invalid())?;
+        Ok(Self::new(if negative { -minor } else { minor }, places))
+    }
+}
+
+// "1,234,567" as "1234567" - separators have to fall every three digits
+fn ungrouped(whole: &str) -> Option<String> {
+    let mut groups = whole.split([',', '_']);
+    let first = groups.next()?;
+    let mut digits = first.to_string();
+    for group in groups {
+        if group.len() != 3 || first.is_empty() {
+            return None;
+        }
+        digits.push_str(group);
+    }
+    digits.chars().all(|c| c.is_ascii_digit()).then_some(digits)
+}
+
+impl PartialEq for Money {
+    fn eq(&self, other: &Self) -> bool {
+        self.trimmed() == other.trimmed()
+    }
+}
+
+impl Eq for Money {}
+
+impl Hash for Money {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.trimmed().hash(state);
+    }
+}
+
+impl PartialOrd for Money {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Money {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let ((a, a_places), (b, b_places)) = (self.trimmed(), other.trimmed());
+        // Scaled to the same places. One too big to scale is bigger than
+        // anything the other can be, so its sign decides.
+        let scaled = |minor: i64, by: u32| {
+            10i128
+                .checked_pow(by)
+                .and_then(|scale| (minor as i128).checked_mul(scale))
+        };
+        match a_places.cmp(&b_places) {
+            Ordering::Equal => a.cmp(&b),
+            Ordering::Less => match scaled(a, b_places - a_places) {
+                Some(a) => a.cmp(&(b as i128)),
+                None => a.cmp(&0),
+            },
+            Ordering::Greater => match scaled(b, a_places - b_places) {
+                Some(b) => (a as i128).cmp(&b),
+                None => 0.cmp(&b),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.minor < 0 { "-" } else { "" };
+        let digits = format!(
+            "{:0>width$}",
+            self.minor.unsigned_abs(),
+            width = self.places as usize + 1
+        );
+        let (whole, fraction) = digits.split_at(digits.len() - self.places as usize);
+        match fraction.is_empty() {
+            true => write!(f, "{}{}", sign, whole),
+            false => write!(f, "{}{}.{}", sign, whole, fraction),
+        }
+    }
+}
+
+impl Parse for Money {
+    fn parse(input: &str) -> Result<Self> {
+        Self::parse_with(input, PLACES.get())
+    }
+
+    fn type_name() -> &'static str {
+        "amount"
+    }
+}
+
+/// Ask for an amount of money with at most `places` decimal places
+///
+/// Exits the program if input is closed before a valid answer arrives.
+///
+/// ```no_run
+/// let budget = velvetio::ask_money("Budget", 2);
+/// println!("{} cents", budget.minor_units());
+/// ```
+pub fn ask_money(prompt: &str, places: u32) -> Money {
+    money_loop(prompt, places, &PromptOptions::default()).unwrap_or_else(|e| give_up(e))
+}
+
+/// `ask_money` that gives up after `MAX_ATTEMPTS` invalid answers
+pub fn try_ask_money(prompt: &str, places: u32) -> Result<Money> {
+    let options = PromptOptions::with_policy(AttemptPolicy::limited(MAX_ATTEMPTS));
+    money_loop(prompt, places, &options)
+}
+
+fn money_loop(prompt: &str, places: u32, options: &PromptOptions) -> Result<Money> {
+    // Parsed with this prompt's places, so an answer with too many is told so
+    let previous = PLACES.replace(places);
+    let result = ask_loop(prompt, None, &accept_any, "", options);
+    PLACES.set(previous);
+    result.map(|(money, _)| money)
+}
+
+#[cfg(feature = "decimal")]
+impl Parse for rust_decimal::Decimal {
+    fn parse(input: &str) -> Result<Self> {
+        // Any number of places, as long as it fits
+        let places = input
+            .split_once('.')
+            .map_or(0, |(_, fraction)| fraction.len());
+        let money = Money::parse_with(input, places.min(28) as u32)?;
+        Ok(money.into())
+    }
+
+    fn type_name() -> &'static str {
+        "decimal"
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl From<Money> for rust_decimal::Decimal {
+    fn from(money: Money) -> Self {
+        rust_decimal::Decimal::new(money.minor, money.places)
+    }
+}
@@ -1,11 +1,20 @@
 // src/parser.rs
 
 use crate::error::{Result, VelvetIOError};
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::sync::Mutex;
 
 /// Parse strings into Rust types
 pub trait Parse: Sized {
     fn parse(input: &str) -> Result<Self>;
     fn type_name() -> &'static str;
+
+    /// Parse an answer the caller already owns. Types that just wrap the text,
+    /// like `String` and `PathBuf`, take it over instead of copying it.
+    fn parse_owned(input: String) -> Result<Self> {
+        Self::parse(&input)
+    }
 }
 
 impl Parse for String {
@@ -13,18 +22,39 @@ impl Parse for String {
         Ok(input.to_string())
     }
 
+    fn parse_owned(input: String) -> Result<Self> {
+        Ok(input)
+    }
+
     fn type_name() -> &'static str {
         "text"
     }
 }
 
-// Accept many ways to say yes/no
+impl Parse for PathBuf {
+    fn parse(input: &str) -> Result<Self> {
+        Ok(PathBuf::from(input))
+    }
+
+    fn parse_owned(input: String) -> Result<Self> {
+        Ok(PathBuf::from(input))
+    }
+
+    fn type_name() -> &'static str {
+        "path"
+    }
+}
+
+// Accept many ways to say yes/no, or the start of one ("tr", "ye")
 impl Parse for bool {
     fn parse(input: &str) -> Result<Self> {
-        match input.trim().to_lowercase().as_str() {
-            "true" | "t" | "yes" | "y" | "1" | "on" => Ok(true),
-            "false" | "f" | "no" | "n" | "0" | "off" => Ok(false),
-            _ => Err(VelvetIOError::parse_error(
+        const WORDS: [&str; 12] = [
+            "true", "t", "yes", "y", "1", "on", "false", "f", "no", "n", "0", "off",
+        ];
+        match find_name(input.trim(), &WORDS) {
+            Ok(index) => Ok(index < 6),
+            Err(matches) if matches.len() > 1 => Err(ambiguous(input, &WORDS, &matches)),
+            Err(_) => Err(VelvetIOError::parse_error(
                 input,
                 "boolean (yes/no, true/false, y/n, 1/0)",
             )),
@@ -51,6 +81,134 @@ impl Parse for char {
     }
 }
 
+/// Which of `names` the answer means, for `Parse` impls on enums and the like
+///
+/// Case doesn't matter, and any unambiguous start of a name works too, so
+/// "post" finds "PostgreSQL". Returns the index of the name that matched.
+///
+/// ```
+/// use velvetio::{Parse, Result, match_name};
+///
+/// enum Database { Postgres, MySql, Sqlite }
+///
+/// impl Parse for Database {
+///     fn parse(input: &str) -> Result<Self> {
+///         let all = [Database::Postgres, Database::MySql, Database::Sqlite];
+///         let index = match_name(input, &["PostgreSQL", "MySQL", "SQLite"])?;
+///         Ok(all.into_iter().nth(index).unwrap())
+///     }
+///
+///     fn type_name() -> &'static str {
+///         "database"
+///     }
+/// }
+///
+/// assert!(matches!(Database::parse("post"), Ok(Database::Postgres)));
+/// ```
+pub fn match_name(input: &str, names: &[&str]) -> Result<usize> {
+    let trimmed = input.trim();
+    find_name(trimmed, names).map_err(|matches| match matches.as_slice() {
+        [] => VelvetIOError::parse_error(input, format!("one of: {}", names.join(", "))),
+        _ => ambiguous(input, names, &matches),
+    })
+}
+
+// An exact match (ignoring case) wins, otherwise the one name the answer
+// starts. `Err` has every name that fit - empty when none did.
+pub(crate) fn find_name<S: AsRef<str>>(
+    input: &str,
+    names: &[S],
+) -> std::result::Result<usize, Vec<usize>> {
+    if input.is_empty() {
+        return Err(Vec::new());
+    }
+
+    let wanted = input.to_lowercase();
+    let names: Vec<String> = names.iter().map(|n| n.as_ref().to_lowercase()).collect();
+    if let Some(index) = names.iter().position(|name| *name == wanted) {
+        return Ok(index);
+    }
+
+    let matches: Vec<usize> = (0..names.len())
+        .filter(|&i| names[i].starts_with(&wanted))
+        .collect();
+    match matches.as_slice() {
+        [index] => Ok(*index),
+        _ => Err(matches),
+    }
+}
+
+// "'po' is ambiguous, could be PostgreSQL or Postgis" - for two or more matches
+pub(crate) fn ambiguous<S: AsRef<str>>(
+    input: &str,
+    names: &[S],
+    matches: &[usize],
+) -> VelvetIOError {
+    let mut names: Vec<&str> = matches.iter().map(|&i| names[i].as_ref()).collect();
+    let last = names.pop().unwrap_or_default();
+
+    let mut error = VelvetIOError::parse_error(input, "an unambiguous name");
+    error.message = format!(
+        "'{}' is ambiguous, could be {} or {}",
+        input.trim(),
+        names.join(", "),
+        last
+    );
+    error
+}
+
+/// Words that mean "no value" for optional answers, unless changed with
+/// [`set_none_words`]
+pub const DEFAULT_NONE_WORDS: &[&str] = &["none", "null", "nil", "-", "skip"];
+
+// Replaces the defaults once set
+static NONE_WORDS: Mutex<Option<Vec<String>>> = Mutex::new(None);
+
+thread_local! {
+    // Words for the prompt being answered right now, from `Input::none_words`
+    static PROMPT_NONE_WORDS: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// Change which words mean "no value" for optional answers (case doesn't matter)
+///
+/// An empty list means only an empty answer is `None`, for when "-" or "nil"
+/// are real values. Individual prompts can override this with
+/// [`Input::none_words`](crate::Input::none_words).
+pub fn set_none_words(words: &[&str]) {
+    *lock() = Some(words.iter().map(|word| word.to_string()).collect());
+}
+
+// Is this a word that means "no value" for the current prompt?
+pub(crate) fn is_none_word(input: &str) -> bool {
+    let matches = |words: &[String]| words.iter().any(|word| input.eq_ignore_ascii_case(word));
+    if let Some(found) = PROMPT_NONE_WORDS.with_borrow(|words| words.as_deref().map(matches)) {
+        return found;
+    }
+    match &*lock() {
+        Some(words) => matches(words),
+        None => DEFAULT_NONE_WORDS
+            .iter()
+            .any(|word| input.eq_ignore_ascii_case(word)),
+    }
+}
+
+// Runs `parse` with a prompt's own none-words in place of the global ones
+pub(crate) fn with_none_words<R>(words: Option<&[String]>, parse: impl FnOnce() -> R) -> R {
+    let Some(words) = words else {
+        return parse();
+    };
+    let previous = PROMPT_NONE_WORDS.replace(Some(words.to_vec()));
+    let result = parse();
+    PROMPT_NONE_WORDS.set(previous);
+    result
+}
+
+fn lock() -> std::sync::MutexGuard<'static, Option<Vec<String>>> {
+    NONE_WORDS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
 // Empty or "none" becomes None, otherwise parse as T
 impl<T: Parse> Parse for Option<T> {
     fn parse(input: &str) -> Result<Self> {
@@ -60,12 +218,10 @@ impl<T: Parse> Parse for Option<T> {
             return Ok(None);
         }
 
-        match trimmed.to_lowercase().as_str() {
-            "none" | "null" | "nil" | "-" | "skip" => Ok(None),
-            _ => match T::parse(trimmed) {
-                Ok(value) => Ok(Some(value)),
-                Err(e) => Err(e),
-            },
+        if is_none_word(trimmed) {
+            Ok(None)
+        } else {
+            T::parse(trimmed).map(Some)
         }
     }
 
@@ -74,13 +230,20 @@ impl<T: Parse> Parse for Option<T> {
     }
 }
 
-// Generate Parse impls for all numeric types
+// Generate Parse impls for all numeric types. Answers that don't parse as
+// typed get a second chance, loosened the way people actually type numbers.
 macro_rules! impl_numeric {
     ($type:ty, $name:expr) => {
+        impl_numeric!($type, $name, false);
+    };
+    ($type:ty, $name:expr, $decimal:expr) => {
         impl Parse for $type {
             fn parse(input: &str) -> Result<Self> {
-                input
-                    .trim()
+                let trimmed = input.trim();
+                if let Ok(number) = trimmed.parse::<$type>() {
+                    return Ok(number);
+                }
+                loosened(input, $decimal, $name)?
                     .parse::<$type>()
                     .map_err(|_| VelvetIOError::parse_error(input, $name))
             }
@@ -92,6 +255,39 @@ macro_rules! impl_numeric {
     };
 }
 
+// Stray spaces dropped (`1 000`, a keypad's `12 34`), and for decimals a
+// comma read as the decimal point: `2,5` is 2.5. A comma before exactly three
+// digits could be a thousands separator, so `1,000` is only 1.0 when the
+// locale writes decimals with commas, and otherwise asks which was meant.
+fn loosened(input: &str, decimal: bool, name: &str) -> Result<String> {
+    let mut number: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+    if !decimal || number.contains('.') {
+        return Ok(number);
+    }
+    let Some((whole, fraction)) = number.split_once(',') else {
+        return Ok(number);
+    };
+    let digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    if !digits(fraction) {
+        return Ok(number);
+    }
+    if fraction.len() == 3 && !crate::i18n::decimal_comma() {
+        let whole = whole.trim_start_matches(['-', '+']);
+        let mut error = VelvetIOError::parse_error(input, name);
+        error.message = format!(
+            "Is '{}' {}{} or {}.{}? Type it without the comma",
+            input.trim(),
+            whole,
+            fraction,
+            whole,
+            fraction
+        );
+        return Err(error);
+    }
+    number = number.replacen(',', ".", 1);
+    Ok(number)
+}
+
 impl_numeric!(i8, "integer (-128 to 127)");
 impl_numeric!(i16, "integer (-32,768 to 32,767)");
 impl_numeric!(i32, "integer");
@@ -106,8 +302,8 @@ impl_numeric!(u64, "positive integer");
 impl_numeric!(u128, "positive integer");
 impl_numeric!(usize, "positive integer");
 
-impl_numeric!(f32, "decimal number");
-impl_numeric!(f64, "decimal number");
+impl_numeric!(f32, "decimal number", true);
+impl_numeric!(f64, "decimal number", true);
 
 // Smart separator detection: comma, semicolon, pipe, or space
 impl<T: Parse> Parse for Vec<T> {
@@ -127,30 +323,19 @@ impl<T: Parse> Parse for Vec<T> {
             ' '
         };
 
-        let parts: Vec<&str> = if separator == ' ' {
-            trimmed.split_whitespace().collect()
+        // Parsed straight off the split, without collecting the pieces first
+        let parsed: Result<Vec<T>> = if separator == ' ' {
+            trimmed.split_whitespace().map(T::parse).collect()
         } else {
             trimmed
                 .split(separator)
                 .map(|s| s.trim())
                 .filter(|s| !s.is_empty())
+                .map(T::parse)
                 .collect()
         };
 
-        let mut results = Vec::with_capacity(parts.len());
-        for part in parts {
-            match T::parse(part) {
-                Ok(value) => results.push(value),
-                Err(_) => {
-                    return Err(VelvetIOError::parse_error(
-                        input,
-                        &format!("list of {}", T::type_name()),
-                    ));
-                }
-            }
-        }
-
-        Ok(results)
+        parsed.map_err(|_| VelvetIOError::parse_error(input, format!("list of {}", T::type_name())))
     }
 
     fn type_name() -> &'static str {
@@ -176,14 +361,14 @@ impl<T1: Parse, T2: Parse> Parse for (T1, T2) {
         let first = T1::parse(parts[0]).map_err(|_| {
             VelvetIOError::parse_error(
                 input,
-                &format!("pair: {} and {}", T1::type_name(), T2::type_name()),
+                format!("pair: {} and {}", T1::type_name(), T2::type_name()),
             )
         })?;
 
         let second = T2::parse(parts[1]).map_err(|_| {
             VelvetIOError::parse_error(
                 input,
-                &format!("pair: {} and {}", T1::type_name(), T2::type_name()),
+                format!("pair: {} and {}", T1::type_name(), T2::type_name()),
             )
         })?;
 
@@ -0,0 +1,132 @@
+// src/paths.rs
+
+// Picking a file or directory. Typed paths are the most common wrong answer
+// in a wizard, so they're checked before they're accepted, and on a terminal
+// tab completes them like a shell does.
+
+use crate::core::{PromptOptions, ask_loop, give_up};
+use crate::editor::Completer;
+use crate::{AttemptPolicy, MAX_ATTEMPTS, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// What `pick_path` accepts
+///
+/// ```no_run
+/// use velvetio::{PickOptions, pick_path};
+///
+/// let config = pick_path("Config file", PickOptions {
+///     extensions: vec!["toml".into(), "json".into()],
+///     ..PickOptions::default()
+/// });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PickOptions {
+    /// Only directories, no files
+    pub dirs_only: bool,
+    /// Only files with one of these extensions (no dot, any case). Empty
+    /// means any file.
+    pub extensions: Vec<String>,
+    /// Where relative paths start - the working directory when `None`
+    pub start_dir: Option<PathBuf>,
+}
+
+/// Ask for an existing file or directory
+///
+/// Answers must exist and match `options`. On a terminal, tab completes the
+/// name being typed and a second tab lists the choices, so you can walk down
+/// directories without leaving the prompt. `~/` means the home directory.
+/// Relative answers come back joined onto `start_dir`.
+///
+/// Exits the program if input is closed before a valid answer arrives.
+pub fn pick_path(prompt: &str, options: PickOptions) -> PathBuf {
+    pick_loop(prompt, options, AttemptPolicy::unlimited()).unwrap_or_else(|e| give_up(e))
+}
+
+/// `pick_path` that gives up after `MAX_ATTEMPTS` invalid answers
+pub fn try_pick_path(prompt: &str, options: PickOptions) -> Result<PathBuf> {
+    pick_loop(prompt, options, AttemptPolicy::limited(MAX_ATTEMPTS))
+}
+
+fn pick_loop(prompt: &str, options: PickOptions, policy: AttemptPolicy) -> Result<PathBuf> {
+    let error_msg = match (options.dirs_only, options.extensions.as_slice()) {
+        (true, _) => "Enter a directory that exists".to_string(),
+        (false, []) => "Enter a path that exists".to_string(),
+        (false, extensions) => {
+            let dotted: Vec<String> = extensions.iter().map(|e| format!(".{}", e)).collect();
+            format!("Enter a {} file that exists", dotted.join(" or "))
+        }
+    };
+
+    let options = Arc::new(options);
+    let completing = Arc::clone(&options);
+    let prompt_options = PromptOptions {
+        complete: Some(Completer(Arc::new(move |typed| {
+            complete(typed, &completing)
+        }))),
+        ..PromptOptions::with_policy(policy)
+    };
+    let fits = |path: &PathBuf| accepts(&resolve(path, &options), &options);
+    let (path, _) = ask_loop(prompt, None, &fits, &error_msg, &prompt_options)?;
+    Ok(resolve(&path, &options))
+}
+
+// The answer as a real path: `~` expanded, relative paths under start_dir
+fn resolve(path: &Path, options: &PickOptions) -> PathBuf {
+    let path = match (path.strip_prefix("~"), std::env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => path.to_path_buf(),
+    };
+    match &options.start_dir {
+        Some(start) if path.is_relative() => start.join(path),
+        _ => path,
+    }
+}
+
+fn accepts(path: &Path, options: &PickOptions) -> bool {
+    if path.is_dir() {
+        return options.dirs_only || options.extensions.is_empty();
+    }
+    path.is_file() && !options.dirs_only && has_extension(path, &options.extensions)
+}
+
+fn has_extension(path: &Path, extensions: &[String]) -> bool {
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    extensions.is_empty() || extensions.iter().any(|e| e.eq_ignore_ascii_case(extension))
+}
+
+// Entries of the typed path's directory that its last part could become.
+// Directories end in `/` so the next tab carries on inside them.
+pub(crate) fn complete(typed: &str, options: &PickOptions) -> Vec<String> {
+    let cut = typed.rfind('/').map_or(0, |at| at + 1);
+    let (dir, partial) = typed.split_at(cut);
+    let listed = match dir {
+        "" => resolve(Path::new("."), options),
+        dir => resolve(Path::new(dir), options),
+    };
+    let Ok(entries) = fs::read_dir(listed) else {
+        return Vec::new();
+    };
+
+    let mut candidates: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            // Hidden entries only when asked for with a leading dot
+            if !name.starts_with(partial) || (name.starts_with('.') && !partial.starts_with('.')) {
+                return None;
+            }
+            let path = entry.path();
+            match path.is_dir() {
+                true => Some(format!("{}{}/", dir, name)),
+                false if !options.dirs_only && has_extension(&path, &options.extensions) => {
+                    Some(format!("{}{}", dir, name))
+                }
+                false => None,
+            }
+        })
+        .collect();
+    candidates.sort();
+    candidates
+}
@@ -0,0 +1,77 @@
+// src/pattern.rs
+
+// Regex answers, compiled the moment they're typed. A bad pattern is pointed
+// at with a caret while the user is still here to fix it, instead of failing
+// later when the tool first tries to match with it.
+
+use crate::core::{PromptOptions, ask_loop, give_up};
+use crate::{AttemptPolicy, MAX_ATTEMPTS, Parse, Result, VelvetIOError, layout};
+use regex::Regex;
+
+impl Parse for Regex {
+    fn parse(input: &str) -> Result<Self> {
+        // regex-syntax says where the problem is; regex's own error only
+        // has it baked into a multi-line message
+        if let Err(error) = regex_syntax::Parser::new().parse(input) {
+            let (kind, offset) = match &error {
+                regex_syntax::Error::Parse(e) => (e.kind().to_string(), e.span().start.offset),
+                regex_syntax::Error::Translate(e) => (e.kind().to_string(), e.span().start.offset),
+                other => (other.to_string(), 0),
+            };
+            return Err(VelvetIOError::validation_error(
+                input,
+                pointed(input, offset, &kind),
+            ));
+        }
+
+        // Parses but can still be too big to compile
+        Regex::new(input).map_err(|e| VelvetIOError::validation_error(input, e.to_string()))
+    }
+
+    fn type_name() -> &'static str {
+        "regex"
+    }
+}
+
+/// Ask for a regex and return it compiled
+///
+/// Patterns that don't compile are rejected on the spot, with a caret under
+/// the problem:
+///
+/// ```text
+/// Pattern: (abc
+/// ❌ unclosed group
+///    (abc
+///    ^
+/// ```
+///
+/// Exits the program if input is closed before a valid answer arrives.
+pub fn ask_regex(prompt: &str) -> Regex {
+    regex_loop(prompt, &PromptOptions::default()).unwrap_or_else(|e| give_up(e))
+}
+
+/// `ask_regex` that gives up after `MAX_ATTEMPTS` invalid answers
+pub fn try_ask_regex(prompt: &str) -> Result<Regex> {
+    let options = PromptOptions::with_policy(AttemptPolicy::limited(MAX_ATTEMPTS));
+    regex_loop(prompt, &options)
+}
+
+fn regex_loop(prompt: &str, options: &PromptOptions) -> Result<Regex> {
+    ask_loop(prompt, None, &|_: &Regex| true, "", options).map(|(regex, _)| regex)
+}
+
+// The message, then the line of the pattern with the problem and a caret
+// under the character at `offset`
+fn pointed(pattern: &str, offset: usize, message: &str) -> String {
+    let line_start = pattern[..offset].rfind('\n').map_or(0, |at| at + 1);
+    let line_end = pattern[offset..]
+        .find('\n')
+        .map_or(pattern.len(), |at| offset + at);
+    let column = layout::text_width(&pattern[line_start..offset]);
+    format!(
+        "{}\n   {}\n   {}^",
+        message,
+        &pattern[line_start..line_end],
+        " ".repeat(column)
+    )
+}
@@ -0,0 +1,111 @@
+// src/phone.rs
+
+// Phone numbers in E.164 form (+15551234567). We only check the shape - a
+// country code and 8 to 15 digits in all - not whether the number exists,
+// which would need per-country numbering plans.
+
+use crate::{Parse, Result, VelvetIOError};
+use std::fmt;
+use std::sync::Mutex;
+
+// Country code for numbers typed without one, like "(555) 123-4567"
+static DEFAULT_COUNTRY: Mutex<Option<String>> = Mutex::new(None);
+
+/// A phone number normalized to E.164, like `+15551234567`
+///
+/// Spaces, dashes, dots and parentheses are ignored, and `00` works in place
+/// of `+`. Numbers without a country code need [`set_default_country_code`].
+///
+/// ```
+/// use velvetio::{Parse, PhoneNumber};
+///
+/// let phone = PhoneNumber::parse("+44 20 7946 0958").unwrap();
+/// assert_eq!(phone.as_str(), "+442079460958");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct PhoneNumber {
+    e164: String,
+}
+
+impl PhoneNumber {
+    pub fn as_str(&self) -> &str {
+        &self.e164
+    }
+}
+
+impl fmt::Display for PhoneNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.e164)
+    }
+}
+
+/// Country code used for numbers typed without one (`"1"` or `"+1"`)
+///
+/// A leading trunk `0` is dropped, so with `"44"`, "020 7946 0958" becomes
+/// +442079460958.
+pub fn set_default_country_code(code: &str) {
+    let code = code.trim().trim_start_matches('+').to_string();
+    *lock() = Some(code).filter(|code| !code.is_empty());
+}
+
+impl Parse for PhoneNumber {
+    fn parse(input: &str) -> Result<Self> {
+        let trimmed = input.trim();
+        if trimmed
+            .chars()
+            .any(|c| !(c.is_ascii_digit() || matches!(c, '+' | ' ' | '-' | '.' | '(' | ')')))
+            || trimmed.rfind('+').is_some_and(|at| at > 0)
+        {
+            return Err(VelvetIOError::parse_error(input, Self::type_name()));
+        }
+
+        let digits: String = trimmed.chars().filter(|c| c.is_ascii_digit()).collect();
+        let international = match (trimmed.starts_with('+'), digits.strip_prefix("00")) {
+            (true, _) => Some(digits.clone()),
+            (false, Some(rest)) => Some(rest.to_string()),
+            (false, None) => None,
+        };
+
+        let full = match international {
+            Some(full) => full,
+            None => match lock().as_deref() {
+                Some(country) => format!("{}{}", country, digits.trim_start_matches('0')),
+                None => {
+                    return Err(VelvetIOError::validation_error(
+                        input,
+                        "Include the country code, like +1 555 123 4567",
+                    ));
+                }
+            },
+        };
+
+        if !(8..=15).contains(&full.len()) || full.starts_with('0') {
+            return Err(VelvetIOError::validation_error(
+                input,
+                "Phone numbers have 8 to 15 digits, country code included",
+            ));
+        }
+        Ok(PhoneNumber {
+            e164: format!("+{}", full),
+        })
+    }
+
+    fn type_name() -> &'static str {
+        "phone number"
+    }
+}
+
+/// String is a phone number `PhoneNumber` would accept
+// Takes &String so it can be passed straight to `validate:` for String prompts
+#[allow(clippy::ptr_arg)]
+pub fn is_phone_number(s: &String) -> bool {
+    PhoneNumber::parse(s).is_ok()
+}
+
+fn lock() -> std::sync::MutexGuard<'static, Option<String>> {
+    DEFAULT_COUNTRY
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
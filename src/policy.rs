@@ -0,0 +1,96 @@
+// src/policy.rs
+
+use std::time::Duration;
+
+/// How many invalid answers a prompt accepts, and how fast
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use velvetio::AttemptPolicy;
+///
+/// // Three tries, one second apart, doubling each time
+/// let policy = AttemptPolicy::limited(3)
+///     .delay(Duration::from_secs(1))
+///     .backoff(2.0)
+///     .lockout_message("Too many failed attempts, try again later");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttemptPolicy {
+    max_attempts: Option<u32>,
+    delay: Duration,
+    backoff: f64,
+    lockout_message: Option<String>,
+}
+
+impl AttemptPolicy {
+    /// Keep asking forever, no delay - what `ask` does
+    pub fn unlimited() -> Self {
+        Self {
+            max_attempts: None,
+            delay: Duration::ZERO,
+            backoff: 1.0,
+            lockout_message: None,
+        }
+    }
+
+    /// Give up after `max_attempts` invalid answers
+    pub fn limited(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: Some(max_attempts.max(1)),
+            ..Self::unlimited()
+        }
+    }
+
+    /// Wait this long after each invalid answer
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Multiply the delay by `factor` after every invalid answer
+    pub fn backoff(mut self, factor: f64) -> Self {
+        self.backoff = if factor.is_finite() && factor >= 1.0 {
+            factor
+        } else {
+            1.0
+        };
+        self
+    }
+
+    /// Message shown instead of the generic one when attempts run out
+    pub fn lockout_message(mut self, message: &str) -> Self {
+        self.lockout_message = Some(message.to_string());
+        self
+    }
+
+    // The same policy, giving up after `max_attempts` instead
+    pub(crate) fn capped(self, max_attempts: u32) -> Self {
+        Self {
+            max_attempts: Some(max_attempts.max(1)),
+            ..self
+        }
+    }
+
+    pub fn max_attempts(&self) -> Option<u32> {
+        self.max_attempts
+    }
+
+    pub fn lockout(&self) -> Option<&str> {
+        self.lockout_message.as_deref()
+    }
+
+    /// Pause after the given (1-based) invalid answer
+    pub fn delay_after(&self, attempt: u32) -> Duration {
+        if self.delay.is_zero() {
+            return Duration::ZERO;
+        }
+        let factor = self.backoff.powi(attempt.saturating_sub(1).min(64) as i32);
+        Duration::try_from_secs_f64(self.delay.as_secs_f64() * factor).unwrap_or(Duration::MAX)
+    }
+}
+
+impl Default for AttemptPolicy {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
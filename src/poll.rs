@@ -0,0 +1,169 @@
+// src/poll.rs
+
+// Answers that turn up when they turn up - from a message queue, a GUI
+// bridge, a chat bot - rather than being read from a stream that blocks.
+// A session asks its provider whether an answer is in yet, sleeping between
+// tries, longer each time up to a cap, so an event-driven app can feed
+// prompts without a thread parked in `read_line`. Prompts themselves still
+// print to the terminal, unless the provider shows them itself.
+//
+// Waiting stops at the polling timeout or at a form's or flow's deadline,
+// whichever comes first, with the same `Timeout` error either way.
+
+use crate::{Transport, deadline};
+use std::io;
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::time::{Duration, Instant};
+
+// The least time between polls, so a zero interval doesn't spin
+const SHORTEST: Duration = Duration::from_millis(1);
+
+/// What an [`AnswerProvider`] has when it's polled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Polled {
+    /// The next answer, without a line ending
+    Answer(String),
+    /// Nothing yet, ask again later
+    Waiting,
+    /// No more answers are coming
+    Closed,
+}
+
+/// A source of answers that's polled instead of read
+///
+/// Set one on a [`Session`](crate::Session) with
+/// [`Session::answers`](crate::Session::answers). A `Receiver<String>` is
+/// one already, so anything that can hold the sending end can answer.
+pub trait AnswerProvider: Send {
+    /// Whether an answer is in. Shouldn't block - the session does the
+    /// waiting.
+    fn poll(&mut self) -> io::Result<Polled>;
+
+    /// Prompt text: questions, menus and errors. Printed here unless the
+    /// provider would rather show it itself, next to where answers come from.
+    fn show(&mut self, text: &str, to_stderr: bool) -> io::Result<()> {
+        crate::transport::write_local(text, to_stderr)
+    }
+}
+
+/// Answers sent down a channel. A dropped sender closes it.
+impl AnswerProvider for Receiver<String> {
+    fn poll(&mut self) -> io::Result<Polled> {
+        match self.try_recv() {
+            Ok(answer) => Ok(Polled::Answer(answer)),
+            Err(TryRecvError::Empty) => Ok(Polled::Waiting),
+            Err(TryRecvError::Disconnected) => Ok(Polled::Closed),
+        }
+    }
+}
+
+/// How often an [`AnswerProvider`] is polled, and for how long
+///
+/// ```
+/// use std::time::Duration;
+/// use velvetio::Polling;
+///
+/// // 20ms, 40ms, 80ms... never more than a second apart, for up to a minute
+/// let polling = Polling::every(Duration::from_millis(20))
+///     .backoff(2.0)
+///     .longest(Duration::from_secs(1))
+///     .timeout(Duration::from_secs(60));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polling {
+    interval: Duration,
+    backoff: f64,
+    longest: Duration,
+    timeout: Option<Duration>,
+}
+
+impl Polling {
+    /// Poll this often, for as long as it takes
+    ///
+    /// Intervals under a millisecond are taken as one, so polling never
+    /// spins.
+    pub fn every(interval: Duration) -> Self {
+        let interval = interval.max(SHORTEST);
+        Self {
+            interval,
+            backoff: 1.0,
+            longest: interval,
+            timeout: None,
+        }
+    }
+
+    /// Multiply the wait by `factor` each time nothing's in, up to
+    /// [`longest`](Self::longest)
+    pub fn backoff(mut self, factor: f64) -> Self {
+        self.backoff = if factor.is_finite() && factor >= 1.0 {
+            factor
+        } else {
+            1.0
+        };
+        self
+    }
+
+    /// The most to wait between polls
+    pub fn longest(mut self, longest: Duration) -> Self {
+        self.longest = longest.max(self.interval);
+        self
+    }
+
+    /// Give up on an answer after this long, with a `Timeout` error
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    // The wait after `wait`
+    fn next(&self, wait: Duration) -> Duration {
+        let longer = Duration::try_from_secs_f64(wait.as_secs_f64() * self.backoff);
+        longer.unwrap_or(Duration::MAX).min(self.longest)
+    }
+}
+
+/// 10ms, doubling up to 250ms, with no timeout
+impl Default for Polling {
+    fn default() -> Self {
+        Self::every(Duration::from_millis(10))
+            .backoff(2.0)
+            .longest(Duration::from_millis(250))
+    }
+}
+
+// A provider as a session's transport, answers coming from polling
+pub(crate) struct PollingTransport<P> {
+    pub(crate) provider: P,
+    pub(crate) polling: Polling,
+}
+
+impl<P: AnswerProvider> Transport for PollingTransport<P> {
+    fn write(&mut self, text: &str, to_stderr: bool) -> io::Result<()> {
+        self.provider.show(text, to_stderr)
+    }
+
+    fn read_line(&mut self, _secret: bool) -> io::Result<Option<String>> {
+        let started = Instant::now();
+        let mut wait = self.polling.interval;
+        loop {
+            match self.provider.poll()? {
+                Polled::Answer(answer) => return Ok(Some(answer)),
+                Polled::Closed => return Ok(None),
+                Polled::Waiting => {}
+            }
+            let timeout = self
+                .polling
+                .timeout
+                .map(|timeout| timeout.saturating_sub(started.elapsed()));
+            let left = match (timeout, deadline::remaining()) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
+            if left == Some(Duration::ZERO) {
+                return Err(io::ErrorKind::TimedOut.into());
+            }
+            std::thread::sleep(left.map_or(wait, |left| wait.min(left)));
+            wait = self.polling.next(wait);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+// src/progress.rs
+
+use crate::script::outln;
+use crate::theme::{Theme, theme};
+
+/// Step counter for wizards that mix prompts with work
+///
+/// ```no_run
+/// use velvetio::Progress;
+///
+/// let mut progress = Progress::steps(3);
+/// progress.advance("Creating project");
+/// progress.advance("Installing dependencies");
+/// progress.advance("Writing config");
+/// progress.finish("Project ready");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Progress {
+    pub(crate) total: usize,
+    pub(crate) current: usize,
+}
+
+impl Progress {
+    /// Track `total` steps
+    pub fn steps(total: usize) -> Self {
+        Self {
+            total: total.max(1),
+            current: 0,
+        }
+    }
+
+    /// Move on to the next step and print it
+    ///
+    /// Advancing past the last step just repeats the final count.
+    pub fn advance(&mut self, label: &str) {
+        self.current = (self.current + 1).min(self.total);
+        outln!("{}", self.render(label, &theme()));
+    }
+
+    /// Print a closing line with the theme's success prefix
+    pub fn finish(&mut self, message: &str) {
+        self.current = self.total;
+        outln!("{} {}", theme().success_prefix, message);
+    }
+
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    // `[2/5] ████████░░░░░░░░░░░░ Installing dependencies`
+    pub(crate) fn render(&self, label: &str, theme: &Theme) -> String {
+        if theme.accessible {
+            return format!("Step {} of {}: {}", self.current, self.total, label);
+        }
+
+        let counter = theme.accent(&format!("[{}/{}]", self.current, self.total));
+        if theme.bar_width == 0 {
+            return format!("{} {}", counter, label);
+        }
+
+        let filled = theme.bar_width * self.current / self.total;
+        let bar: String = std::iter::repeat_n(theme.bar_filled, filled)
+            .chain(std::iter::repeat_n(
+                theme.bar_empty,
+                theme.bar_width - filled,
+            ))
+            .collect();
+        format!("{} {} {}", counter, bar, label)
+    }
+}
@@ -0,0 +1,239 @@
+// src/protocol.rs
+
+// Prompts for programs instead of people. In protocol mode every prompt is a
+// JSON object on its own line of stdout, and every answer a JSON line on
+// stdin, so a GUI, a web page or an editor extension can run a wizard
+// without pretending to be a terminal. Everything else the wizard prints
+// comes out as JSON too, so stdout stays one object per line.
+//
+// Written for stdout:
+//
+//   {"type":"prompt","id":1,"text":"Port","default":"8080"}
+//   {"type":"prompt","id":2,"text":"Tier","choices":["free","pro"]}
+//   {"type":"prompt","id":3,"text":"Features","choices":["a","b"],"multiple":true}
+//   {"type":"prompt","id":4,"text":"Password","secret":true}
+//   {"type":"output","stream":"stdout","text":"Project ready\n"}
+//   {"type":"error","kind":"validation","message":"...","input":"x","prompt":"Port","attempt":1}
+//
+// Read from stdin, one per prompt: `"8080"` or `{"answer":"8080"}` (an `id`
+// alongside is fine), or `{"cancel":true}`. Menus take the same answers a
+// person would type: a number, a label, or `1,3` for several.
+//
+// It's on after `set_json_protocol(true)`, or when VELVETIO_PROTOCOL is
+// `json`. Protocol mode counts as headless, so the terminal is never touched.
+
+use crate::cache::{push_json_string, read_json_string, skip_whitespace};
+use crate::core::read_stdin;
+use crate::{Result, VelvetIOError};
+use std::io::{self, Write};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static ENABLED: Mutex<Option<bool>> = Mutex::new(None);
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Turn JSON lines protocol mode on or off
+///
+/// ```no_run
+/// velvetio::set_json_protocol(true);
+/// // Prints {"type":"prompt","id":1,"text":"Project name"} and reads
+/// // a line like "my-app" or {"answer":"my-app"}
+/// let name: String = velvetio::ask("Project name");
+/// ```
+pub fn set_json_protocol(enabled: bool) {
+    *lock() = Some(enabled);
+}
+
+/// Whether prompts are talking JSON lines instead of drawing on a terminal
+pub fn is_json_protocol() -> bool {
+    *lock().get_or_insert_with(|| {
+        std::env::var("VELVETIO_PROTOCOL").is_ok_and(|value| value.eq_ignore_ascii_case("json"))
+    })
+}
+
+// What a prompt event says besides its text
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Details {
+    pub(crate) default: Option<String>,
+    pub(crate) choices: Vec<String>,
+    pub(crate) multiple: bool,
+}
+
+// Sends the prompt and waits for its answer. Lines that aren't an answer are
+// answered with a protocol error and skipped.
+pub(crate) fn ask(prompt: &str, secret: bool, details: Option<&Details>) -> Result<String> {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let mut event = format!("{{\"type\":\"prompt\",\"id\":{},\"text\":", id);
+    push_json_string(&mut event, &crate::markup::strip(prompt));
+    if let Some(details) = details {
+        if let Some(default) = &details.default {
+            event.push_str(",\"default\":");
+            push_json_string(&mut event, default);
+        }
+        if !details.choices.is_empty() {
+            event.push_str(",\"choices\":[");
+            for (i, choice) in details.choices.iter().enumerate() {
+                if i > 0 {
+                    event.push(',');
+                }
+                push_json_string(&mut event, choice);
+            }
+            event.push(']');
+        }
+        if details.multiple {
+            event.push_str(",\"multiple\":true");
+        }
+    }
+    if secret {
+        event.push_str(",\"secret\":true");
+    }
+    event.push('}');
+    send(&event);
+
+    loop {
+        let Some(line) = read_stdin(false, str::to_string)? else {
+            return Err(VelvetIOError::eof());
+        };
+        match reply(&line) {
+            Some(Reply::Answer(answer)) => return Ok(answer),
+            Some(Reply::Cancel) => return Err(VelvetIOError::cancelled()),
+            None => {
+                let mut event =
+                    String::from("{\"type\":\"error\",\"kind\":\"protocol\",\"message\":");
+                push_json_string(
+                    &mut event,
+                    "Expected a JSON string, {\"answer\": \"...\"} or {\"cancel\": true}",
+                );
+                event.push_str(",\"input\":");
+                push_json_string(&mut event, line.trim_end());
+                event.push('}');
+                send(&event);
+            }
+        }
+    }
+}
+
+// An error event for a rejected answer or a prompt that failed
+pub(crate) fn error(error: &VelvetIOError) {
+    let mut event = String::from("{\"type\":\"error\",\"kind\":");
+    push_json_string(&mut event, &kind_name(error.kind));
+    event.push_str(",\"message\":");
+    push_json_string(&mut event, &error.message);
+    event.push_str(",\"input\":");
+    push_json_string(&mut event, &error.input);
+    if let Some(key) = error.key() {
+        event.push_str(",\"key\":");
+        push_json_string(&mut event, key);
+    }
+    if let Some(prompt) = error.prompt() {
+        event.push_str(",\"prompt\":");
+        push_json_string(&mut event, prompt);
+    }
+    if let Some(attempt) = error.attempt() {
+        event.push_str(&format!(",\"attempt\":{}", attempt));
+    }
+    event.push('}');
+    send(&event);
+}
+
+// `TooManyAttempts` as `too_many_attempts`, like the serde feature spells it
+fn kind_name(kind: crate::ErrorKind) -> String {
+    let mut name = String::new();
+    for c in format!("{:?}", kind).chars() {
+        if c.is_ascii_uppercase() && !name.is_empty() {
+            name.push('_');
+        }
+        name.push(c.to_ascii_lowercase());
+    }
+    name
+}
+
+// Anything else the wizard prints
+pub(crate) fn output(to_stderr: bool, text: &str) {
+    let stream = match to_stderr {
+        true => "stderr",
+        false => "stdout",
+    };
+    let mut event = format!("{{\"type\":\"output\",\"stream\":\"{}\",\"text\":", stream);
+    push_json_string(&mut event, text);
+    event.push('}');
+    send(&event);
+}
+
+fn send(event: &str) {
+    let mut out = io::stdout().lock();
+    let _ = writeln!(out, "{}", event);
+    let _ = out.flush();
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Reply {
+    Answer(String),
+    Cancel,
+}
+
+// `"text"`, or an object with `answer` or `cancel`. Other keys (like `id`)
+// are skipped.
+pub(crate) fn reply(line: &str) -> Option<Reply> {
+    let mut chars = line.trim().chars().peekable();
+    if chars.peek() == Some(&'"') {
+        let answer = read_json_string(&mut chars)?;
+        return chars.next().is_none().then_some(Reply::Answer(answer));
+    }
+
+    if chars.next()? != '{' {
+        return None;
+    }
+    let mut answer = None;
+    let mut cancel = false;
+    loop {
+        skip_whitespace(&mut chars);
+        match chars.peek()? {
+            '}' => break,
+            ',' => {
+                chars.next();
+                continue;
+            }
+            _ => {}
+        }
+
+        let key = read_json_string(&mut chars)?;
+        skip_whitespace(&mut chars);
+        if chars.next()? != ':' {
+            return None;
+        }
+        skip_whitespace(&mut chars);
+        if chars.peek() == Some(&'"') {
+            let value = read_json_string(&mut chars)?;
+            if key == "answer" {
+                answer = Some(value);
+            }
+            continue;
+        }
+        // Numbers, true, false and null
+        let mut word = String::new();
+        while let Some(&c) = chars
+            .peek()
+            .filter(|c| c.is_ascii_alphanumeric() || "+-.".contains(**c))
+        {
+            word.push(c);
+            chars.next();
+        }
+        match (key.as_str(), word.as_str()) {
+            ("cancel", "true") => cancel = true,
+            (_, "") => return None,
+            _ => {}
+        }
+    }
+
+    match (cancel, answer) {
+        (true, _) => Some(Reply::Cancel),
+        (false, answer) => answer.map(Reply::Answer),
+    }
+}
+
+fn lock() -> std::sync::MutexGuard<'static, Option<bool>> {
+    ENABLED
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
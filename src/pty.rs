@@ -0,0 +1,316 @@
+// src/pty.rs
+
+// End-to-end tests under a real pseudo-terminal. Like the rest of the crate
+// we don't bind to libc for it: the program runs under `script`, which owns
+// the pty, and we talk to it through script's stdin and stdout. What comes
+// back goes through a small screen model that understands the escape
+// sequences prompts use, so assertions see what a user would.
+
+use std::io::{self, Read, Write};
+use std::process::{Child, ChildStdin, Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long `expect` waits for text to show up
+const EXPECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A key to send, spelled the way the terminal sends it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Enter,
+    Tab,
+    Backspace,
+    Escape,
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    /// Ctrl plus a letter, like `Ctrl('c')`
+    Ctrl(char),
+}
+
+impl Key {
+    fn bytes(self) -> Vec<u8> {
+        match self {
+            Key::Enter => b"\r".to_vec(),
+            Key::Tab => b"\t".to_vec(),
+            Key::Backspace => b"\x7f".to_vec(),
+            Key::Escape => b"\x1b".to_vec(),
+            Key::Up => b"\x1b[A".to_vec(),
+            Key::Down => b"\x1b[B".to_vec(),
+            Key::Right => b"\x1b[C".to_vec(),
+            Key::Left => b"\x1b[D".to_vec(),
+            Key::Home => b"\x1b[H".to_vec(),
+            Key::End => b"\x1b[F".to_vec(),
+            Key::Ctrl(c) => vec![(c.to_ascii_lowercase() as u8) & 0x1f],
+        }
+    }
+}
+
+/// A program running under a pseudo-terminal, with its screen
+///
+/// Needs `script` (util-linux on Linux, built in on macOS). The terminal is
+/// 80x24. Integration tests can run the crate's own binaries with
+/// `env!("CARGO_BIN_EXE_<name>")`.
+///
+/// ```no_run
+/// use velvetio::{Key, PtySession};
+///
+/// # macro_rules! env { ($name:literal) => { "target/debug/setup" } }
+/// let mut app = PtySession::spawn(env!("CARGO_BIN_EXE_setup"), &[]).unwrap();
+/// app.expect("Project name");
+/// app.send_line("demo");
+/// app.expect("Tier");
+/// app.send_key(Key::Down);
+/// app.send_key(Key::Enter);
+/// assert!(app.wait().success());
+/// ```
+pub struct PtySession {
+    child: Child,
+    input: Option<ChildStdin>,
+    output: Arc<Mutex<Vec<u8>>>,
+}
+
+impl PtySession {
+    /// Start `program` with `args` on a fresh 80x24 terminal
+    pub fn spawn(program: &str, args: &[&str]) -> io::Result<Self> {
+        let mut line = String::from("stty cols 80 rows 24 2>/dev/null; exec");
+        for word in std::iter::once(program).chain(args.iter().copied()) {
+            line.push(' ');
+            line.push_str(&shell_quote(word));
+        }
+
+        let mut child = script_command(&line)
+            .env("TERM", "xterm")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        // Read everything as it arrives, so the program never blocks on a
+        // full pipe while a test is waiting on it
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let mut stdout = child.stdout.take().expect("stdout is piped");
+        let collected = Arc::clone(&output);
+        thread::spawn(move || {
+            let mut buffer = [0; 4096];
+            while let Ok(read @ 1..) = stdout.read(&mut buffer) {
+                lock(&collected).extend_from_slice(&buffer[..read]);
+            }
+        });
+
+        Ok(Self {
+            input: child.stdin.take(),
+            child,
+            output,
+        })
+    }
+
+    /// Type `text` as it is
+    pub fn send(&mut self, text: &str) {
+        self.write(text.as_bytes());
+    }
+
+    /// Type `text`, then enter
+    pub fn send_line(&mut self, text: &str) {
+        self.send(text);
+        self.send_key(Key::Enter);
+    }
+
+    pub fn send_key(&mut self, key: Key) {
+        self.write(&key.bytes());
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        if let Some(input) = &mut self.input {
+            // A program that's gone shows up in the screen and exit status,
+            // which say more than a broken pipe would
+            let _ = input.write_all(bytes).and_then(|_| input.flush());
+        }
+    }
+
+    /// The screen right now, one line per row, trailing spaces trimmed
+    ///
+    /// Rows that scrolled off the top are kept, so nothing printed is lost.
+    pub fn screen(&self) -> String {
+        Screen::render(&lock(&self.output))
+    }
+
+    /// Wait for `text` to be on the screen, and panic with the screen if it
+    /// doesn't show up within 5 seconds
+    #[track_caller]
+    pub fn expect(&mut self, text: &str) {
+        if let Err(screen) = self.wait_for(text, EXPECT_TIMEOUT) {
+            panic!(
+                "{:?} never showed up on the screen:\n{}",
+                text,
+                quoted(&screen)
+            );
+        }
+    }
+
+    /// Wait up to `timeout` for `text` to be on the screen. The error holds
+    /// the screen as it was.
+    pub fn wait_for(&mut self, text: &str, timeout: Duration) -> Result<(), String> {
+        let started = Instant::now();
+        loop {
+            let screen = self.screen();
+            if screen.contains(text) {
+                return Ok(());
+            }
+            if started.elapsed() >= timeout {
+                return Err(screen);
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    /// Close input and wait for the program to finish
+    pub fn wait(mut self) -> ExitStatus {
+        drop(self.input.take());
+        self.child.wait().expect("the program was started")
+    }
+}
+
+impl Drop for PtySession {
+    // A test that panicked halfway shouldn't leave the program running
+    fn drop(&mut self) {
+        if let Ok(None) = self.child.try_wait() {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+}
+
+// util-linux and BSD spell it differently
+#[cfg(target_os = "macos")]
+fn script_command(line: &str) -> Command {
+    let mut command = Command::new("script");
+    command.args(["-q", "/dev/null", "sh", "-c", line]);
+    command
+}
+
+#[cfg(not(target_os = "macos"))]
+fn script_command(line: &str) -> Command {
+    let mut command = Command::new("script");
+    command.args(["-qfec", line, "/dev/null"]);
+    command
+}
+
+fn shell_quote(word: &str) -> String {
+    format!("'{}'", word.replace('\'', r"'\''"))
+}
+
+fn lock(output: &Mutex<Vec<u8>>) -> std::sync::MutexGuard<'_, Vec<u8>> {
+    output
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn quoted(screen: &str) -> String {
+    screen
+        .lines()
+        .map(|line| format!("  | {}\n", line))
+        .collect()
+}
+
+// Just enough of a terminal for prompts: text, line endings, cursor
+// movement and erasing. Colors and anything else are skipped.
+#[derive(Debug, Default)]
+pub(crate) struct Screen {
+    rows: Vec<Vec<char>>,
+    row: usize,
+    col: usize,
+}
+
+impl Screen {
+    const WIDTH: usize = 80;
+
+    pub(crate) fn render(output: &[u8]) -> String {
+        let mut screen = Screen::default();
+        let text = String::from_utf8_lossy(output);
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\r' => screen.col = 0,
+                '\n' => screen.row += 1,
+                '\x08' => screen.col = screen.col.saturating_sub(1),
+                '\x1b' if chars.next_if_eq(&'[').is_some() => {
+                    let mut params = String::new();
+                    while let Some(&p) = chars.peek() {
+                        if ('@'..='~').contains(&p) {
+                            break;
+                        }
+                        params.push(p);
+                        chars.next();
+                    }
+                    if let Some(command) = chars.next() {
+                        screen.csi(&params, command);
+                    }
+                }
+                // Other escapes are two characters
+                '\x1b' => {
+                    chars.next();
+                }
+                c if c.is_control() => {}
+                c => screen.put(c),
+            }
+        }
+
+        let lines: Vec<String> = screen
+            .rows
+            .iter()
+            .map(|row| row.iter().collect::<String>().trim_end().to_string())
+            .collect();
+        let mut screen = lines.join("\n");
+        screen.truncate(screen.trim_end().len());
+        screen
+    }
+
+    fn line(&mut self) -> &mut Vec<char> {
+        if self.rows.len() <= self.row {
+            self.rows.resize(self.row + 1, Vec::new());
+        }
+        &mut self.rows[self.row]
+    }
+
+    fn put(&mut self, c: char) {
+        if self.col >= Self::WIDTH {
+            self.row += 1;
+            self.col = 0;
+        }
+        let col = self.col;
+        let line = self.line();
+        if line.len() <= col {
+            line.resize(col + 1, ' ');
+        }
+        line[col] = c;
+        self.col += 1;
+    }
+
+    fn csi(&mut self, params: &str, command: char) {
+        let n = params.parse::<usize>().unwrap_or(1).max(1);
+        let col = self.col;
+        match command {
+            'A' => self.row = self.row.saturating_sub(n),
+            'B' => self.row += n,
+            'C' => self.col = (self.col + n).min(Self::WIDTH),
+            'D' => self.col = self.col.saturating_sub(n),
+            'G' => self.col = n - 1,
+            'K' => match params {
+                // To the end of the line, the start, or all of it
+                "" | "0" => self.line().truncate(col),
+                "1" => self.line().iter_mut().take(col + 1).for_each(|c| *c = ' '),
+                _ => self.line().clear(),
+            },
+            'J' if params.is_empty() || params == "0" => {
+                self.line().truncate(col);
+                self.rows.truncate(self.row + 1);
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,140 @@
+// src/redact.rs
+
+// Answers that must never be written down. A prompt is sensitive when its
+// key or text matches one of the patterns (passwords, tokens and the like by
+// default), when it was tagged with `.sensitive()`, or when its input is
+// secret. Everything that records answers checks here: transcripts and
+// snapshots, echoed headless answers, the answer cache, error messages (and
+// so JSON errors and protocol events), summaries and dry runs. They all show
+// `***` instead of the value.
+
+use std::cell::Cell;
+use std::sync::Mutex;
+
+/// What a sensitive answer is written as
+pub const REDACTED: &str = "***";
+
+/// Keys and prompts that count as sensitive unless
+/// [`set_sensitive_patterns`] says otherwise
+pub const DEFAULT_SENSITIVE_PATTERNS: &[&str] = &[
+    "*password*",
+    "*passphrase*",
+    "*secret*",
+    "*token*",
+    "*api_key*",
+    "*private_key*",
+];
+
+// `None` until set, meaning the defaults
+static PATTERNS: Mutex<Option<Vec<String>>> = Mutex::new(None);
+
+thread_local! {
+    // Set while a tagged, secret or sensitive prompt runs
+    static HIDING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Replace the patterns that make a key or prompt sensitive
+///
+/// `*` matches anything, and matching ignores case, with spaces and dashes
+/// read as underscores - `*api_key*` covers "API key" and `api-key`. Pass
+/// [`DEFAULT_SENSITIVE_PATTERNS`] along to keep the built-in ones.
+///
+/// ```
+/// use velvetio::{DEFAULT_SENSITIVE_PATTERNS, is_sensitive, set_sensitive_patterns};
+///
+/// let mut patterns = DEFAULT_SENSITIVE_PATTERNS.to_vec();
+/// patterns.push("*ssn*");
+/// set_sensitive_patterns(&patterns);
+/// assert!(is_sensitive("Customer SSN"));
+/// assert!(is_sensitive("GitHub token"));
+/// ```
+pub fn set_sensitive_patterns(patterns: &[&str]) {
+    *lock() = Some(patterns.iter().map(|pattern| normalize(pattern)).collect());
+}
+
+/// Whether a key or prompt matches a sensitive pattern
+pub fn is_sensitive(name: &str) -> bool {
+    // Asked on every prompt, so it doesn't allocate for the usual one
+    let name = crate::markup::render(name, false);
+    let name = name.trim().as_bytes();
+    match lock().as_ref() {
+        Some(patterns) => patterns.iter().any(|pattern| glob(pattern, name)),
+        None => DEFAULT_SENSITIVE_PATTERNS
+            .iter()
+            .any(|pattern| glob(pattern, name)),
+    }
+}
+
+// Whether an answer to `name` has to be kept out of the record, counting
+// the prompt that's running
+pub(crate) fn hidden(name: &str) -> bool {
+    HIDING.get() || is_sensitive(name)
+}
+
+// Whether a sensitive prompt is running, for lines read without one
+pub(crate) fn hiding() -> bool {
+    HIDING.get()
+}
+
+// Everything asked until this is dropped counts as sensitive, when `on`
+pub(crate) struct Scope {
+    outer: bool,
+}
+
+pub(crate) fn scope(on: bool) -> Scope {
+    let outer = HIDING.get();
+    HIDING.set(outer || on);
+    Scope { outer }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        HIDING.set(self.outer);
+    }
+}
+
+fn normalize(name: &str) -> String {
+    name.trim().bytes().map(fold).map(char::from).collect()
+}
+
+// Case and separators don't count
+fn fold(byte: u8) -> u8 {
+    match byte {
+        b' ' | b'-' => b'_',
+        byte => byte.to_ascii_lowercase(),
+    }
+}
+
+fn same(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(&a, &b)| fold(a) == fold(b))
+}
+
+// `*` for any run of characters, everything else literal. `name` is folded
+// as it's compared, `pattern` already is.
+fn glob(pattern: &str, name: &[u8]) -> bool {
+    let Some((head, last)) = pattern.rsplit_once('*') else {
+        return same(name, pattern.as_bytes());
+    };
+    let mut parts = head.split('*').map(str::as_bytes);
+    let first = parts.next().unwrap_or_default();
+    if name.len() < first.len() || !same(&name[..first.len()], first) {
+        return false;
+    }
+    let mut rest = &name[first.len()..];
+    for part in parts {
+        let found = (0..=rest.len().saturating_sub(part.len()))
+            .find(|&at| rest.len() >= at + part.len() && same(&rest[at..at + part.len()], part));
+        match found {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    let last = last.as_bytes();
+    rest.len() >= last.len() && same(&rest[rest.len() - last.len()..], last)
+}
+
+fn lock() -> std::sync::MutexGuard<'static, Option<Vec<String>>> {
+    PATTERNS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -0,0 +1,170 @@
+// src/rules.rs
+
+// Dependencies and conflicts between multi_select options, for feature
+// pickers where some options need or rule out others. Options are named by
+// their labels, so the rules read like the menu does.
+
+use crate::core::{give_up, listed_order, multi_select_inner, out_of_attempts, report, warn};
+use crate::{AttemptPolicy, MAX_ATTEMPTS, Result, VelvetIOError};
+use std::fmt::Display;
+
+/// What `multi_select_with_rules` options need or rule out, by label
+///
+/// ```no_run
+/// use velvetio::{SelectRules, multi_select_with_rules};
+///
+/// let rules = SelectRules::new()
+///     .requires("TLS", "OpenSSL")
+///     .conflicts("SQLite", "Postgres-only mode");
+/// let features = multi_select_with_rules(
+///     "Features",
+///     &["TLS", "OpenSSL", "SQLite", "Postgres-only mode"],
+///     &rules,
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SelectRules {
+    requires: Vec<(String, String)>,
+    conflicts: Vec<(String, String)>,
+}
+
+impl SelectRules {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Picking `option` picks `needed` too
+    pub fn requires(mut self, option: &str, needed: &str) -> Self {
+        self.requires.push((option.to_string(), needed.to_string()));
+        self
+    }
+
+    /// `a` and `b` can't both be picked
+    pub fn conflicts(mut self, a: &str, b: &str) -> Self {
+        self.conflicts.push((a.to_string(), b.to_string()));
+        self
+    }
+
+    // The picks plus everything they need, with a note for each option that
+    // was added. A conflict in the result is the error.
+    pub(crate) fn resolve(
+        &self,
+        picked: &[String],
+    ) -> std::result::Result<(Vec<String>, Vec<String>), String> {
+        let mut all = picked.to_vec();
+        let mut notes = Vec::new();
+        let mut i = 0;
+        // Needs of needs are followed too, each option checked once
+        while i < all.len() {
+            for (option, needed) in &self.requires {
+                if *option == all[i] && !all.contains(needed) {
+                    notes.push(format!("Also picked {} ({} needs it)", needed, option));
+                    all.push(needed.clone());
+                }
+            }
+            i += 1;
+        }
+
+        for (a, b) in &self.conflicts {
+            if all.contains(a) && all.contains(b) {
+                let why = |option: &String| match picked.contains(option) {
+                    true => String::new(),
+                    false => format!(" ({})", self.needed_by(option, &all)),
+                };
+                return Err(format!(
+                    "{}{} and {}{} can't both be picked",
+                    a,
+                    why(a),
+                    b,
+                    why(b)
+                ));
+            }
+        }
+        Ok((all, notes))
+    }
+
+    // "TLS needs it", for an option that was added for another
+    fn needed_by(&self, option: &str, picked: &[String]) -> String {
+        let by: Vec<&str> = self
+            .requires
+            .iter()
+            .filter(|(by, needed)| needed == option && picked.contains(by))
+            .map(|(by, _)| by.as_str())
+            .collect();
+        format!("{} needs it", by.join(" and "))
+    }
+}
+
+/// `multi_select` that follows `rules`: needed options are added (and said
+/// so), and conflicting picks are refused so the user can pick again
+///
+/// Added options come after the picked ones, unless the selection order is
+/// `SelectionOrder::Listed`.
+///
+/// Exits the program if input is closed before a valid selection, or if
+/// `choices` is empty - use `try_multi_select_with_rules` if it might be.
+pub fn multi_select_with_rules<T>(prompt: &str, choices: &[T], rules: &SelectRules) -> Vec<T>
+where
+    T: Display + Clone,
+{
+    rules_loop(prompt, choices, rules, &AttemptPolicy::unlimited()).unwrap_or_else(|e| give_up(e))
+}
+
+/// `multi_select_with_rules` that returns an error instead of exiting or
+/// retrying forever
+pub fn try_multi_select_with_rules<T>(
+    prompt: &str,
+    choices: &[T],
+    rules: &SelectRules,
+) -> Result<Vec<T>>
+where
+    T: Display + Clone,
+{
+    rules_loop(
+        prompt,
+        choices,
+        rules,
+        &AttemptPolicy::limited(MAX_ATTEMPTS),
+    )
+}
+
+fn rules_loop<T>(
+    prompt: &str,
+    choices: &[T],
+    rules: &SelectRules,
+    policy: &AttemptPolicy,
+) -> Result<Vec<T>>
+where
+    T: Display + Clone,
+{
+    let _turn = crate::lock::hold();
+    let labels: Vec<String> = choices.iter().map(|c| c.to_string()).collect();
+
+    let mut attempts = 0;
+    loop {
+        let picked = multi_select_inner(prompt, choices, None, policy, false)?;
+        let picked: Vec<String> = picked.iter().map(|c| c.to_string()).collect();
+
+        match rules.resolve(&picked) {
+            Ok((all, notes)) => {
+                for note in notes {
+                    warn(note);
+                }
+                // Rules can name options that aren't on this menu; those are skipped
+                let mut indices: Vec<usize> = all
+                    .iter()
+                    .filter_map(|label| labels.iter().position(|l| l == label))
+                    .collect();
+                if listed_order() {
+                    indices.sort_unstable();
+                }
+                return Ok(indices.into_iter().map(|i| choices[i].clone()).collect());
+            }
+            Err(message) => {
+                let error = VelvetIOError::validation_error(picked.join(", "), message);
+                report(&error.during(prompt, attempts + 1));
+            }
+        }
+        out_of_attempts(&mut attempts, policy).map_err(|e| e.during(prompt, attempts + 1))?;
+    }
+}
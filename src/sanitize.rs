@@ -0,0 +1,69 @@
+// src/sanitize.rs
+
+// Cleaning up pasted answers. Text copied from web pages, chat apps and
+// terminals drags along escape sequences and invisible characters that make
+// parsing fail for no visible reason.
+
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Strip escape sequences and invisible characters from answers (on by default)
+pub fn set_sanitize_input(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub(crate) fn enabled() -> bool {
+    crate::session::sanitize().unwrap_or_else(|| ENABLED.load(Ordering::Relaxed))
+}
+
+/// `input` without ANSI escape sequences, control characters (except tabs)
+/// and zero-width characters. Borrowed when there was nothing to remove.
+pub(crate) fn sanitize(input: &str) -> Cow<'_, str> {
+    if !input.chars().any(unwanted) {
+        return Cow::Borrowed(input);
+    }
+
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            if !unwanted(c) {
+                out.push(c);
+            }
+            continue;
+        }
+
+        match chars.next() {
+            // CSI: parameters, then one final byte in @..~
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC: ends with BEL or ESC \
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' {
+                        chars.next_if_eq(&'\\');
+                        break;
+                    }
+                }
+            }
+            // Anything else is a two-character sequence
+            _ => {}
+        }
+    }
+    Cow::Owned(out)
+}
+
+fn unwanted(c: char) -> bool {
+    (c.is_control() && c != '\t' && c != '\n')
+        || matches!(c, '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}')
+}
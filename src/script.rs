@@ -0,0 +1,372 @@
+// src/script.rs
+
+// A scripted terminal for testing wizards. Answers come from a list instead
+// of stdin, and what the prompts print goes into a transcript instead of the
+// terminal, laid out the way the user would have seen it. Scripts are per
+// thread, so tests running side by side each get their own.
+//
+// Snapshot runs go further and pin down everything that could differ between
+// machines (theme, width), then mark each line with where it came from.
+
+use crate::core::KeepTyped;
+use crate::{PromptTiming, Session, Theme};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+thread_local! {
+    static SCRIPT: RefCell<Option<Script>> = const { RefCell::new(None) };
+}
+
+struct Script {
+    answers: VecDeque<String>,
+    events: Vec<Event>,
+    // Rendering pinned for snapshots
+    pinned: bool,
+}
+
+// Everything that happened, in order
+enum Event {
+    Out(String),
+    Err(String),
+    Asked {
+        // As printed, suffix included
+        prompt: String,
+        answer: Option<String>,
+        secret: bool,
+    },
+    // A follow-up line with no prompt
+    Line(String),
+}
+
+// Snapshots render menus as a terminal this wide would
+const SNAPSHOT_WIDTH: usize = 80;
+
+/// What came out of [`run_scripted`]
+#[derive(Debug, Clone)]
+pub struct ScriptRun<R> {
+    /// What the closure returned
+    pub value: R,
+    /// Everything the prompts printed, answers included
+    pub transcript: String,
+    /// Answers left over because nothing asked for them
+    pub unused: Vec<String>,
+    /// What each form and flow field took to answer, by key, in the order
+    /// they were asked - see [`FormData::timing`](crate::FormData::timing)
+    pub timings: Vec<(String, PromptTiming)>,
+}
+
+/// Run `run` with `answers` typed in, one per prompt, and collect what it
+/// printed
+///
+/// Runs with the current theme minus colors, without the answer cache, and
+/// without the line editor. When the answers run out, prompts see closed
+/// input. An infallible prompt that would exit the program panics instead,
+/// after printing the transcript so far. Prompts on other threads aren't
+/// scripted.
+///
+/// ```
+/// use velvetio::{ask, run_scripted};
+///
+/// let run = run_scripted(&["Ada", "36"], || {
+///     let name: String = ask("Name");
+///     let age: u32 = ask("Age");
+///     (name, age)
+/// });
+/// assert_eq!(run.value, ("Ada".to_string(), 36));
+/// assert_eq!(run.transcript, "Name: Ada\nAge: 36\n");
+/// ```
+pub fn run_scripted<R>(answers: &[&str], run: impl FnOnce() -> R) -> ScriptRun<R> {
+    let typed = KeepTyped::timings();
+    let (value, script) = run_script(answers, false, run);
+    ScriptRun {
+        value,
+        transcript: transcript(&script.events),
+        unused: script.answers.into(),
+        timings: typed.finish().timings,
+    }
+}
+
+/// Run `run` against scripted answers and render the interaction for a
+/// snapshot test
+///
+/// The output is the same on every machine: the default theme without
+/// colors (ignoring `set_theme`), menus laid out for an 80 column terminal
+/// (ignoring `COLUMNS`), and spinners as their message alone. Each line is
+/// marked with where it came from: `|` for output, `!` for errors and
+/// warnings, `?` for a prompt with the answer after it. Hidden answers show
+/// as `(hidden)`, closed input as `(end of input)`, and answers nothing
+/// asked for are listed at the end.
+///
+/// It's a plain string, so it works with `insta::assert_snapshot!` or a
+/// file you compare against.
+///
+/// ```
+/// use velvetio::{ask, choose, run_snapshot};
+///
+/// let (_, snapshot) = run_snapshot(&["Ada", "3", "2"], || {
+///     let name: String = ask("Name");
+///     (name, choose("Plan", &["free", "pro"]))
+/// });
+/// assert_eq!(snapshot, "\
+/// ? Name: Ada
+/// | Plan:
+/// |   1. free
+/// |   2. pro
+/// ? Choose (1-2): 3
+/// ! ❌ Please choose between 1 and 2
+/// ? Choose (1-2): 2
+/// ");
+/// ```
+pub fn run_snapshot<R>(answers: &[&str], run: impl FnOnce() -> R) -> (R, String) {
+    let (value, script) = run_script(answers, true, run);
+    let mut snapshot = golden(&script.events);
+    for unused in &script.answers {
+        snapshot.push_str(&format!("- never asked: {}\n", unused));
+    }
+    (value, snapshot)
+}
+
+fn run_script<R>(answers: &[&str], pinned: bool, run: impl FnOnce() -> R) -> (R, Script) {
+    let script = Script {
+        answers: answers.iter().map(|a| a.to_string()).collect(),
+        events: Vec::new(),
+        pinned,
+    };
+    // Nested scripts put the outer one back when they're done
+    let outer = SCRIPT.with_borrow_mut(|slot| slot.replace(script));
+
+    let theme = match pinned {
+        true => Theme::default(),
+        false => crate::theme(),
+    };
+    let theme = Theme {
+        color: false,
+        accessible: theme.accessible && !pinned,
+        minimal: theme.minimal && !pinned,
+        ..theme
+    };
+    let mut session = Session::new().theme(theme).quiet(true);
+    let value = panic::catch_unwind(AssertUnwindSafe(|| session.run(run)));
+
+    let script = SCRIPT.with_borrow_mut(|slot| std::mem::replace(slot, outer));
+    let script = script.expect("the script is only taken here");
+    match value {
+        Ok(value) => (value, script),
+        Err(panicked) => {
+            let events = &script.events;
+            eprintln!("Transcript so far:\n{}", quoted(&transcript(events)));
+            panic::resume_unwind(panicked)
+        }
+    }
+}
+
+// What a terminal would have shown
+fn transcript(events: &[Event]) -> String {
+    let mut transcript = String::new();
+    for event in events {
+        match event {
+            Event::Out(text) | Event::Err(text) => transcript.push_str(text),
+            Event::Asked {
+                prompt,
+                answer,
+                secret,
+            } => {
+                let echoed = match answer {
+                    Some(answer) if !secret => answer.as_str(),
+                    _ => "",
+                };
+                transcript.push_str(prompt);
+                transcript.push_str(echoed);
+                transcript.push('\n');
+            }
+            Event::Line(line) => {
+                transcript.push_str(line);
+                transcript.push('\n');
+            }
+        }
+    }
+    transcript
+}
+
+// The snapshot format: one marked line per terminal line, trailing spaces
+// trimmed. Output that didn't end its line is finished off before a prompt.
+fn golden(events: &[Event]) -> String {
+    let mut golden = String::new();
+    let mut mark = |marker: &str, text: &str| {
+        for line in text.lines() {
+            golden.push_str(format!("{} {}", marker, line).trim_end());
+            golden.push('\n');
+        }
+    };
+    for event in events {
+        match event {
+            Event::Out(text) => mark("|", text),
+            Event::Err(text) => mark("!", text),
+            Event::Asked {
+                prompt,
+                answer,
+                secret,
+            } => {
+                let answer = match (answer, secret) {
+                    (None, _) => "(end of input)",
+                    (Some(_), true) => "(hidden)",
+                    (Some(answer), false) => answer.as_str(),
+                };
+                mark("?", &format!("{}{}", prompt, answer));
+            }
+            Event::Line(line) => mark(">", line),
+        }
+    }
+    golden
+}
+
+// What `assert_interaction!` expands to
+#[doc(hidden)]
+#[track_caller]
+pub fn check_interaction<R>(inputs: &[&str], expected: &[&str], run: impl FnOnce() -> R) -> R {
+    let run = run_scripted(inputs, run);
+    let missing = expected.iter().any(|text| !run.transcript.contains(text));
+    if !missing && run.unused.is_empty() {
+        return run.value;
+    }
+
+    let mut report = String::from("assert_interaction! failed\n\nExpected output containing:\n");
+    for text in expected {
+        let mark = match run.transcript.contains(text) {
+            true => "found  ",
+            false => "MISSING",
+        };
+        report.push_str(&format!("  {} {:?}\n", mark, text));
+    }
+    if !run.unused.is_empty() {
+        report.push_str(&format!("\nNever asked for: {:?}\n", run.unused));
+    }
+    report.push_str(&format!("\nTranscript:\n{}", quoted(&run.transcript)));
+    panic!("{}", report)
+}
+
+// The transcript indented behind a bar, so blank lines and trailing spaces show
+fn quoted(transcript: &str) -> String {
+    transcript
+        .lines()
+        .map(|line| format!("  | {}\n", line))
+        .collect()
+}
+
+pub(crate) fn active() -> bool {
+    SCRIPT.with_borrow(|script| script.is_some())
+}
+
+// The width snapshot runs render for, when one is running
+pub(crate) fn pinned_width() -> Option<usize> {
+    SCRIPT.with_borrow(|script| script.as_ref()?.pinned.then_some(SNAPSHOT_WIDTH))
+}
+
+// The next answer for `prompt` (as printed, up to where the answer starts),
+// echoed after it into the transcript - through `mask` when there is one,
+// like `***` for a sensitive answer. `None` when no script is running;
+// `Some(None)` once the answers have run out.
+pub(crate) fn answer(
+    prompt: String,
+    secret: bool,
+    mask: Option<&dyn Fn(&str) -> String>,
+) -> Option<Option<String>> {
+    SCRIPT.with_borrow_mut(|script| {
+        let script = script.as_mut()?;
+        let answer = script.answers.pop_front();
+        let recorded = match mask {
+            Some(mask) => answer.as_ref().map(|answer| match answer.is_empty() {
+                true => String::new(),
+                false => mask(answer),
+            }),
+            None => answer.clone(),
+        };
+        script.events.push(Event::Asked {
+            prompt,
+            answer: recorded,
+            secret,
+        });
+        Some(answer)
+    })
+}
+
+// A follow-up line with no prompt of its own, like the rest of a pasted key.
+// Written down as `***` while a sensitive prompt runs.
+pub(crate) fn line() -> Option<Option<String>> {
+    SCRIPT.with_borrow_mut(|script| {
+        let script = script.as_mut()?;
+        let line = script.answers.pop_front();
+        if let Some(line) = &line {
+            let recorded = match crate::redact::hiding() {
+                true => crate::REDACTED.to_string(),
+                false => line.clone(),
+            };
+            script.events.push(Event::Line(recorded));
+        }
+        Some(line)
+    })
+}
+
+// Where `out!`, `outln!` and `errln!` end up
+pub(crate) fn write(to_stderr: bool, text: fmt::Arguments) {
+    let captured = SCRIPT.with_borrow_mut(|script| match script {
+        Some(script) => {
+            // Consecutive writes to the same stream go together
+            let text = text.to_string();
+            match (script.events.last_mut(), to_stderr) {
+                (Some(Event::Out(last)), false) | (Some(Event::Err(last)), true) => {
+                    last.push_str(&text)
+                }
+                (_, false) => script.events.push(Event::Out(text)),
+                (_, true) => script.events.push(Event::Err(text)),
+            }
+            true
+        }
+        None => false,
+    });
+    if captured {
+        return;
+    }
+    let sent = crate::session::with_transport(|transport| {
+        // A connection that's gone shows up as closed input on the next prompt
+        let _ = transport.write(&text.to_string(), to_stderr);
+    });
+    if sent.is_some() {
+        return;
+    }
+    if crate::protocol::is_json_protocol() {
+        crate::protocol::output(to_stderr, &text.to_string());
+        return;
+    }
+
+    match to_stderr {
+        true => eprint!("{}", text),
+        false => print!("{}", text),
+    }
+}
+
+// print!, println! and eprintln! for prompt output, which scripts capture
+macro_rules! out {
+    ($($arg:tt)*) => {
+        $crate::script::write(false, format_args!($($arg)*))
+    };
+}
+
+macro_rules! outln {
+    () => {
+        $crate::script::write(false, format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::script::write(false, format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+macro_rules! errln {
+    ($($arg:tt)*) => {
+        $crate::script::write(true, format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+pub(crate) use {errln, out, outln};
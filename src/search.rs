@@ -0,0 +1,70 @@
+// src/search.rs
+
+// Fuzzy search for the pickers over long lists (git refs, the built-in
+// country, locale and timezone tables), where scrolling isn't an option.
+
+// Indexes of the names holding the query's letters in order, best first:
+// substrings starting a word, then other substrings, both in list order
+// (git refs come most recent first), then the tightest and earliest spread
+// of letters
+pub(crate) fn fuzzy_matches<S: AsRef<str>>(query: &str, names: &[S]) -> Vec<usize> {
+    let query: Vec<char> = query.trim().to_lowercase().chars().collect();
+    let mut ranked: Vec<((u8, usize, usize), usize)> = Vec::new();
+
+    for (index, name) in names.iter().enumerate() {
+        let name: Vec<char> = name.as_ref().to_lowercase().chars().collect();
+        let found: Vec<usize> = name
+            .windows(query.len().max(1))
+            .enumerate()
+            .filter(|(_, w)| *w == query)
+            .map(|(at, _)| at)
+            .collect();
+        if !found.is_empty() {
+            let word_start = |at: &usize| *at == 0 || !name[at - 1].is_alphanumeric();
+            let tier = if found.iter().any(word_start) { 0 } else { 1 };
+            ranked.push(((tier, 0, 0), index));
+            continue;
+        }
+        // Letters in order, possibly with others in between
+        let mut positions = Vec::new();
+        let mut rest = name.iter().enumerate();
+        for wanted in &query {
+            match rest.find(|(_, c)| *c == wanted) {
+                Some((at, _)) => positions.push(at),
+                None => break,
+            }
+        }
+        if positions.len() == query.len() {
+            let first = positions.first().copied().unwrap_or(0);
+            let spread = positions.last().copied().unwrap_or(0) - first;
+            ranked.push(((2, spread, first), index));
+        }
+    }
+
+    ranked.sort_by_key(|(rank, _)| *rank);
+    ranked.into_iter().map(|(_, index)| index).collect()
+}
+
+// Pick an id from a built-in table of (id, label, other names). Answers are
+// paged like `choose_lazy`, and `/text` searches labels and other names.
+#[cfg(any(feature = "countries", feature = "locales", feature = "timezones"))]
+pub(crate) fn choose_entry(
+    prompt: &str,
+    entries: &[(&'static str, String, String)],
+    policy: &crate::AttemptPolicy,
+) -> crate::Result<&'static str> {
+    const PAGE: usize = 10;
+    let searched: Vec<String> = entries
+        .iter()
+        .map(|(id, label, other)| format!("{} {} {}", label, id, other).replace('_', " "))
+        .collect();
+    let source = |page: usize, filter: &str| {
+        fuzzy_matches(filter, &searched)
+            .into_iter()
+            .skip(page * PAGE)
+            .take(PAGE)
+            .map(|index| crate::Choice::new(entries[index].1.clone(), entries[index].0))
+            .collect()
+    };
+    crate::lazy::lazy_inner(prompt, source, policy).map(crate::Choice::into_value)
+}
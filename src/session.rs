@@ -0,0 +1,280 @@
+// src/session.rs
+
+// Prompt settings owned by a value instead of the process. The free functions
+// use the process-wide settings (`set_theme`, `set_quiet`, `enable_cache`...),
+// which act as the default session. A `Session` swaps its own settings in for
+// the current thread while it runs prompts, then takes them back.
+
+use crate::poll::PollingTransport;
+use crate::{
+    AnswerCache, AnswerProvider, Parse, Polling, Result, SelectionOrder, Theme, Transport, sanitize,
+};
+use std::cell::RefCell;
+use std::fmt::{self, Display};
+use std::sync::{Arc, Mutex};
+
+thread_local! {
+    // Innermost last, so sessions can nest
+    static ACTIVE: RefCell<Vec<Settings>> = const { RefCell::new(Vec::new()) };
+}
+
+#[derive(Debug, Clone, Default)]
+struct Settings {
+    theme: Theme,
+    quiet: bool,
+    sanitize: bool,
+    locale: Option<String>,
+    selection_order: Option<SelectionOrder>,
+    cache: Option<AnswerCache>,
+    transport: Option<Shared>,
+}
+
+// Cloned sessions talk over the same connection
+#[derive(Clone)]
+struct Shared(Arc<Mutex<dyn Transport>>);
+
+impl fmt::Debug for Shared {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Transport")
+    }
+}
+
+/// Prompts with their own theme, cache and switches, independent of the
+/// global ones
+///
+/// ```no_run
+/// use velvetio::{Session, Theme};
+///
+/// let mut session = Session::new().theme(Theme::plain()).quiet(true);
+/// let port: u16 = session.ask("Port");
+/// let go = session.confirm("Start the server?");
+/// ```
+///
+/// Anything can run inside a session, forms included:
+///
+/// ```no_run
+/// # let mut session = velvetio::Session::new();
+/// let answers = session.run(|| velvetio::form().text("name", "Name").collect());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Session {
+    settings: Settings,
+}
+
+impl Session {
+    /// Starts from the current global settings, without an answer cache
+    pub fn new() -> Self {
+        Self {
+            settings: Settings {
+                theme: crate::theme(),
+                quiet: crate::quiet(),
+                sanitize: sanitize::enabled(),
+                locale: None,
+                selection_order: None,
+                cache: None,
+                transport: None,
+            },
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.settings.theme = theme;
+        self
+    }
+
+    /// Never beep or flash, whatever the theme says
+    pub fn quiet(mut self, quiet: bool) -> Self {
+        self.settings.quiet = quiet;
+        self
+    }
+
+    /// Strip escape sequences and invisible characters from answers
+    pub fn sanitize_input(mut self, enabled: bool) -> Self {
+        self.settings.sanitize = enabled;
+        self
+    }
+
+    /// Show prompts in this locale instead of the global one
+    pub fn locale(mut self, locale: &str) -> Self {
+        self.settings.locale = Some(crate::i18n::normalize(locale));
+        self
+    }
+
+    /// Return `multi_select` picks in this order instead of the global one
+    pub fn selection_order(mut self, order: SelectionOrder) -> Self {
+        self.settings.selection_order = Some(order);
+        self
+    }
+
+    /// Remember keyed answers in this cache instead of the global one
+    pub fn cache(mut self, cache: AnswerCache) -> Self {
+        self.settings.cache = Some(cache);
+        self
+    }
+
+    /// Serve prompts over `transport` instead of the terminal
+    ///
+    /// The theme still comes from the session, so pick one that suits the
+    /// other end - `Theme::plain()` when it can't show colors.
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.settings.transport = Some(Shared(Arc::new(Mutex::new(transport))));
+        self
+    }
+
+    /// Take answers from `provider`, polling it as `polling` says, instead
+    /// of waiting on stdin
+    ///
+    /// Prompts go to the provider's [`show`](AnswerProvider::show), in their
+    /// plain numbered versions - printed here, unless it shows them. An
+    /// answer that doesn't come in time fails the prompt with a `Timeout`
+    /// error, as does a form's or flow's deadline passing while it waits.
+    ///
+    /// ```
+    /// use std::sync::mpsc;
+    /// use velvetio::{Polling, Session};
+    ///
+    /// let (send, answers) = mpsc::channel();
+    /// let mut session = Session::new().answers(answers, Polling::default());
+    /// // From anywhere, like a UI callback
+    /// send.send("8080".to_string()).unwrap();
+    /// let port: u16 = session.ask("Port");
+    /// assert_eq!(port, 8080);
+    /// ```
+    pub fn answers(self, provider: impl AnswerProvider + 'static, polling: Polling) -> Self {
+        self.transport(PollingTransport { provider, polling })
+    }
+
+    pub fn answer_cache(&self) -> Option<&AnswerCache> {
+        self.settings.cache.as_ref()
+    }
+
+    /// Run `f` with this session's settings in place of the global ones
+    pub fn run<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        let settings = std::mem::take(&mut self.settings);
+        ACTIVE.with_borrow_mut(|active| active.push(settings));
+        // Popped even if `f` panics, so the thread isn't stuck in this session
+        let _restore = Restore(self);
+        f()
+    }
+
+    /// Run `command` with its prompts asked in this session - see
+    /// [`forward_prompts`](crate::forward_prompts)
+    pub fn forward(
+        &mut self,
+        command: &mut std::process::Command,
+    ) -> Result<std::process::ExitStatus> {
+        self.run(|| crate::forward_prompts(command))
+    }
+
+    pub fn ask<T: Parse>(&mut self, prompt: &str) -> T {
+        self.run(|| crate::ask(prompt))
+    }
+
+    pub fn try_ask<T: Parse>(&mut self, prompt: &str) -> Result<T> {
+        self.run(|| crate::try_ask(prompt))
+    }
+
+    pub fn ask_with_default<T: Parse + Display + Clone>(&mut self, prompt: &str, default: T) -> T {
+        self.run(|| crate::ask_with_default(prompt, default))
+    }
+
+    pub fn ask_keyed<T: Parse>(&mut self, key: &str, prompt: &str) -> T {
+        self.run(|| crate::ask_keyed(key, prompt))
+    }
+
+    pub fn confirm(&mut self, prompt: &str) -> bool {
+        self.run(|| crate::confirm(prompt))
+    }
+
+    pub fn try_confirm(&mut self, prompt: &str) -> Result<bool> {
+        self.run(|| crate::try_confirm(prompt))
+    }
+
+    pub fn choose<T: Display + Clone>(&mut self, prompt: &str, choices: &[T]) -> T {
+        self.run(|| crate::choose(prompt, choices))
+    }
+
+    pub fn try_choose<T: Display + Clone>(&mut self, prompt: &str, choices: &[T]) -> Result<T> {
+        self.run(|| crate::try_choose(prompt, choices))
+    }
+
+    pub fn multi_select<T: Display + Clone>(&mut self, prompt: &str, choices: &[T]) -> Vec<T> {
+        self.run(|| crate::multi_select(prompt, choices))
+    }
+
+    pub fn try_multi_select<T: Display + Clone>(
+        &mut self,
+        prompt: &str,
+        choices: &[T],
+    ) -> Result<Vec<T>> {
+        self.run(|| crate::try_multi_select(prompt, choices))
+    }
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Takes the settings back off the thread, keeping what the prompts changed
+// (like new cached answers)
+struct Restore<'a>(&'a mut Session);
+
+impl Drop for Restore<'_> {
+    fn drop(&mut self) {
+        if let Some(settings) = ACTIVE.with_borrow_mut(|active| active.pop()) {
+            self.0.settings = settings;
+        }
+    }
+}
+
+// The innermost running session's theme, if any
+pub(crate) fn theme() -> Option<Theme> {
+    with_active(|settings| settings.theme.clone())
+}
+
+pub(crate) fn quiet() -> Option<bool> {
+    with_active(|settings| settings.quiet)
+}
+
+pub(crate) fn sanitize() -> Option<bool> {
+    with_active(|settings| settings.sanitize)
+}
+
+// The running session's own locale, if it set one
+pub(crate) fn locale() -> Option<String> {
+    with_active(|settings| settings.locale.clone()).flatten()
+}
+
+pub(crate) fn selection_order() -> Option<SelectionOrder> {
+    with_active(|settings| settings.selection_order).flatten()
+}
+
+// Like `theme`, without the copy
+pub(crate) fn with_theme<R>(f: impl FnOnce(&Theme) -> R) -> Option<R> {
+    with_active(|settings| f(&settings.theme))
+}
+
+// `Some` when a session is running, holding its cache (which may be off)
+pub(crate) fn with_cache<R>(f: impl FnOnce(Option<&mut AnswerCache>) -> R) -> Option<R> {
+    with_active(|settings| f(settings.cache.as_mut()))
+}
+
+pub(crate) fn has_transport() -> bool {
+    with_active(|settings| settings.transport.is_some()).unwrap_or(false)
+}
+
+// `Some` when the running session has a transport, with what `f` made of it
+pub(crate) fn with_transport<R>(f: impl FnOnce(&mut dyn Transport) -> R) -> Option<R> {
+    let shared = with_active(|settings| settings.transport.clone()).flatten()?;
+    let mut transport = shared
+        .0
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    Some(f(&mut *transport))
+}
+
+fn with_active<R>(f: impl FnOnce(&mut Settings) -> R) -> Option<R> {
+    ACTIVE.with_borrow_mut(|active| active.last_mut().map(f))
+}
@@ -0,0 +1,124 @@
+// src/spinner.rs
+
+use crate::script::outln;
+use crate::term;
+use crate::theme::{Theme, theme};
+use std::io::{self, Write};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Show a spinner while `work` runs, then clear it
+///
+/// The spinner is gone by the time this returns (even if `work` panics), so
+/// the next prompt starts on a clean line. When stdout isn't a terminal it
+/// prints the message followed by a dot every second instead.
+///
+/// For async work, see [`with_spinner_async`].
+///
+/// ```no_run
+/// let reachable = velvetio::with_spinner("Checking connectivity...", || {
+///     std::net::TcpStream::connect("example.com:443").is_ok()
+/// });
+/// ```
+pub fn with_spinner<T>(message: &str, work: impl FnOnce() -> T) -> T {
+    let _spinner = Spinner::start(message, theme());
+    work()
+}
+
+/// [`with_spinner`] for a future: the spinner runs until `work` is done, or
+/// dropped
+///
+/// It animates on its own thread, so it works the same with any runtime.
+///
+/// ```no_run
+/// # async fn fetch() -> u32 { 200 }
+/// # async fn run() {
+/// let status = velvetio::with_spinner_async("Fetching...", fetch()).await;
+/// # }
+/// ```
+pub async fn with_spinner_async<F: Future>(message: &str, work: F) -> F::Output {
+    let _spinner = Spinner::start(message, theme());
+    work.await
+}
+
+// Animates on its own thread until dropped
+struct Spinner {
+    stop: Option<Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Spinner {
+    fn start(message: &str, theme: Theme) -> Self {
+        // Scripted and headless runs get the message up front, in order with
+        // the prompts, and nothing else. So do sessions with a transport,
+        // whose screen is somewhere else, and WASM, which has no threads to
+        // animate with.
+        if crate::script::active()
+            || crate::is_headless()
+            || crate::session::has_transport()
+            || cfg!(target_family = "wasm")
+        {
+            outln!("{}", message);
+            return Self {
+                stop: None,
+                handle: None,
+            };
+        }
+        let (stop, stopped) = mpsc::channel::<()>();
+        let message = message.to_string();
+        let animated = term::cursor_control() && !theme.spinner.is_empty() && !theme.accessible;
+        // Screen readers would announce every dot
+        let dots = !theme.accessible && !theme.minimal;
+
+        let handle = thread::spawn(move || {
+            // Returns false once the Spinner is dropped
+            let wait = |interval| {
+                matches!(
+                    stopped.recv_timeout(interval),
+                    Err(RecvTimeoutError::Timeout)
+                )
+            };
+
+            let mut out = io::stdout();
+            if animated {
+                let frames = theme.spinner.iter().cycle();
+                for frame in frames {
+                    let frame = theme.accent(&frame.to_string());
+                    let _ = write!(out, "\r{} {}", frame, message);
+                    let _ = out.flush();
+                    if !wait(Duration::from_millis(80)) {
+                        break;
+                    }
+                }
+                // Clear the line and leave the cursor at its start
+                let _ = write!(out, "\r\x1b[2K");
+            } else {
+                let _ = write!(out, "{}", message);
+                let _ = out.flush();
+                while wait(Duration::from_secs(1)) {
+                    if dots {
+                        let _ = write!(out, ".");
+                        let _ = out.flush();
+                    }
+                }
+                let _ = writeln!(out);
+            }
+            let _ = out.flush();
+        });
+
+        Self {
+            stop: Some(stop),
+            handle: Some(handle),
+        }
+    }
+}
+
+impl Drop for Spinner {
+    fn drop(&mut self) {
+        drop(self.stop.take());
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
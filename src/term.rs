@@ -0,0 +1,144 @@
+// src/term.rs
+
+// What the terminal we're talking to can do. There's no terminfo database
+// here - like most CLI tools we go by the environment (TERM, COLORTERM,
+// NO_COLOR, the locale) and ask `stty` for the size. Scripted and headless
+// runs count as no terminal at all.
+//
+// The default theme and the interactive parts (line editor, in-place menu
+// retries, spinner) decide what to do from this.
+
+use crate::{layout, tty};
+
+/// What the terminal supports, from [`terminal`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Terminal {
+    pub stdin_tty: bool,
+    pub stdout_tty: bool,
+    pub stderr_tty: bool,
+    /// Colors stdout can show
+    pub colors: ColorDepth,
+    /// Whether emoji and box drawing characters come out right
+    pub unicode: bool,
+    /// Columns, when stdout is a terminal or `COLUMNS` is set
+    pub width: Option<usize>,
+    /// Rows, when stdout is a terminal or `LINES` is set
+    pub height: Option<usize>,
+    /// Keys can be read one at a time, for the line editor. Only on Unix -
+    /// on Windows and WASI prompts read whole lines.
+    pub raw_keys: bool,
+}
+
+/// How many colors a terminal can show
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorDepth {
+    /// No colors - not a terminal, `NO_COLOR`, or `TERM=dumb`
+    None,
+    /// The 16 standard ANSI colors
+    Basic,
+    /// The 256 color palette
+    Ansi256,
+    /// 24-bit color
+    TrueColor,
+}
+
+/// Look at the terminal as it is right now
+///
+/// Sizes are read fresh on every call, so a resized window shows up.
+///
+/// ```no_run
+/// use velvetio::{ColorDepth, terminal};
+///
+/// let term = terminal();
+/// if term.colors >= ColorDepth::Ansi256 && term.unicode {
+///     println!("Fancy output it is");
+/// }
+/// ```
+pub fn terminal() -> Terminal {
+    let stdout_tty = tty::stdout_is_tty();
+    // Falls back to 24 rows like `layout::width` does to 80 columns, for
+    // terminals that won't say
+    let height = env_number("LINES")
+        .or_else(|| stdout_tty.then(|| tty::size().map_or(24, |(rows, _)| rows)));
+    Terminal {
+        stdin_tty: tty::stdin_is_tty(),
+        stdout_tty,
+        stderr_tty: tty::stderr_is_tty(),
+        colors: colors(),
+        unicode: unicode_terminal(),
+        width: layout::width(),
+        height,
+        raw_keys: raw_keys(),
+    }
+}
+
+// What the default theme goes by. Scripted and headless output is never
+// colored, even when forced.
+pub(crate) fn colors() -> ColorDepth {
+    if crate::script::active() || crate::is_headless() {
+        return ColorDepth::None;
+    }
+    color_depth(&env, tty::stdout_is_tty())
+}
+
+// Moving the cursor and redrawing lines works, which dumb terminals (and
+// Emacs shells) can't do even when they're a tty
+pub(crate) fn cursor_control() -> bool {
+    tty::stdout_is_tty() && env("TERM").is_none_or(|term| term != "dumb")
+}
+
+pub(crate) fn raw_keys() -> bool {
+    cfg!(unix) && tty::stdin_is_tty()
+}
+
+pub(crate) fn unicode_terminal() -> bool {
+    unicode(&env)
+}
+
+fn env(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+fn env_number(name: &str) -> Option<usize> {
+    env(name)?.trim().parse().ok().filter(|&n| n > 0)
+}
+
+// `env` is passed in so tests can fake the environment
+pub(crate) fn color_depth(env: &dyn Fn(&str) -> Option<String>, stdout_tty: bool) -> ColorDepth {
+    // https://no-color.org, and FORCE_COLOR from the Node world for CI logs
+    if env("NO_COLOR").is_some() {
+        return ColorDepth::None;
+    }
+    let forced = env("FORCE_COLOR").is_some_and(|force| force != "0");
+    let term = env("TERM").unwrap_or_default();
+    if !forced && (!stdout_tty || term == "dumb") {
+        return ColorDepth::None;
+    }
+
+    let colorterm = env("COLORTERM").unwrap_or_default().to_ascii_lowercase();
+    if colorterm == "truecolor" || colorterm == "24bit" || env("WT_SESSION").is_some() {
+        ColorDepth::TrueColor
+    } else if term.contains("256color") {
+        ColorDepth::Ansi256
+    } else {
+        ColorDepth::Basic
+    }
+}
+
+pub(crate) fn unicode(env: &dyn Fn(&str) -> Option<String>) -> bool {
+    // The Linux console only has a small font to draw with
+    if env("TERM").as_deref() == Some("linux") {
+        return false;
+    }
+    // Windows Terminal and VS Code both do, the old console host doesn't
+    if cfg!(windows) {
+        return env("WT_SESSION").is_some() || env("TERM_PROGRAM").is_some();
+    }
+    // The first locale variable that's set is the one that counts
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|name| env(name))
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    locale.contains("utf-8") || locale.contains("utf8")
+}
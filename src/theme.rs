@@ -0,0 +1,286 @@
+// src/theme.rs
+
+use crate::{ColorDepth, VelvetIOError, session, term, tty};
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+type Renderer = Arc<dyn Fn(&VelvetIOError, &Theme) -> String + Send + Sync>;
+
+static THEME: Mutex<Option<Theme>> = Mutex::new(None);
+static RENDERER: Mutex<Option<Renderer>> = Mutex::new(None);
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// How prompts, errors and progress look
+///
+/// ```no_run
+/// use velvetio::{Theme, set_theme};
+///
+/// set_theme(Theme {
+///     error_prefix: "error:".to_string(),
+///     ..Theme::default()
+/// });
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    /// Printed before every error message
+    pub error_prefix: String,
+    /// Printed before warnings, like a pasted answer being cleaned up
+    pub warning_prefix: String,
+    /// Printed before success messages, like a finished progress bar
+    pub success_prefix: String,
+    /// Filled part of a progress bar
+    pub bar_filled: char,
+    /// Empty part of a progress bar
+    pub bar_empty: char,
+    /// Progress bar width in characters, 0 hides the bar
+    pub bar_width: usize,
+    /// Between nested context labels, as in `Database ▸ Host`
+    pub context_separator: String,
+    /// Spinner animation frames
+    pub spinner: Vec<char>,
+    /// Between the prompt and the answer
+    pub prompt_suffix: String,
+    /// How a default follows the prompt, with `{}` standing for the value
+    pub default_format: String,
+    /// Put the answer on its own line under the prompt, after this, as in
+    /// `Project name` then `> my-app`
+    pub input_indicator: Option<String>,
+    /// Use ANSI colors for highlights
+    pub color: bool,
+    /// Get the user's attention when an answer is rejected
+    pub alert: Alert,
+    /// Screen-reader friendly output: no cursor movement or animation, one
+    /// option per line, and defaults spelled out in words
+    pub accessible: bool,
+    /// Just the prompts and what went wrong: no hints, no menus shown twice,
+    /// and an error repeated on the same prompt is only printed once
+    pub minimal: bool,
+    /// How yes/no questions are answered
+    pub confirm_style: ConfirmStyle,
+}
+
+impl Theme {
+    /// ASCII only, no colors - for logs and dumb terminals
+    pub fn plain() -> Self {
+        Self {
+            error_prefix: "error:".to_string(),
+            warning_prefix: "warning:".to_string(),
+            success_prefix: "done:".to_string(),
+            bar_filled: '#',
+            bar_empty: '-',
+            bar_width: 20,
+            context_separator: ">".to_string(),
+            spinner: vec!['|', '/', '-', '\\'],
+            prompt_suffix: ": ".to_string(),
+            default_format: "[{}]".to_string(),
+            input_indicator: None,
+            color: false,
+            alert: Alert::None,
+            accessible: false,
+            minimal: false,
+            confirm_style: ConfirmStyle::Words,
+        }
+    }
+
+    /// For expert users and recorded sessions - `plain`, minus everything
+    /// that isn't a prompt or an error
+    ///
+    /// Used by default when the `VELVETIO_MINIMAL` environment variable is set.
+    pub fn minimal() -> Self {
+        Self {
+            bar_width: 0,
+            spinner: Vec::new(),
+            minimal: true,
+            ..Self::plain()
+        }
+    }
+
+    /// For screen readers - words instead of glyphs, nothing redrawn or animated
+    ///
+    /// Used by default when the `VELVETIO_ACCESSIBLE` environment variable is set.
+    pub fn accessible() -> Self {
+        Self {
+            error_prefix: "Error:".to_string(),
+            warning_prefix: "Warning:".to_string(),
+            success_prefix: "Done:".to_string(),
+            bar_width: 0,
+            context_separator: "-".to_string(),
+            spinner: Vec::new(),
+            default_format: "(default: {})".to_string(),
+            accessible: true,
+            ..Self::plain()
+        }
+    }
+
+    // Bold cyan when colors are on
+    pub(crate) fn accent(&self, text: &str) -> String {
+        if self.color {
+            format!("\x1b[1;36m{}\x1b[0m", text)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+impl Default for Theme {
+    /// Emoji and block characters, colored when the terminal has colors
+    ///
+    /// A terminal that can't show Unicode gets the `plain` characters instead,
+    /// still in color.
+    fn default() -> Self {
+        let set = |name| std::env::var(name).is_ok_and(|value| !value.is_empty() && value != "0");
+        if set("VELVETIO_ACCESSIBLE") {
+            return Self::accessible();
+        }
+        if set("VELVETIO_MINIMAL") {
+            return Self::minimal();
+        }
+        let color = term::colors() != ColorDepth::None;
+        if tty::stdout_is_tty() && !term::unicode_terminal() {
+            return Self {
+                color,
+                ..Self::plain()
+            };
+        }
+
+        Self {
+            error_prefix: "❌".to_string(),
+            warning_prefix: "⚠️".to_string(),
+            success_prefix: "✅".to_string(),
+            bar_filled: '█',
+            bar_empty: '░',
+            bar_width: 20,
+            context_separator: "▸".to_string(),
+            spinner: "⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏".chars().collect(),
+            prompt_suffix: ": ".to_string(),
+            default_format: "[{}]".to_string(),
+            input_indicator: None,
+            color,
+            alert: Alert::None,
+            accessible: false,
+            minimal: false,
+            confirm_style: ConfirmStyle::Words,
+        }
+    }
+}
+
+/// How a yes/no question is answered. Every style gives the same `bool`.
+///
+/// The toggle and the checkbox need a terminal that can send single keys.
+/// Anywhere else - piped or scripted input, headless runs, transports,
+/// screen readers - they're asked as words.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConfirmStyle {
+    /// Type `y` or `n`, as in `Deploy? (y/n):`
+    #[default]
+    Words,
+    /// Pick Yes or No with the arrow keys, as in `Deploy? [Yes]  No`
+    Toggle,
+    /// Tick a box with space, as in `[x] Deploy`
+    Checkbox,
+}
+
+/// Extra signal when an answer is rejected, on top of the error message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alert {
+    /// Just the message
+    #[default]
+    None,
+    /// Ring the terminal bell
+    Bell,
+    /// Briefly flip the screen to inverse video
+    Flash,
+}
+
+impl Alert {
+    // Only on a terminal, and never in quiet mode
+    pub(crate) fn fire(self) {
+        let mut err = std::io::stderr();
+        if quiet() || !tty::stderr_is_tty() {
+            return;
+        }
+
+        match self {
+            Alert::None => {}
+            Alert::Bell => {
+                let _ = write!(err, "\x07");
+            }
+            Alert::Flash => {
+                let _ = write!(err, "\x1b[?5h");
+                let _ = err.flush();
+                std::thread::sleep(Duration::from_millis(100));
+                let _ = write!(err, "\x1b[?5l");
+            }
+        }
+        let _ = err.flush();
+    }
+}
+
+/// Quiet mode: never beep or flash, whatever the theme says
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+pub fn quiet() -> bool {
+    session::quiet().unwrap_or_else(|| QUIET.load(Ordering::Relaxed))
+}
+
+/// Use this theme for everything printed from now on
+pub fn set_theme(theme: Theme) {
+    *lock() = Some(theme);
+}
+
+/// The active theme - the running `Session`'s, or the global one
+pub fn theme() -> Theme {
+    session::theme().unwrap_or_else(|| lock().get_or_insert_with(Theme::default).clone())
+}
+
+// A look at the active theme without cloning it, which matters on the path
+// every prompt takes. `f` mustn't ask for the theme itself.
+pub(crate) fn with_theme<R>(f: impl Fn(&Theme) -> R) -> R {
+    session::with_theme(&f).unwrap_or_else(|| f(lock().get_or_insert_with(Theme::default)))
+}
+
+/// Format errors yourself instead of `<error_prefix> <message>`
+///
+/// ```no_run
+/// use velvetio::set_error_renderer;
+///
+/// set_error_renderer(|error, _theme| format!("[{:?}] {}", error.kind, error));
+/// ```
+pub fn set_error_renderer<F>(render: F)
+where
+    F: Fn(&VelvetIOError, &Theme) -> String + Send + Sync + 'static,
+{
+    *RENDERER
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(Arc::new(render));
+}
+
+/// Go back to the theme's `<error_prefix> <message>`
+pub fn reset_error_renderer() {
+    *RENDERER
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+}
+
+// The line printed for a rejected answer or a failed prompt
+pub(crate) fn render_error(error: &VelvetIOError) -> String {
+    // Cloned out so a renderer that prompts or sets one itself can't deadlock
+    let renderer = RENDERER
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    match renderer {
+        Some(render) => render(error, &theme()),
+        None => with_theme(|theme| format!("{} {}", theme.error_prefix, error)),
+    }
+}
+
+fn lock() -> std::sync::MutexGuard<'static, Option<Theme>> {
+    THEME
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -0,0 +1,720 @@
+// src/timezones.rs
+
+// IANA timezones, generated from tzdata's zone.tab: one entry per zone that
+// some country uses, so `Europe/Amsterdam` is here even though it's a link
+// now. Each is searchable by country, the old names that link to it, and
+// the usual abbreviations.
+
+use crate::core::give_up;
+use crate::search::choose_entry;
+use crate::{AttemptPolicy, MAX_ATTEMPTS, Result};
+
+/// Pick a timezone and get its IANA name, like `"Europe/Berlin"`
+///
+/// Type `/` and part of a name to search: a city (`/new york`), a country
+/// (`/germany`), an old name (`/US/Eastern`) or an abbreviation (`/pst`).
+/// Exits the program if input is closed before a timezone is picked.
+///
+/// ```no_run
+/// let zone = velvetio::choose_timezone("Server timezone");
+/// ```
+pub fn choose_timezone(prompt: &str) -> &'static str {
+    timezone_loop(prompt, &AttemptPolicy::unlimited()).unwrap_or_else(|e| give_up(e))
+}
+
+/// `choose_timezone` that gives up after `MAX_ATTEMPTS` invalid answers
+pub fn try_choose_timezone(prompt: &str) -> Result<&'static str> {
+    timezone_loop(prompt, &AttemptPolicy::limited(MAX_ATTEMPTS))
+}
+
+fn timezone_loop(prompt: &str, policy: &AttemptPolicy) -> Result<&'static str> {
+    let entries: Vec<_> = TIMEZONES
+        .iter()
+        .map(|(zone, other)| (*zone, zone.to_string(), other.to_string()))
+        .collect();
+    choose_entry(prompt, &entries, policy)
+}
+
+// (zone, countries, tzdata's comment, links and abbreviations)
+const TIMEZONES: &[(&str, &str)] = &[
+    ("UTC", "GMT Z Zulu Universal Coordinated Etc/UTC"),
+    ("Africa/Abidjan", "Côte d'Ivoire Africa/Timbuktu Iceland"),
+    ("Africa/Accra", "Ghana"),
+    ("Africa/Addis_Ababa", "Ethiopia"),
+    ("Africa/Algiers", "Algeria"),
+    ("Africa/Asmara", "Eritrea"),
+    ("Africa/Bamako", "Mali"),
+    ("Africa/Bangui", "Central African Rep."),
+    ("Africa/Banjul", "Gambia"),
+    ("Africa/Bissau", "Guinea-Bissau"),
+    ("Africa/Blantyre", "Malawi"),
+    ("Africa/Brazzaville", "Congo (Rep.)"),
+    ("Africa/Bujumbura", "Burundi"),
+    ("Africa/Cairo", "Egypt Egypt"),
+    ("Africa/Casablanca", "Morocco"),
+    ("Africa/Ceuta", "Spain Ceuta, Melilla"),
+    ("Africa/Conakry", "Guinea"),
+    ("Africa/Dakar", "Senegal"),
+    ("Africa/Dar_es_Salaam", "Tanzania"),
+    ("Africa/Djibouti", "Djibouti"),
+    ("Africa/Douala", "Cameroon"),
+    ("Africa/El_Aaiun", "Western Sahara"),
+    ("Africa/Freetown", "Sierra Leone"),
+    ("Africa/Gaborone", "Botswana"),
+    ("Africa/Harare", "Zimbabwe"),
+    ("Africa/Johannesburg", "South Africa"),
+    ("Africa/Juba", "South Sudan"),
+    ("Africa/Kampala", "Uganda"),
+    ("Africa/Khartoum", "Sudan"),
+    ("Africa/Kigali", "Rwanda"),
+    (
+        "Africa/Kinshasa",
+        "Congo (Dem. Rep.) Dem. Rep. of Congo (west)",
+    ),
+    ("Africa/Lagos", "Nigeria"),
+    ("Africa/Libreville", "Gabon"),
+    ("Africa/Lome", "Togo"),
+    ("Africa/Luanda", "Angola"),
+    (
+        "Africa/Lubumbashi",
+        "Congo (Dem. Rep.) Dem. Rep. of Congo (east)",
+    ),
+    ("Africa/Lusaka", "Zambia"),
+    ("Africa/Malabo", "Equatorial Guinea"),
+    ("Africa/Maputo", "Mozambique"),
+    ("Africa/Maseru", "Lesotho"),
+    ("Africa/Mbabane", "Eswatini (Swaziland)"),
+    ("Africa/Mogadishu", "Somalia"),
+    ("Africa/Monrovia", "Liberia"),
+    ("Africa/Nairobi", "Kenya Africa/Asmera"),
+    ("Africa/Ndjamena", "Chad"),
+    ("Africa/Niamey", "Niger"),
+    ("Africa/Nouakchott", "Mauritania"),
+    ("Africa/Ouagadougou", "Burkina Faso"),
+    ("Africa/Porto-Novo", "Benin"),
+    ("Africa/Sao_Tome", "Sao Tome & Principe"),
+    ("Africa/Tripoli", "Libya Libya"),
+    ("Africa/Tunis", "Tunisia"),
+    ("Africa/Windhoek", "Namibia"),
+    (
+        "America/Adak",
+        "United States Alaska - western Aleutians US/Aleutian America/Atka",
+    ),
+    (
+        "America/Anchorage",
+        "United States Alaska (most areas) US/Alaska AKST Alaska",
+    ),
+    ("America/Anguilla", "Anguilla"),
+    ("America/Antigua", "Antigua & Barbuda"),
+    ("America/Araguaina", "Brazil Tocantins"),
+    (
+        "America/Argentina/Buenos_Aires",
+        "Argentina Buenos Aires (BA, CF) America/Buenos_Aires",
+    ),
+    (
+        "America/Argentina/Catamarca",
+        "Argentina Catamarca (CT), Chubut (CH) America/Catamarca America/Argentina/ComodRivadavia",
+    ),
+    (
+        "America/Argentina/Cordoba",
+        "Argentina Argentina (most areas: CB, CC, CN, ER, FM, MN, SE, SF) America/Cordoba America/Rosario",
+    ),
+    (
+        "America/Argentina/Jujuy",
+        "Argentina Jujuy (JY) America/Jujuy",
+    ),
+    ("America/Argentina/La_Rioja", "Argentina La Rioja (LR)"),
+    (
+        "America/Argentina/Mendoza",
+        "Argentina Mendoza (MZ) America/Mendoza",
+    ),
+    (
+        "America/Argentina/Rio_Gallegos",
+        "Argentina Santa Cruz (SC)",
+    ),
+    (
+        "America/Argentina/Salta",
+        "Argentina Salta (SA, LP, NQ, RN)",
+    ),
+    ("America/Argentina/San_Juan", "Argentina San Juan (SJ)"),
+    ("America/Argentina/San_Luis", "Argentina San Luis (SL)"),
+    ("America/Argentina/Tucuman", "Argentina Tucuman (TM)"),
+    (
+        "America/Argentina/Ushuaia",
+        "Argentina Tierra del Fuego (TF)",
+    ),
+    ("America/Aruba", "Aruba"),
+    ("America/Asuncion", "Paraguay"),
+    (
+        "America/Atikokan",
+        "Canada EST - ON (Atikokan), NU (Coral H)",
+    ),
+    ("America/Bahia", "Brazil Bahia"),
+    ("America/Bahia_Banderas", "Mexico Bahia de Banderas"),
+    ("America/Barbados", "Barbados"),
+    ("America/Belem", "Brazil Para (east), Amapa"),
+    ("America/Belize", "Belize"),
+    (
+        "America/Blanc-Sablon",
+        "Canada AST - QC (Lower North Shore)",
+    ),
+    ("America/Boa_Vista", "Brazil Roraima"),
+    ("America/Bogota", "Colombia"),
+    (
+        "America/Boise",
+        "United States Mountain - ID (south), OR (east)",
+    ),
+    ("America/Cambridge_Bay", "Canada Mountain - NU (west)"),
+    ("America/Campo_Grande", "Brazil Mato Grosso do Sul"),
+    ("America/Cancun", "Mexico Quintana Roo"),
+    ("America/Caracas", "Venezuela"),
+    ("America/Cayenne", "French Guiana"),
+    ("America/Cayman", "Cayman Islands"),
+    (
+        "America/Chicago",
+        "United States Central (most areas) US/Central CST CDT Central",
+    ),
+    ("America/Chihuahua", "Mexico Chihuahua (most areas)"),
+    (
+        "America/Ciudad_Juarez",
+        "Mexico Chihuahua (US border - west)",
+    ),
+    ("America/Costa_Rica", "Costa Rica"),
+    ("America/Coyhaique", "Chile Aysen Region"),
+    ("America/Creston", "Canada MST - BC (Creston)"),
+    ("America/Cuiaba", "Brazil Mato Grosso"),
+    ("America/Curacao", "Curaçao"),
+    (
+        "America/Danmarkshavn",
+        "Greenland National Park (east coast)",
+    ),
+    ("America/Dawson", "Canada MST - Yukon (west)"),
+    (
+        "America/Dawson_Creek",
+        "Canada MST - BC (Dawson Cr, Ft St John)",
+    ),
+    (
+        "America/Denver",
+        "United States Mountain (most areas) Navajo US/Mountain America/Shiprock MST MDT Mountain",
+    ),
+    (
+        "America/Detroit",
+        "United States Eastern - MI (most areas) US/Michigan",
+    ),
+    ("America/Dominica", "Dominica"),
+    (
+        "America/Edmonton",
+        "Canada Mountain - AB, BC(E), NT(E), SK(W) Canada/Mountain America/Yellowknife",
+    ),
+    ("America/Eirunepe", "Brazil Amazonas (west)"),
+    ("America/El_Salvador", "El Salvador"),
+    ("America/Fort_Nelson", "Canada MST - BC (Ft Nelson)"),
+    (
+        "America/Fortaleza",
+        "Brazil Brazil (northeast: MA, PI, CE, RN, PB)",
+    ),
+    ("America/Glace_Bay", "Canada Atlantic - NS (Cape Breton)"),
+    (
+        "America/Goose_Bay",
+        "Canada Atlantic - Labrador (most areas)",
+    ),
+    ("America/Grand_Turk", "Turks & Caicos Is"),
+    ("America/Grenada", "Grenada"),
+    ("America/Guadeloupe", "Guadeloupe"),
+    ("America/Guatemala", "Guatemala"),
+    ("America/Guayaquil", "Ecuador Ecuador (mainland)"),
+    ("America/Guyana", "Guyana"),
+    (
+        "America/Halifax",
+        "Canada Atlantic - NS (most areas), PE Canada/Atlantic",
+    ),
+    ("America/Havana", "Cuba Cuba"),
+    ("America/Hermosillo", "Mexico Sonora"),
+    (
+        "America/Indiana/Indianapolis",
+        "United States Eastern - IN (most areas) US/East-Indiana America/Indianapolis America/Fort_Wayne",
+    ),
+    (
+        "America/Indiana/Knox",
+        "United States Central - IN (Starke) US/Indiana-Starke America/Knox_IN",
+    ),
+    (
+        "America/Indiana/Marengo",
+        "United States Eastern - IN (Crawford)",
+    ),
+    (
+        "America/Indiana/Petersburg",
+        "United States Eastern - IN (Pike)",
+    ),
+    (
+        "America/Indiana/Tell_City",
+        "United States Central - IN (Perry)",
+    ),
+    (
+        "America/Indiana/Vevay",
+        "United States Eastern - IN (Switzerland)",
+    ),
+    (
+        "America/Indiana/Vincennes",
+        "United States Eastern - IN (Da, Du, K, Mn)",
+    ),
+    (
+        "America/Indiana/Winamac",
+        "United States Eastern - IN (Pulaski)",
+    ),
+    ("America/Inuvik", "Canada Mountain - NT (west)"),
+    (
+        "America/Iqaluit",
+        "Canada Eastern - NU (most areas) America/Pangnirtung",
+    ),
+    ("America/Jamaica", "Jamaica Jamaica"),
+    ("America/Juneau", "United States Alaska - Juneau area"),
+    (
+        "America/Kentucky/Louisville",
+        "United States Eastern - KY (Louisville area) America/Louisville",
+    ),
+    (
+        "America/Kentucky/Monticello",
+        "United States Eastern - KY (Wayne)",
+    ),
+    ("America/Kralendijk", "Caribbean NL"),
+    ("America/La_Paz", "Bolivia"),
+    ("America/Lima", "Peru"),
+    (
+        "America/Los_Angeles",
+        "United States Pacific US/Pacific PST PDT Pacific",
+    ),
+    ("America/Lower_Princes", "St Maarten (Dutch)"),
+    ("America/Maceio", "Brazil Alagoas, Sergipe"),
+    ("America/Managua", "Nicaragua"),
+    ("America/Manaus", "Brazil Amazonas (east) Brazil/West"),
+    ("America/Marigot", "St Martin (French)"),
+    ("America/Martinique", "Martinique"),
+    (
+        "America/Matamoros",
+        "Mexico Coahuila, Nuevo Leon, Tamaulipas (US border)",
+    ),
+    (
+        "America/Mazatlan",
+        "Mexico Baja California Sur, Nayarit (most areas), Sinaloa Mexico/BajaSur",
+    ),
+    (
+        "America/Menominee",
+        "United States Central - MI (Wisconsin border)",
+    ),
+    ("America/Merida", "Mexico Campeche, Yucatan"),
+    (
+        "America/Metlakatla",
+        "United States Alaska - Annette Island",
+    ),
+    (
+        "America/Mexico_City",
+        "Mexico Central Mexico Mexico/General",
+    ),
+    ("America/Miquelon", "St Pierre & Miquelon"),
+    ("America/Moncton", "Canada Atlantic - New Brunswick"),
+    (
+        "America/Monterrey",
+        "Mexico Durango; Coahuila, Nuevo Leon, Tamaulipas (most areas)",
+    ),
+    ("America/Montevideo", "Uruguay"),
+    ("America/Montserrat", "Montserrat"),
+    ("America/Nassau", "Bahamas"),
+    (
+        "America/New_York",
+        "United States Eastern (most areas) US/Eastern EST EDT Eastern",
+    ),
+    ("America/Nome", "United States Alaska (west)"),
+    (
+        "America/Noronha",
+        "Brazil Atlantic islands Brazil/DeNoronha",
+    ),
+    (
+        "America/North_Dakota/Beulah",
+        "United States Central - ND (Mercer)",
+    ),
+    (
+        "America/North_Dakota/Center",
+        "United States Central - ND (Oliver)",
+    ),
+    (
+        "America/North_Dakota/New_Salem",
+        "United States Central - ND (Morton rural)",
+    ),
+    (
+        "America/Nuuk",
+        "Greenland most of Greenland America/Godthab",
+    ),
+    ("America/Ojinaga", "Mexico Chihuahua (US border - east)"),
+    ("America/Panama", "Panama America/Coral_Harbour"),
+    ("America/Paramaribo", "Suriname"),
+    (
+        "America/Phoenix",
+        "United States MST - AZ (except Navajo) US/Arizona",
+    ),
+    ("America/Port-au-Prince", "Haiti"),
+    ("America/Port_of_Spain", "Trinidad & Tobago"),
+    ("America/Porto_Velho", "Brazil Rondonia"),
+    (
+        "America/Puerto_Rico",
+        "Puerto Rico America/Kralendijk America/Lower_Princes America/Marigot America/St_Barthelemy America/Virgin",
+    ),
+    ("America/Punta_Arenas", "Chile Magallanes Region"),
+    ("America/Rankin_Inlet", "Canada Central - NU (central)"),
+    ("America/Recife", "Brazil Pernambuco"),
+    (
+        "America/Regina",
+        "Canada CST - SK (most areas) Canada/Saskatchewan",
+    ),
+    ("America/Resolute", "Canada Central - NU (Resolute)"),
+    (
+        "America/Rio_Branco",
+        "Brazil Acre Brazil/Acre America/Porto_Acre",
+    ),
+    ("America/Santarem", "Brazil Para (west)"),
+    ("America/Santiago", "Chile most of Chile Chile/Continental"),
+    ("America/Santo_Domingo", "Dominican Republic"),
+    (
+        "America/Sao_Paulo",
+        "Brazil Brazil (southeast: GO, DF, MG, ES, RJ, SP, PR, SC, RS) Brazil/East",
+    ),
+    (
+        "America/Scoresbysund",
+        "Greenland Scoresbysund/Ittoqqortoormiit",
+    ),
+    ("America/Sitka", "United States Alaska - Sitka area"),
+    ("America/St_Barthelemy", "St Barthelemy"),
+    (
+        "America/St_Johns",
+        "Canada Newfoundland, Labrador (SE) Canada/Newfoundland",
+    ),
+    ("America/St_Kitts", "St Kitts & Nevis"),
+    ("America/St_Lucia", "St Lucia"),
+    ("America/St_Thomas", "Virgin Islands (US)"),
+    ("America/St_Vincent", "St Vincent"),
+    ("America/Swift_Current", "Canada CST - SK (midwest)"),
+    ("America/Tegucigalpa", "Honduras"),
+    ("America/Thule", "Greenland Thule/Pituffik"),
+    (
+        "America/Tijuana",
+        "Mexico Baja California Mexico/BajaNorte America/Ensenada America/Santa_Isabel",
+    ),
+    (
+        "America/Toronto",
+        "Canada Eastern - ON & QC (most areas) Canada/Eastern America/Montreal America/Nipigon America/Thunder_Bay",
+    ),
+    ("America/Tortola", "Virgin Islands (UK)"),
+    (
+        "America/Vancouver",
+        "Canada Pacific - BC (most areas) Canada/Pacific",
+    ),
+    (
+        "America/Whitehorse",
+        "Canada MST - Yukon (east) Canada/Yukon",
+    ),
+    (
+        "America/Winnipeg",
+        "Canada Central - ON (west), Manitoba Canada/Central America/Rainy_River",
+    ),
+    ("America/Yakutat", "United States Alaska - Yakutat"),
+    ("Antarctica/Casey", "Antarctica Casey"),
+    ("Antarctica/Davis", "Antarctica Davis"),
+    ("Antarctica/DumontDUrville", "Antarctica Dumont-d'Urville"),
+    ("Antarctica/Macquarie", "Australia Macquarie Island"),
+    ("Antarctica/Mawson", "Antarctica Mawson"),
+    (
+        "Antarctica/McMurdo",
+        "Antarctica New Zealand time - McMurdo, South Pole",
+    ),
+    ("Antarctica/Palmer", "Antarctica Palmer"),
+    ("Antarctica/Rothera", "Antarctica Rothera"),
+    ("Antarctica/Syowa", "Antarctica Syowa"),
+    ("Antarctica/Troll", "Antarctica Troll"),
+    ("Antarctica/Vostok", "Antarctica Vostok"),
+    ("Arctic/Longyearbyen", "Svalbard & Jan Mayen"),
+    ("Asia/Aden", "Yemen"),
+    ("Asia/Almaty", "Kazakhstan most of Kazakhstan"),
+    ("Asia/Amman", "Jordan"),
+    ("Asia/Anadyr", "Russia MSK+09 - Bering Sea"),
+    ("Asia/Aqtau", "Kazakhstan Mangghystau/Mankistau"),
+    ("Asia/Aqtobe", "Kazakhstan Aqtobe/Aktobe"),
+    ("Asia/Ashgabat", "Turkmenistan Asia/Ashkhabad"),
+    ("Asia/Atyrau", "Kazakhstan Atyrau/Atirau/Gur'yev"),
+    ("Asia/Baghdad", "Iraq"),
+    ("Asia/Bahrain", "Bahrain"),
+    ("Asia/Baku", "Azerbaijan"),
+    ("Asia/Bangkok", "Thailand"),
+    ("Asia/Barnaul", "Russia MSK+04 - Altai"),
+    ("Asia/Beirut", "Lebanon"),
+    ("Asia/Bishkek", "Kyrgyzstan"),
+    ("Asia/Brunei", "Brunei"),
+    ("Asia/Chita", "Russia MSK+06 - Zabaykalsky"),
+    ("Asia/Colombo", "Sri Lanka"),
+    ("Asia/Damascus", "Syria"),
+    ("Asia/Dhaka", "Bangladesh Asia/Dacca"),
+    ("Asia/Dili", "East Timor"),
+    ("Asia/Dubai", "United Arab Emirates"),
+    ("Asia/Dushanbe", "Tajikistan"),
+    ("Asia/Famagusta", "Cyprus Northern Cyprus"),
+    ("Asia/Gaza", "Palestine Gaza Strip"),
+    ("Asia/Hebron", "Palestine West Bank"),
+    ("Asia/Ho_Chi_Minh", "Vietnam Asia/Saigon"),
+    ("Asia/Hong_Kong", "Hong Kong Hongkong"),
+    ("Asia/Hovd", "Mongolia Bayan-Olgii, Hovd, Uvs"),
+    ("Asia/Irkutsk", "Russia MSK+05 - Irkutsk, Buryatia"),
+    ("Asia/Jakarta", "Indonesia Java, Sumatra"),
+    (
+        "Asia/Jayapura",
+        "Indonesia New Guinea (West Papua / Irian Jaya), Malukus/Moluccas",
+    ),
+    ("Asia/Jerusalem", "Israel Israel Asia/Tel_Aviv"),
+    ("Asia/Kabul", "Afghanistan"),
+    ("Asia/Kamchatka", "Russia MSK+09 - Kamchatka"),
+    ("Asia/Karachi", "Pakistan"),
+    ("Asia/Kathmandu", "Nepal Asia/Katmandu"),
+    ("Asia/Khandyga", "Russia MSK+06 - Tomponsky, Ust-Maysky"),
+    ("Asia/Kolkata", "India Asia/Calcutta IST Calcutta"),
+    ("Asia/Krasnoyarsk", "Russia MSK+04 - Krasnoyarsk area"),
+    ("Asia/Kuala_Lumpur", "Malaysia Malaysia (peninsula)"),
+    ("Asia/Kuching", "Malaysia Sabah, Sarawak"),
+    ("Asia/Kuwait", "Kuwait"),
+    ("Asia/Macau", "Macau Asia/Macao"),
+    ("Asia/Magadan", "Russia MSK+08 - Magadan"),
+    (
+        "Asia/Makassar",
+        "Indonesia Borneo (east, south), Sulawesi/Celebes, Bali, Nusa Tengarra, Timor (west) Asia/Ujung_Pandang",
+    ),
+    ("Asia/Manila", "Philippines"),
+    ("Asia/Muscat", "Oman"),
+    ("Asia/Nicosia", "Cyprus most of Cyprus Europe/Nicosia"),
+    ("Asia/Novokuznetsk", "Russia MSK+04 - Kemerovo"),
+    ("Asia/Novosibirsk", "Russia MSK+04 - Novosibirsk"),
+    ("Asia/Omsk", "Russia MSK+03 - Omsk"),
+    ("Asia/Oral", "Kazakhstan West Kazakhstan"),
+    ("Asia/Phnom_Penh", "Cambodia"),
+    ("Asia/Pontianak", "Indonesia Borneo (west, central)"),
+    ("Asia/Pyongyang", "Korea (North)"),
+    ("Asia/Qatar", "Qatar"),
+    ("Asia/Qostanay", "Kazakhstan Qostanay/Kostanay/Kustanay"),
+    ("Asia/Qyzylorda", "Kazakhstan Qyzylorda/Kyzylorda/Kzyl-Orda"),
+    ("Asia/Riyadh", "Saudi Arabia"),
+    ("Asia/Sakhalin", "Russia MSK+08 - Sakhalin Island"),
+    ("Asia/Samarkand", "Uzbekistan Uzbekistan (west)"),
+    ("Asia/Seoul", "Korea (South) ROK"),
+    (
+        "Asia/Shanghai",
+        "China Beijing Time PRC Asia/Chongqing Asia/Harbin Asia/Chungking Beijing",
+    ),
+    ("Asia/Singapore", "Singapore Singapore"),
+    (
+        "Asia/Srednekolymsk",
+        "Russia MSK+08 - Sakha (E), N Kuril Is",
+    ),
+    ("Asia/Taipei", "Taiwan ROC"),
+    ("Asia/Tashkent", "Uzbekistan Uzbekistan (east)"),
+    ("Asia/Tbilisi", "Georgia"),
+    ("Asia/Tehran", "Iran Iran"),
+    ("Asia/Thimphu", "Bhutan Asia/Thimbu"),
+    ("Asia/Tokyo", "Japan Japan JST"),
+    ("Asia/Tomsk", "Russia MSK+04 - Tomsk"),
+    (
+        "Asia/Ulaanbaatar",
+        "Mongolia most of Mongolia Asia/Choibalsan Asia/Ulan_Bator",
+    ),
+    ("Asia/Urumqi", "China Xinjiang Time Asia/Kashgar"),
+    ("Asia/Ust-Nera", "Russia MSK+07 - Oymyakonsky"),
+    ("Asia/Vientiane", "Laos"),
+    ("Asia/Vladivostok", "Russia MSK+07 - Amur River"),
+    ("Asia/Yakutsk", "Russia MSK+06 - Lena River"),
+    ("Asia/Yangon", "Myanmar (Burma) Asia/Rangoon"),
+    ("Asia/Yekaterinburg", "Russia MSK+02 - Urals"),
+    ("Asia/Yerevan", "Armenia"),
+    ("Atlantic/Azores", "Portugal Azores"),
+    ("Atlantic/Bermuda", "Bermuda"),
+    ("Atlantic/Canary", "Spain Canary Islands"),
+    ("Atlantic/Cape_Verde", "Cape Verde"),
+    ("Atlantic/Faroe", "Faroe Islands Atlantic/Faeroe"),
+    ("Atlantic/Madeira", "Portugal Madeira Islands"),
+    ("Atlantic/Reykjavik", "Iceland"),
+    (
+        "Atlantic/South_Georgia",
+        "South Georgia & the South Sandwich Islands",
+    ),
+    ("Atlantic/St_Helena", "St Helena"),
+    ("Atlantic/Stanley", "Falkland Islands"),
+    (
+        "Australia/Adelaide",
+        "Australia South Australia Australia/South",
+    ),
+    (
+        "Australia/Brisbane",
+        "Australia Queensland (most areas) Australia/Queensland",
+    ),
+    (
+        "Australia/Broken_Hill",
+        "Australia New South Wales (Yancowinna) Australia/Yancowinna",
+    ),
+    (
+        "Australia/Darwin",
+        "Australia Northern Territory Australia/North",
+    ),
+    ("Australia/Eucla", "Australia Western Australia (Eucla)"),
+    (
+        "Australia/Hobart",
+        "Australia Tasmania Australia/Tasmania Australia/Currie",
+    ),
+    (
+        "Australia/Lindeman",
+        "Australia Queensland (Whitsunday Islands)",
+    ),
+    (
+        "Australia/Lord_Howe",
+        "Australia Lord Howe Island Australia/LHI",
+    ),
+    (
+        "Australia/Melbourne",
+        "Australia Victoria Australia/Victoria",
+    ),
+    (
+        "Australia/Perth",
+        "Australia Western Australia (most areas) Australia/West",
+    ),
+    (
+        "Australia/Sydney",
+        "Australia New South Wales (most areas) Australia/ACT Australia/NSW Australia/Canberra AEST AEDT",
+    ),
+    ("Europe/Amsterdam", "Netherlands"),
+    ("Europe/Andorra", "Andorra"),
+    ("Europe/Astrakhan", "Russia MSK+01 - Astrakhan"),
+    ("Europe/Athens", "Greece EET EEST"),
+    ("Europe/Belgrade", "Serbia Europe/Podgorica"),
+    (
+        "Europe/Berlin",
+        "Germany most of Germany Arctic/Longyearbyen Atlantic/Jan_Mayen CET CEST",
+    ),
+    ("Europe/Bratislava", "Slovakia"),
+    ("Europe/Brussels", "Belgium"),
+    ("Europe/Bucharest", "Romania"),
+    ("Europe/Budapest", "Hungary"),
+    ("Europe/Busingen", "Germany Busingen"),
+    ("Europe/Chisinau", "Moldova Europe/Tiraspol"),
+    ("Europe/Copenhagen", "Denmark"),
+    ("Europe/Dublin", "Ireland Eire"),
+    ("Europe/Gibraltar", "Gibraltar"),
+    ("Europe/Guernsey", "Guernsey"),
+    ("Europe/Helsinki", "Finland Europe/Mariehamn"),
+    ("Europe/Isle_of_Man", "Isle of Man"),
+    ("Europe/Istanbul", "Turkey Turkey Asia/Istanbul"),
+    ("Europe/Jersey", "Jersey"),
+    ("Europe/Kaliningrad", "Russia MSK-01 - Kaliningrad"),
+    ("Europe/Kirov", "Russia MSK+00 - Kirov"),
+    (
+        "Europe/Kyiv",
+        "Ukraine most of Ukraine Europe/Uzhgorod Europe/Zaporozhye Europe/Kiev",
+    ),
+    ("Europe/Lisbon", "Portugal Portugal (mainland) Portugal"),
+    ("Europe/Ljubljana", "Slovenia"),
+    (
+        "Europe/London",
+        "Britain (UK) GB GB-Eire Europe/Belfast BST GMT",
+    ),
+    ("Europe/Luxembourg", "Luxembourg"),
+    ("Europe/Madrid", "Spain Spain (mainland)"),
+    ("Europe/Malta", "Malta"),
+    ("Europe/Mariehamn", "Åland Islands"),
+    ("Europe/Minsk", "Belarus"),
+    ("Europe/Monaco", "Monaco"),
+    ("Europe/Moscow", "Russia MSK+00 - Moscow area W-SU"),
+    ("Europe/Oslo", "Norway"),
+    ("Europe/Paris", "France"),
+    ("Europe/Podgorica", "Montenegro"),
+    ("Europe/Prague", "Czech Republic Europe/Bratislava"),
+    ("Europe/Riga", "Latvia"),
+    ("Europe/Rome", "Italy Europe/San_Marino Europe/Vatican"),
+    ("Europe/Samara", "Russia MSK+01 - Samara, Udmurtia"),
+    ("Europe/San_Marino", "San Marino"),
+    ("Europe/Sarajevo", "Bosnia & Herzegovina"),
+    ("Europe/Saratov", "Russia MSK+01 - Saratov"),
+    ("Europe/Simferopol", "Ukraine Crimea"),
+    ("Europe/Skopje", "North Macedonia"),
+    ("Europe/Sofia", "Bulgaria"),
+    ("Europe/Stockholm", "Sweden"),
+    ("Europe/Tallinn", "Estonia"),
+    ("Europe/Tirane", "Albania"),
+    ("Europe/Ulyanovsk", "Russia MSK+01 - Ulyanovsk"),
+    ("Europe/Vaduz", "Liechtenstein"),
+    ("Europe/Vatican", "Vatican City"),
+    ("Europe/Vienna", "Austria"),
+    ("Europe/Vilnius", "Lithuania"),
+    ("Europe/Volgograd", "Russia MSK+00 - Volgograd"),
+    ("Europe/Warsaw", "Poland Poland"),
+    ("Europe/Zagreb", "Croatia"),
+    ("Europe/Zurich", "Switzerland Europe/Busingen"),
+    ("Indian/Antananarivo", "Madagascar"),
+    ("Indian/Chagos", "British Indian Ocean Territory"),
+    ("Indian/Christmas", "Christmas Island"),
+    ("Indian/Cocos", "Cocos (Keeling) Islands"),
+    ("Indian/Comoro", "Comoros"),
+    ("Indian/Kerguelen", "French S. Terr."),
+    ("Indian/Mahe", "Seychelles"),
+    ("Indian/Maldives", "Maldives"),
+    ("Indian/Mauritius", "Mauritius"),
+    ("Indian/Mayotte", "Mayotte"),
+    ("Indian/Reunion", "Réunion"),
+    ("Pacific/Apia", "Samoa (western)"),
+    (
+        "Pacific/Auckland",
+        "New Zealand most of New Zealand NZ Antarctica/South_Pole",
+    ),
+    ("Pacific/Bougainville", "Papua New Guinea Bougainville"),
+    ("Pacific/Chatham", "New Zealand Chatham Islands NZ-CHAT"),
+    ("Pacific/Chuuk", "Micronesia Chuuk/Truk, Yap"),
+    ("Pacific/Easter", "Chile Easter Island Chile/EasterIsland"),
+    ("Pacific/Efate", "Vanuatu"),
+    ("Pacific/Fakaofo", "Tokelau"),
+    ("Pacific/Fiji", "Fiji"),
+    ("Pacific/Funafuti", "Tuvalu"),
+    ("Pacific/Galapagos", "Ecuador Galapagos Islands"),
+    ("Pacific/Gambier", "French Polynesia Gambier Islands"),
+    ("Pacific/Guadalcanal", "Solomon Islands Pacific/Ponape"),
+    ("Pacific/Guam", "Guam"),
+    (
+        "Pacific/Honolulu",
+        "United States Hawaii US/Hawaii Pacific/Johnston HST Hawaii",
+    ),
+    (
+        "Pacific/Kanton",
+        "Kiribati Phoenix Islands Pacific/Enderbury",
+    ),
+    ("Pacific/Kiritimati", "Kiribati Line Islands"),
+    ("Pacific/Kosrae", "Micronesia Kosrae"),
+    ("Pacific/Kwajalein", "Marshall Islands Kwajalein Kwajalein"),
+    (
+        "Pacific/Majuro",
+        "Marshall Islands most of Marshall Islands",
+    ),
+    ("Pacific/Marquesas", "French Polynesia Marquesas Islands"),
+    ("Pacific/Midway", "US minor outlying islands Midway Islands"),
+    ("Pacific/Nauru", "Nauru"),
+    ("Pacific/Niue", "Niue"),
+    ("Pacific/Norfolk", "Norfolk Island"),
+    ("Pacific/Noumea", "New Caledonia"),
+    (
+        "Pacific/Pago_Pago",
+        "Samoa (American) US/Samoa Pacific/Samoa",
+    ),
+    ("Pacific/Palau", "Palau"),
+    ("Pacific/Pitcairn", "Pitcairn"),
+    ("Pacific/Pohnpei", "Micronesia Pohnpei/Ponape"),
+    (
+        "Pacific/Port_Moresby",
+        "Papua New Guinea most of Papua New Guinea Pacific/Truk Pacific/Yap",
+    ),
+    ("Pacific/Rarotonga", "Cook Islands"),
+    ("Pacific/Saipan", "Northern Mariana Islands"),
+    ("Pacific/Tahiti", "French Polynesia Society Islands"),
+    ("Pacific/Tarawa", "Kiribati Gilbert Islands"),
+    ("Pacific/Tongatapu", "Tonga"),
+    ("Pacific/Wake", "US minor outlying islands Wake Island"),
+    ("Pacific/Wallis", "Wallis & Futuna"),
+];
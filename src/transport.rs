@@ -0,0 +1,98 @@
+// src/transport.rs
+
+// Where prompts go and where answers come from. Normally that's the terminal
+// the program runs in, but a session can hand both to a transport instead -
+// a socket, an SSH channel, a WebSocket - so a server can ask a remote
+// operator the same questions a CLI would ask its user.
+//
+// Transports are line based: prompts are written as text and answers read
+// back one line each. The line editor, arrow-key menus and spinners need a
+// local terminal, so prompts fall back to their plain numbered versions.
+
+use crate::Terminal;
+use std::io::{self, BufRead, BufReader, Read, Write};
+
+/// Something prompts can be served over
+///
+/// Everything a prompt prints goes through `write`, and each answer is one
+/// `read_line`. Set one on a [`Session`](crate::Session) with
+/// [`Session::transport`](crate::Session::transport).
+///
+/// [`Terminal`] is the one prompts use by default, and [`StreamTransport`]
+/// covers anything with a reader and a writer, like a `TcpStream`.
+pub trait Transport: Send {
+    /// Show prompt text: the question, menus, errors and other output
+    fn write(&mut self, text: &str, to_stderr: bool) -> io::Result<()>;
+
+    /// The next answer, without its line ending. `None` once the other end
+    /// has closed.
+    ///
+    /// `secret` is set for passwords, for transports that can stop the other
+    /// end from echoing them.
+    fn read_line(&mut self, secret: bool) -> io::Result<Option<String>>;
+}
+
+/// Stdout and stderr out, stdin in, with typing hidden for secrets
+impl Transport for Terminal {
+    fn write(&mut self, text: &str, to_stderr: bool) -> io::Result<()> {
+        write_local(text, to_stderr)
+    }
+
+    fn read_line(&mut self, secret: bool) -> io::Result<Option<String>> {
+        crate::core::read_stdin(secret, str::to_string)
+    }
+}
+
+/// Prompts over any reader and writer, one line per answer
+///
+/// ```no_run
+/// use std::net::TcpListener;
+/// use velvetio::{Session, StreamTransport, Theme};
+///
+/// let listener = TcpListener::bind("127.0.0.1:7000")?;
+/// let (stream, _) = listener.accept()?;
+/// let transport = StreamTransport::new(stream.try_clone()?, stream);
+/// let mut session = Session::new().theme(Theme::plain()).transport(transport);
+/// let port: u16 = session.try_ask("Port")?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct StreamTransport<R, W> {
+    reader: BufReader<R>,
+    writer: W,
+}
+
+impl<R: Read, W: Write> StreamTransport<R, W> {
+    pub fn new(reader: R, writer: W) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+            writer,
+        }
+    }
+}
+
+impl<R: Read + Send, W: Write + Send> Transport for StreamTransport<R, W> {
+    fn write(&mut self, text: &str, _to_stderr: bool) -> io::Result<()> {
+        self.writer.write_all(text.as_bytes())?;
+        self.writer.flush()
+    }
+
+    fn read_line(&mut self, _secret: bool) -> io::Result<Option<String>> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(line.trim_end_matches(['\n', '\r']).to_string()))
+    }
+}
+
+// Prompt text to this process's stdout or stderr
+pub(crate) fn write_local(text: &str, to_stderr: bool) -> io::Result<()> {
+    match to_stderr {
+        true => io::stderr().lock().write_all(text.as_bytes()),
+        false => {
+            let mut out = io::stdout().lock();
+            out.write_all(text.as_bytes())?;
+            out.flush()
+        }
+    }
+}
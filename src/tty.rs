@@ -0,0 +1,188 @@
+// src/tty.rs
+
+// Low-level terminal control. We shell out to `stty` instead of pulling in
+// libc/termios bindings - it's on every Unix box and keeps us dependency free.
+// Elsewhere (Windows, WASI) there's no stty and no way to run it, so prompts
+// stay in line mode.
+//
+// Raw mode turns off the terminal's signal keys, so Ctrl+Z reaches the
+// editor as a key. It suspends the program itself (with `kill`, for the same
+// reason) after putting the terminal back, and picks up where it was once
+// the shell continues it.
+
+use std::io::{self, IsTerminal};
+use std::sync::{Mutex, Once};
+
+// Settings from before the outermost active guard, for restoring on a panic
+// or an exit that skips the guard's drop
+static ORIGINAL: Mutex<Option<String>> = Mutex::new(None);
+static PANIC_HOOK: Once = Once::new();
+
+// Scripted and headless runs are never on a terminal, whatever they're
+// really attached to
+pub(crate) fn stdin_is_tty() -> bool {
+    io::stdin().is_terminal() && local()
+}
+
+pub(crate) fn stdout_is_tty() -> bool {
+    io::stdout().is_terminal() && local()
+}
+
+pub(crate) fn stderr_is_tty() -> bool {
+    io::stderr().is_terminal() && local()
+}
+
+// Prompts are talking to this terminal, not a script, headless input or a
+// session's transport
+fn local() -> bool {
+    !crate::script::active() && !crate::is_headless() && !crate::session::has_transport()
+}
+
+/// Changes terminal settings until dropped
+///
+/// Does nothing when stdin isn't a terminal or `stty` isn't available
+/// (Windows, WASI), so the answer is still read, just visibly.
+pub(crate) struct ModeGuard {
+    saved: Option<String>,
+    args: &'static [&'static str],
+}
+
+impl ModeGuard {
+    /// Typing isn't echoed, lines are still edited by the terminal
+    pub(crate) fn hide() -> Self {
+        Self::set(&["-echo"])
+    }
+
+    /// Every key comes straight to us, Ctrl+C included
+    pub(crate) fn raw() -> Self {
+        Self::set(&["-icanon", "-echo", "-isig", "min", "1", "time", "0"])
+    }
+
+    fn set(args: &'static [&'static str]) -> Self {
+        if !stdin_is_tty() {
+            return Self { saved: None, args };
+        }
+
+        let saved = stty(&["-g"]).filter(|_| stty(args).is_some());
+        if let Some(saved) = &saved {
+            lock().get_or_insert_with(|| saved.clone());
+            install_panic_hook();
+        }
+        Self { saved, args }
+    }
+
+    pub(crate) fn is_active(&self) -> bool {
+        self.saved.is_some()
+    }
+
+    /// Stop the program like Ctrl+Z would, with the terminal the way the
+    /// shell expects it, and switch this mode back on once it's continued
+    ///
+    /// Returns after the program is resumed (`fg`), so the caller just
+    /// redraws its prompt.
+    pub(crate) fn suspend(&self) {
+        if !self.is_active() {
+            return;
+        }
+        let original = lock().clone();
+        if let Some(original) = original {
+            let _ = stty(&[original.as_str()]);
+        }
+        stop();
+        let _ = stty(self.args);
+    }
+}
+
+impl Drop for ModeGuard {
+    fn drop(&mut self) {
+        if let Some(saved) = self.saved.take() {
+            let _ = stty(&[saved.as_str()]);
+            let mut original = lock();
+            if original.as_deref() == Some(saved.as_str()) {
+                *original = None;
+            }
+        }
+    }
+}
+
+/// Put the terminal back the way it was before a prompt changed it
+///
+/// Runs automatically when a prompt finishes, on panics, and before VelvetIO
+/// exits the program. Call it yourself before exiting from inside a prompt
+/// some other way, like a Ctrl+C handler calling `std::process::exit`.
+pub fn restore_terminal() {
+    if let Some(original) = lock().take() {
+        let _ = stty(&[original.as_str()]);
+    }
+}
+
+// Restores before the previous hook prints, so the message isn't mangled by
+// raw mode. Runs even with `panic = "abort"`, where guards never drop.
+fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            restore_terminal();
+            previous(info);
+        }));
+    });
+}
+
+fn lock() -> std::sync::MutexGuard<'static, Option<String>> {
+    ORIGINAL
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// Runs stty against our terminal, returning its trimmed output on success
+#[cfg(unix)]
+fn stty(args: &[&str]) -> Option<String> {
+    use std::process::{Command, Stdio};
+
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    if output.status.success() {
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        None
+    }
+}
+
+#[cfg(not(unix))]
+fn stty(_args: &[&str]) -> Option<String> {
+    None
+}
+
+// SIGTSTP to ourselves. `kill` is waited on, and we're stopped before it
+// exits, so this returns once we've been continued.
+#[cfg(unix)]
+fn stop() {
+    use std::process::{Command, Stdio};
+
+    let _ = Command::new("kill")
+        .args(["-TSTP", &std::process::id().to_string()])
+        .stderr(Stdio::null())
+        .status();
+}
+
+#[cfg(not(unix))]
+fn stop() {}
+
+/// Terminal size as (rows, columns), if stdin is a terminal we can ask
+pub(crate) fn size() -> Option<(usize, usize)> {
+    if !stdin_is_tty() {
+        return None;
+    }
+
+    let output = stty(&["size"])?;
+    let mut parts = output.split_whitespace().map(|n| n.parse::<usize>().ok());
+    match (parts.next()??, parts.next()??) {
+        (rows, cols) if rows > 0 && cols > 0 => Some((rows, cols)),
+        _ => None,
+    }
+}
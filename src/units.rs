@@ -0,0 +1,148 @@
+// src/units.rs
+
+// Numbers with units. The prompt says which unit it wants, answers can use any
+// unit of the same kind, and the value comes back converted. Sizes are binary
+// (1GB = 1024MB), since that's what memory and disk limits usually mean.
+
+use crate::core::{PromptOptions, ask_loop, give_up};
+use crate::{AttemptPolicy, MAX_ATTEMPTS, Parse, Result, VelvetIOError};
+
+// Each kind of unit, with how many base units each name is worth
+const KINDS: &[Kind] = &[
+    Kind {
+        summary: "B, KB, MB, GB, TB",
+        units: &[
+            ("b", 1.0),
+            ("bytes", 1.0),
+            ("k", KIB),
+            ("kb", KIB),
+            ("kib", KIB),
+            ("m", MIB),
+            ("mb", MIB),
+            ("mib", MIB),
+            ("g", GIB),
+            ("gb", GIB),
+            ("gib", GIB),
+            ("t", TIB),
+            ("tb", TIB),
+            ("tib", TIB),
+        ],
+    },
+    Kind {
+        summary: "ms, s, min, h, d",
+        units: &[
+            ("ms", 0.001),
+            ("s", 1.0),
+            ("sec", 1.0),
+            ("secs", 1.0),
+            ("seconds", 1.0),
+            ("m", 60.0),
+            ("min", 60.0),
+            ("mins", 60.0),
+            ("minutes", 60.0),
+            ("h", 3600.0),
+            ("hr", 3600.0),
+            ("hours", 3600.0),
+            ("d", 86400.0),
+            ("days", 86400.0),
+        ],
+    },
+];
+
+struct Kind {
+    // The usual names, for error messages
+    summary: &'static str,
+    units: &'static [(&'static str, f64)],
+}
+
+const KIB: f64 = 1024.0;
+const MIB: f64 = KIB * 1024.0;
+const GIB: f64 = MIB * 1024.0;
+const TIB: f64 = GIB * 1024.0;
+
+/// Ask for an amount in `unit`, shown as `Memory limit (MB)`
+///
+/// A plain number is taken to be in `unit`. Other units of the same kind are
+/// converted, so asking in MB and getting "2GB" returns 2048. Known kinds are
+/// sizes (B, KB, MB, GB, TB) and durations (ms, s, min, h, d); any other unit
+/// only accepts plain numbers or its own name.
+///
+/// Exits the program if input is closed before a valid answer arrives.
+///
+/// ```no_run
+/// let memory = velvetio::ask_with_unit("Memory limit", "MB");
+/// let timeout = velvetio::ask_with_unit("Timeout", "s");
+/// ```
+pub fn ask_with_unit(prompt: &str, unit: &str) -> f64 {
+    unit_loop(prompt, unit, &PromptOptions::default()).unwrap_or_else(|e| give_up(e))
+}
+
+/// `ask_with_unit` that gives up after `MAX_ATTEMPTS` invalid answers
+pub fn try_ask_with_unit(prompt: &str, unit: &str) -> Result<f64> {
+    let options = PromptOptions::with_policy(AttemptPolicy::limited(MAX_ATTEMPTS));
+    unit_loop(prompt, unit, &options)
+}
+
+fn unit_loop(prompt: &str, unit: &str, options: &PromptOptions) -> Result<f64> {
+    let display = format!("{} ({})", prompt, unit);
+    let error_msg = match kind_of(unit) {
+        Some(kind) => format!("Use a number of {}, or add a unit ({})", unit, kind.summary),
+        None => format!("Use a number of {}", unit),
+    };
+
+    let converts = |amount: &Amount| convert(amount, unit).is_some();
+    let (amount, _) = ask_loop(&display, None, &converts, &error_msg, options)?;
+    Ok(convert(&amount, unit).expect("checked by the validator"))
+}
+
+// A number with whatever unit was typed after it
+pub(crate) struct Amount {
+    value: f64,
+    unit: String,
+}
+
+impl Parse for Amount {
+    fn parse(input: &str) -> Result<Self> {
+        let trimmed = input.trim();
+        let split = trimmed
+            .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | '_')))
+            .unwrap_or(trimmed.len());
+        let (number, unit) = trimmed.split_at(split);
+
+        match number.replace('_', "").parse::<f64>() {
+            Ok(value) if value.is_finite() => Ok(Amount {
+                value,
+                unit: unit.trim().to_string(),
+            }),
+            _ => Err(VelvetIOError::parse_error(input, Self::type_name())),
+        }
+    }
+
+    fn type_name() -> &'static str {
+        "number with an optional unit"
+    }
+}
+
+// `amount` in `unit`, if the two are the same kind (or it had no unit)
+pub(crate) fn convert(amount: &Amount, unit: &str) -> Option<f64> {
+    if amount.unit.is_empty() || amount.unit.eq_ignore_ascii_case(unit) {
+        return Some(amount.value);
+    }
+
+    let kind = kind_of(unit)?;
+    let factor = |name: &str| {
+        kind.units
+            .iter()
+            .find(|(n, _)| name.eq_ignore_ascii_case(n))
+            .map(|(_, factor)| *factor)
+    };
+    Some(amount.value * factor(&amount.unit)? / factor(unit)?)
+}
+
+fn kind_of(unit: &str) -> Option<&'static Kind> {
+    KINDS.iter().find(|kind| {
+        kind.units
+            .iter()
+            .any(|(name, _)| unit.eq_ignore_ascii_case(name))
+    })
+}
@@ -1,6 +1,93 @@
 // src/validators.rs
 
+// Validators are checks on a parsed answer. Any `Fn(&T) -> bool` is one; the
+// `_described` versions of the built-in ones also say what they check, and
+// prompts show that as a hint and use it as the error when none is given, so
+// the same rule isn't written out twice. The plain ones stay closures.
+
+use std::fmt::Display;
+use std::ops::Deref;
+
+/// A check on an answer
+///
+/// Closures are validators already. [`describe`](Self::describe) is what a
+/// prompt shows as a hint, and says when an answer fails without an `error:`
+/// of its own.
+///
+/// ```
+/// use velvetio::{Validator, and_described, max_length_described, min_length_described};
+///
+/// let name = and_described(min_length_described(3), max_length_described(20));
+/// assert!(name.check(&"velvet".to_string()));
+/// assert_eq!(name.describe().unwrap(), "at least 3 characters, at most 20 characters");
+/// // A closure doesn't know what it checks
+/// let email = and_described(min_length_described(3), |s: &String| s.contains('@'));
+/// assert_eq!(email.describe(), None);
+/// ```
+pub trait Validator<T> {
+    /// Whether `value` passes
+    fn check(&self, value: &T) -> bool;
+
+    /// What passes, in a few lowercase words like "at most 20 characters".
+    /// `None` when there's no saying.
+    fn describe(&self) -> Option<String> {
+        None
+    }
+}
+
+impl<T, F: Fn(&T) -> bool> Validator<T> for F {
+    fn check(&self, value: &T) -> bool {
+        self(value)
+    }
+}
+
+/// A validator that knows what it checks. It can still be called like the
+/// closure inside it.
+#[derive(Clone)]
+pub struct Rule<F> {
+    check: F,
+    description: Option<String>,
+}
+
+impl<F> Deref for Rule<F> {
+    type Target = F;
+
+    fn deref(&self) -> &F {
+        &self.check
+    }
+}
+
+impl<T, F: Fn(&T) -> bool> Validator<T> for Rule<F> {
+    fn check(&self, value: &T) -> bool {
+        (self.check)(value)
+    }
+
+    fn describe(&self) -> Option<String> {
+        self.description.clone()
+    }
+}
+
+/// Give a closure a description, for the hint and the error
+///
+/// ```
+/// use velvetio::{Validator, described};
+///
+/// let handle = described(
+///     |s: &String| s.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()),
+///     "lowercase letters and digits only",
+/// );
+/// assert!(!handle.check(&"Velvet".to_string()));
+/// ```
+pub fn described<T, F: Fn(&T) -> bool>(validator: F, description: &str) -> Rule<F> {
+    Rule {
+        check: validator,
+        description: Some(description.to_string()),
+    }
+}
+
 /// String is not empty after trimming
+// Takes &String so it can be passed straight to `validate:` for String prompts
+#[allow(clippy::ptr_arg)]
 pub fn not_empty(s: &String) -> bool {
     !s.trim().is_empty()
 }
@@ -10,11 +97,21 @@ pub fn min_length(min: usize) -> impl Fn(&String) -> bool {
     move |s: &String| s.len() >= min
 }
 
+/// `min_length` that says what it checks
+pub fn min_length_described(min: usize) -> Rule<impl Fn(&String) -> bool> {
+    described(min_length(min), &format!("at least {} characters", min))
+}
+
 /// String has at most max characters
 pub fn max_length(max: usize) -> impl Fn(&String) -> bool {
     move |s: &String| s.len() <= max
 }
 
+/// `max_length` that says what it checks
+pub fn max_length_described(max: usize) -> Rule<impl Fn(&String) -> bool> {
+    described(max_length(max), &format!("at most {} characters", max))
+}
+
 /// Number is positive (> 0)
 pub fn is_positive<T: PartialOrd + Default>(n: &T) -> bool {
     *n > T::default()
@@ -25,6 +122,14 @@ pub fn in_range<T: PartialOrd + Copy>(min: T, max: T) -> impl Fn(&T) -> bool {
     move |n: &T| *n >= min && *n <= max
 }
 
+/// `in_range` that says what it checks
+pub fn in_range_described<T: PartialOrd + Copy + Display>(
+    min: T,
+    max: T,
+) -> Rule<impl Fn(&T) -> bool> {
+    described(in_range(min, max), &format!("between {} and {}", min, max))
+}
+
 /// Both validators must pass
 pub fn and<T, F1, F2>(validator1: F1, validator2: F2) -> impl Fn(&T) -> bool
 where
@@ -34,6 +139,23 @@ where
     move |value: &T| validator1(value) && validator2(value)
 }
 
+/// `and` for validators that say what they check
+pub fn and_described<T, V1, V2>(validator1: V1, validator2: V2) -> Rule<impl Fn(&T) -> bool>
+where
+    V1: Validator<T>,
+    V2: Validator<T>,
+{
+    // Half a description would leave out what the other half checks
+    let description = match (validator1.describe(), validator2.describe()) {
+        (Some(first), Some(second)) => Some(format!("{}, {}", first, second)),
+        _ => None,
+    };
+    Rule {
+        check: move |value: &T| validator1.check(value) && validator2.check(value),
+        description,
+    }
+}
+
 /// Either validator can pass
 pub fn or<T, F1, F2>(validator1: F1, validator2: F2) -> impl Fn(&T) -> bool
 where
@@ -43,6 +165,42 @@ where
     move |value: &T| validator1(value) || validator2(value)
 }
 
+/// `or` for validators that say what they check
+pub fn or_described<T, V1, V2>(validator1: V1, validator2: V2) -> Rule<impl Fn(&T) -> bool>
+where
+    V1: Validator<T>,
+    V2: Validator<T>,
+{
+    let description = match (validator1.describe(), validator2.describe()) {
+        (Some(first), Some(second)) => Some(format!("{}, or {}", first, second)),
+        _ => None,
+    };
+    Rule {
+        check: move |value: &T| validator1.check(value) || validator2.check(value),
+        description,
+    }
+}
+
+// What a prompt says when `validator` fails: the error it was given, or what
+// the validator checks, or a shrug
+pub(crate) fn error_message<T>(validator: &impl Validator<T>, error: Option<&str>) -> String {
+    if let Some(error) = error {
+        return error.to_string();
+    }
+    match validator.describe() {
+        Some(description) => capitalized(&description),
+        None => "Invalid input, please try again".to_string(),
+    }
+}
+
+pub(crate) fn capitalized(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
 // Custom validator examples:
 //
 // Email: |s: &String| s.contains('@') && s.contains('.')
@@ -0,0 +1,210 @@
+// src/version.rs
+
+// Semantic versions, for release tooling. Only what picking the next version
+// needs: parsing, ordering and the three bumps.
+
+use crate::core::{PromptOptions, ask_loop, choose_inner, give_up};
+use crate::{AttemptPolicy, Choice, MAX_ATTEMPTS, Parse, Result, VelvetIOError};
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A semantic version like `1.4.2` or `2.0.0-rc.1`
+///
+/// A leading `v` is fine when parsing. Versions order the semver way, so
+/// `2.0.0-rc.1` comes before `2.0.0`; build metadata only breaks ties.
+///
+/// ```
+/// use velvetio::{Parse, Version};
+///
+/// let current = Version::parse("v1.4.2").unwrap();
+/// assert_eq!(current.bump_minor().to_string(), "1.5.0");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    /// Pre-release tag, like `rc.1` - empty for a release
+    pub pre: String,
+    /// Build metadata, like `build.5` - usually empty
+    pub build: String,
+}
+
+impl Version {
+    pub fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            pre: String::new(),
+            build: String::new(),
+        }
+    }
+
+    /// `1.4.2` to `1.4.3`. A pre-release like `1.4.3-rc.1` becomes `1.4.3`.
+    pub fn bump_patch(&self) -> Self {
+        match self.pre.is_empty() {
+            true => Self::new(self.major, self.minor, self.patch + 1),
+            false => Self::new(self.major, self.minor, self.patch),
+        }
+    }
+
+    /// `1.4.2` to `1.5.0`. A pre-release like `1.5.0-rc.1` becomes `1.5.0`.
+    pub fn bump_minor(&self) -> Self {
+        match self.pre.is_empty() || self.patch != 0 {
+            true => Self::new(self.major, self.minor + 1, 0),
+            false => Self::new(self.major, self.minor, 0),
+        }
+    }
+
+    /// `1.4.2` to `2.0.0`. A pre-release like `2.0.0-rc.1` becomes `2.0.0`.
+    pub fn bump_major(&self) -> Self {
+        match self.pre.is_empty() || self.minor != 0 || self.patch != 0 {
+            true => Self::new(self.major + 1, 0, 0),
+            false => Self::new(self.major, 0, 0),
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() {
+            write!(f, "-{}", self.pre)?;
+        }
+        if !self.build.is_empty() {
+            write!(f, "+{}", self.build)?;
+        }
+        Ok(())
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => compare_pre(&self.pre, &other.pre),
+            })
+            .then_with(|| self.build.cmp(&other.build))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Dot-separated parts left to right: numbers compare as numbers and sort
+// before words, and a shorter tag sorts first when the rest match
+fn compare_pre(a: &str, b: &str) -> Ordering {
+    let mut a_parts = a.split('.');
+    let mut b_parts = b.split('.');
+    loop {
+        let order = match (a_parts.next(), b_parts.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => match (x.parse::<u64>(), y.parse::<u64>()) {
+                (Ok(x), Ok(y)) => x.cmp(&y),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => x.cmp(y),
+            },
+        };
+        if order != Ordering::Equal {
+            return order;
+        }
+    }
+}
+
+impl Parse for Version {
+    fn parse(input: &str) -> Result<Self> {
+        let invalid = || VelvetIOError::parse_error(input, "version, like 1.4.2");
+        let text = input.trim();
+        let text = text.strip_prefix(['v', 'V']).unwrap_or(text);
+
+        let (text, build) = text.split_once('+').unwrap_or((text, ""));
+        let (core, pre) = text.split_once('-').unwrap_or((text, ""));
+        let tag_ok = |tag: &str| {
+            tag.split('.').all(|part| {
+                !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            })
+        };
+        if (text.contains('-') && !tag_ok(pre)) || (input.contains('+') && !tag_ok(build)) {
+            return Err(invalid());
+        }
+
+        let numbers: Vec<u64> = core
+            .split('.')
+            .map(|n| match n.len() > 1 && n.starts_with('0') {
+                true => None,
+                false => n.parse().ok(),
+            })
+            .collect::<Option<_>>()
+            .ok_or_else(invalid)?;
+        match numbers[..] {
+            [major, minor, patch] => Ok(Version {
+                pre: pre.to_string(),
+                build: build.to_string(),
+                ..Version::new(major, minor, patch)
+            }),
+            _ => Err(invalid()),
+        }
+    }
+
+    fn type_name() -> &'static str {
+        "version"
+    }
+}
+
+/// Offer the next patch, minor or major version after `current`, or a custom
+/// one, and return the pick
+///
+/// Enter picks the patch release. A custom version has to come after `current`.
+///
+/// Exits the program if input is closed before a valid answer arrives.
+///
+/// ```no_run
+/// use velvetio::{Parse, Version};
+///
+/// let current = Version::parse("1.4.2").unwrap();
+/// let next = velvetio::ask_version_bump(&current);
+/// // Next version (current 1.4.2):
+/// //   1. patch  1.4.3
+/// //   2. minor  1.5.0
+/// //   3. major  2.0.0
+/// //   4. custom
+/// ```
+pub fn ask_version_bump(current: &Version) -> Version {
+    bump_loop(current, AttemptPolicy::unlimited()).unwrap_or_else(|e| give_up(e))
+}
+
+/// `ask_version_bump` that gives up after `MAX_ATTEMPTS` invalid answers
+pub fn try_ask_version_bump(current: &Version) -> Result<Version> {
+    bump_loop(current, AttemptPolicy::limited(MAX_ATTEMPTS))
+}
+
+fn bump_loop(current: &Version, policy: AttemptPolicy) -> Result<Version> {
+    let _turn = crate::lock::hold();
+    let bump = |name: &str, next: Version| Choice::new(format!("{}  {}", name, next), Some(next));
+    let choices = [
+        bump("patch", current.bump_patch()),
+        bump("minor", current.bump_minor()),
+        bump("major", current.bump_major()),
+        Choice::new("custom", None),
+    ];
+
+    let prompt = format!("Next version (current {})", current);
+    if let Some(next) = choose_inner(&prompt, &choices, Some(0), &policy)?.into_value() {
+        return Ok(next);
+    }
+    let after = |version: &Version| version > current;
+    let error_msg = format!("Pick a version after {}", current);
+    let options = PromptOptions::with_policy(policy);
+    ask_loop("Version", None, &after, &error_msg, &options).map(|(version, _)| version)
+}
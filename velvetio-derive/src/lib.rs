@@ -0,0 +1,158 @@
+// velvetio-derive/src/lib.rs
+
+//! Derive macros for VelvetIO. Use them through `velvetio` with the `derive`
+//! feature rather than depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Attribute, Data, DeriveInput, Fields, LitStr, parse_macro_input};
+
+/// Build an enum by picking a variant from a menu, then asking for each of
+/// its fields
+///
+/// Variants are listed by name (`DryRun` shows as "Dry run") and fields are
+/// asked for by name with their `Parse` impls, under the variant's label as
+/// context. Fields of tuple variants are "Value", or "Value 1 of 2" and so on.
+/// Override either with
+/// `#[interactive(label = "...")]` on a variant or
+/// `#[interactive(prompt = "...")]` on a field.
+#[proc_macro_derive(Interactive, attributes(interactive))]
+pub fn derive_interactive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "Interactive can only be derived for enums",
+        ));
+    };
+    if data.variants.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "Interactive needs at least one variant to choose from",
+        ));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut labels = Vec::new();
+    let mut arms = Vec::new();
+    for (index, variant) in data.variants.iter().enumerate() {
+        let ident = &variant.ident;
+        let label =
+            attribute(&variant.attrs, "label")?.unwrap_or_else(|| words(&ident.to_string()));
+
+        let build = match &variant.fields {
+            Fields::Unit => quote!(#name::#ident),
+            Fields::Named(fields) => {
+                let mut values = Vec::new();
+                for field in &fields.named {
+                    let field_ident = field.ident.as_ref().expect("named field");
+                    let prompt = attribute(&field.attrs, "prompt")?
+                        .unwrap_or_else(|| sentence(&field_ident.to_string()));
+                    let ty = &field.ty;
+                    values.push(quote! {
+                        #field_ident: ::velvetio::__private::field::<#ty>(#prompt, policy)?
+                    });
+                }
+                quote!(#name::#ident { #(#values),* })
+            }
+            Fields::Unnamed(fields) => {
+                let count = fields.unnamed.len();
+                let mut values = Vec::new();
+                for (position, field) in fields.unnamed.iter().enumerate() {
+                    // The variant's label is already shown as the context
+                    let prompt = attribute(&field.attrs, "prompt")?.unwrap_or_else(|| {
+                        if count == 1 {
+                            "Value".to_string()
+                        } else {
+                            format!("Value {} of {}", position + 1, count)
+                        }
+                    });
+                    let ty = &field.ty;
+                    values.push(quote! {
+                        ::velvetio::__private::field::<#ty>(#prompt, policy)?
+                    });
+                }
+                quote!(#name::#ident(#(#values),*))
+            }
+        };
+
+        let asks = !matches!(variant.fields, Fields::Unit);
+        arms.push(if asks {
+            quote! {
+                #index => ::velvetio::with_context(#label, || {
+                    ::velvetio::Result::Ok(#build)
+                }),
+            }
+        } else {
+            quote!(#index => ::velvetio::Result::Ok(#build),)
+        });
+        labels.push(label);
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::velvetio::Interactive for #name #type_generics #where_clause {
+            fn interact_with(
+                prompt: &str,
+                policy: &::velvetio::AttemptPolicy,
+            ) -> ::velvetio::Result<Self> {
+                let variant = ::velvetio::__private::variant(prompt, &[#(#labels),*], policy)?;
+                match variant {
+                    #(#arms)*
+                    _ => ::core::unreachable!(),
+                }
+            }
+        }
+    })
+}
+
+// The string in `#[interactive(<key> = "...")]`, if given
+fn attribute(attrs: &[Attribute], key: &str) -> syn::Result<Option<String>> {
+    let mut found = None;
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("interactive"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(key) {
+                found = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else {
+                Err(meta.error(format!("expected `{}`", key)))
+            }
+        })?;
+    }
+    Ok(found)
+}
+
+// `DryRun` -> "Dry run"
+fn words(ident: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in ident.chars().enumerate() {
+        if i > 0 && c.is_uppercase() {
+            out.push(' ');
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+// `max_retries` -> "Max retries"
+fn sentence(ident: &str) -> String {
+    let spaced = ident.trim_start_matches("r#").replace('_', " ");
+    let mut chars = spaced.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => spaced,
+    }
+}